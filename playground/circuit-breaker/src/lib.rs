@@ -0,0 +1,193 @@
+// Circuit breaker shared by the playground programs. A `PauseState` PDA
+// (seeds `[b"pause_state"]`) lets its pauser freeze the mutating
+// instructions of a program during an incident, all at once (`All`) or one
+// by one. A paused instruction fails with `Errors::Paused`, and pausing or
+// unpausing emits an event.
+//
+// Unpausing one instruction while `All` is paused leaves it paused.
+//
+// Anchor checks that an `Account<PauseState>` is owned by `crate::ID`, and
+// `#[program]` only dispatches to the functions written in its module, so
+// the accounts and events are declared in each program by `pause_state!`,
+// next to the program's scopes:
+//
+//     #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+//     pub enum Scope {
+//         All,      // every scope below, always first
+//         Donate,   // donate
+//         Withdraw, // withdraw
+//     }
+//
+//     circuit_breaker::pause_state!(program = Day26Crowdfund, scope = Scope { All, Donate, Withdraw });
+//
+// and the program's handlers only forward to them:
+//
+//     pub fn initialize_pause_state(ctx: Context<InitializePauseState>) -> Result<()> {
+//         ctx.accounts.initialize(ctx.bumps.pause_state)
+//     }
+//
+//     pub fn pause(ctx: Context<SetPause>, scope: Scope) -> Result<()> {
+//         ctx.accounts.pause(scope)
+//     }
+//
+// The paused scopes are the bits of a `u8`, so there can be at most 8 of
+// them. `pause_state!` lists the variants to check that at compile time,
+// and fails to compile if the list is not the whole enum.
+//
+// The program's `Errors` needs `Paused` and `NotUpgradeAuthority` variants.
+//
+// Only the program's upgrade authority can create the `PauseState`, and it
+// becomes the pauser, so nobody can front-run the deployment to take the
+// role. Until then every instruction checking the pause fails with
+// `AccountNotInitialized`: `initialize_pause_state` is part of deploying.
+
+pub const PAUSE_STATE_SEED: &[u8] = b"pause_state";
+
+// Bit of the `All` scope, the first variant of every scope enum
+pub const ALL: u8 = 1;
+
+// Scopes a `PauseState` has room for, one bit each
+pub const MAX_SCOPES: u8 = u8::BITS as u8;
+
+// Bit of the scope with this variant index, below `MAX_SCOPES`
+pub const fn scope_bit(index: u8) -> u8 {
+    1 << index
+}
+
+// Whether `paused` (scope bits) stops the scope with this bit
+pub fn is_paused(paused: u8, bit: u8) -> bool {
+    paused & (ALL | bit) != 0
+}
+
+// Declares `PauseState`, its `PausedEvent` and `UnpausedEvent`, and the
+// `InitializePauseState` and `SetPause` accounts, for the program whose
+// `#[program]` module is `program` (as in `crate::program::<program>`) and
+// whose scopes are the variants of the `scope` enum. `crate` is the program
+// using it.
+#[allow(clippy::crate_in_macro_def)]
+#[macro_export]
+macro_rules! pause_state {
+    (program = $program:ident, scope = $scope:ident { $($variant:ident),+ $(,)? }) => {
+        const _: () = {
+            $(assert!(
+                ($scope::$variant as u8) < $crate::MAX_SCOPES,
+                concat!(stringify!($scope), "::", stringify!($variant), " has no bit left in PauseState::paused"),
+            );)+
+        };
+
+        impl $scope {
+            // Exhaustive, so every variant has been checked above
+            fn bit(self) -> u8 {
+                match self {
+                    $($scope::$variant => $crate::scope_bit($scope::$variant as u8),)+
+                }
+            }
+        }
+
+        #[account]
+        pub struct PauseState {
+            pub pauser: Pubkey,
+            pub paused: u8, // Scope bits
+            pub bump:   u8,
+        }
+
+        impl PauseState {
+            pub const SEED: &'static [u8] = $crate::PAUSE_STATE_SEED;
+
+            pub fn find_address() -> (Pubkey, u8) {
+                Pubkey::find_program_address(&[Self::SEED], &crate::ID)
+            }
+
+            pub fn is_paused(&self, scope: $scope) -> bool {
+                $crate::is_paused(self.paused, scope.bit())
+            }
+
+            pub fn require_not_paused(&self, scope: $scope) -> Result<()> {
+                require!(!self.is_paused(scope), crate::Errors::Paused);
+                Ok(())
+            }
+
+            pub fn pause(&mut self, scope: $scope) {
+                self.paused |= scope.bit();
+                emit!(PausedEvent { scope, pauser: self.pauser });
+            }
+
+            pub fn unpause(&mut self, scope: $scope) {
+                self.paused &= !scope.bit();
+                emit!(UnpausedEvent { scope, pauser: self.pauser });
+            }
+        }
+
+        #[event]
+        pub struct PausedEvent {
+            pub scope:  $scope,
+            pub pauser: Pubkey,
+        }
+
+        #[event]
+        pub struct UnpausedEvent {
+            pub scope:  $scope,
+            pub pauser: Pubkey,
+        }
+
+        #[derive(Accounts)]
+        pub struct InitializePauseState<'info> {
+            #[account(
+                init,
+                payer = signer,
+                space = ::std::mem::size_of::<PauseState>() + 8,
+                seeds = [PauseState::SEED],
+                bump
+            )]
+            pub pause_state: Account<'info, PauseState>,
+
+            #[account(mut)]
+            pub signer: Signer<'info>,
+
+            #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+            pub program: Program<'info, crate::program::$program>,
+
+            #[account(
+                constraint = program_data.upgrade_authority_address == Some(signer.key())
+                    @ crate::Errors::NotUpgradeAuthority
+            )]
+            pub program_data: Account<'info, ProgramData>,
+
+            pub system_program: Program<'info, System>,
+        }
+
+        impl<'info> InitializePauseState<'info> {
+            // The upgrade authority becomes the pauser
+            pub fn initialize(&mut self, bump: u8) -> Result<()> {
+                self.pause_state.pauser = self.signer.key();
+                self.pause_state.bump   = bump;
+                Ok(())
+            }
+        }
+
+        #[derive(Accounts)]
+        pub struct SetPause<'info> {
+            #[account(mut, seeds = [PauseState::SEED], bump = pause_state.bump, has_one = pauser)]
+            pub pause_state: Account<'info, PauseState>,
+
+            pub pauser: Signer<'info>,
+        }
+
+        impl<'info> SetPause<'info> {
+            pub fn pause(&mut self, scope: $scope) -> Result<()> {
+                self.pause_state.pause(scope);
+                Ok(())
+            }
+
+            pub fn unpause(&mut self, scope: $scope) -> Result<()> {
+                self.pause_state.unpause(scope);
+                Ok(())
+            }
+
+            pub fn set_pauser(&mut self, new_pauser: Pubkey) -> Result<()> {
+                self.pause_state.pauser = new_pauser;
+                Ok(())
+            }
+        }
+    };
+}
@@ -0,0 +1,98 @@
+// `pause_state!` in a program of its own, as the playground programs use it
+use anchor_lang::prelude::*;
+
+declare_id!("AZef6b3Gg35U75eKFemSNfbW9QvnV5Cs48vuxvQbEyTD");
+
+#[program]
+pub mod vault {
+    use super::*;
+
+    pub fn initialize_pause_state(ctx: Context<InitializePauseState>) -> Result<()> {
+        ctx.accounts.initialize(ctx.bumps.pause_state)
+    }
+
+    pub fn pause(ctx: Context<SetPause>, scope: Scope) -> Result<()> {
+        ctx.accounts.pause(scope)
+    }
+
+    pub fn unpause(ctx: Context<SetPause>, scope: Scope) -> Result<()> {
+        ctx.accounts.unpause(scope)
+    }
+
+    pub fn set_pauser(ctx: Context<SetPause>, new_pauser: Pubkey) -> Result<()> {
+        ctx.accounts.set_pauser(new_pauser)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Scope {
+    All,      // every scope below
+    Deposit,  // deposit
+    Withdraw, // withdraw
+}
+
+circuit_breaker::pause_state!(program = Vault, scope = Scope { All, Deposit, Withdraw });
+
+#[error_code]
+pub enum Errors {
+    #[msg("This instruction is paused")]
+    Paused,
+    #[msg("Signer is not the program's upgrade authority")]
+    NotUpgradeAuthority,
+}
+
+fn pause_state() -> PauseState {
+    PauseState { pauser: Pubkey::new_unique(), paused: 0, bump: 255 }
+}
+
+#[test]
+fn pauses_one_scope() {
+    let mut pause_state = pause_state();
+
+    pause_state.pause(Scope::Deposit);
+    assert!(pause_state.is_paused(Scope::Deposit));
+    assert!(!pause_state.is_paused(Scope::Withdraw));
+
+    pause_state.unpause(Scope::Deposit);
+    assert!(!pause_state.is_paused(Scope::Deposit));
+}
+
+#[test]
+fn all_pauses_every_scope() {
+    let mut pause_state = pause_state();
+
+    pause_state.pause(Scope::All);
+    assert!(pause_state.is_paused(Scope::Deposit));
+    assert!(pause_state.is_paused(Scope::Withdraw));
+
+    // still paused by `All`
+    pause_state.unpause(Scope::Withdraw);
+    assert!(pause_state.is_paused(Scope::Withdraw));
+
+    pause_state.unpause(Scope::All);
+    assert!(!pause_state.is_paused(Scope::Withdraw));
+}
+
+#[test]
+fn paused_scopes_fail_with_the_program_error() {
+    let mut pause_state = pause_state();
+    pause_state.pause(Scope::Withdraw);
+
+    assert!(pause_state.require_not_paused(Scope::Deposit).is_ok());
+    assert_eq!(pause_state.require_not_paused(Scope::Withdraw).unwrap_err(), error!(Errors::Paused));
+}
+
+#[test]
+fn address_uses_the_shared_seed() {
+    let (address, bump) = Pubkey::find_program_address(&[b"pause_state"], &ID);
+
+    assert_eq!(PauseState::SEED, circuit_breaker::PAUSE_STATE_SEED);
+    assert_eq!(PauseState::find_address(), (address, bump));
+}
+
+#[test]
+fn scope_bits() {
+    assert_eq!(circuit_breaker::scope_bit(Scope::All as u8), circuit_breaker::ALL);
+    assert!(circuit_breaker::is_paused(circuit_breaker::ALL, circuit_breaker::scope_bit(7)));
+    assert!(!circuit_breaker::is_paused(circuit_breaker::scope_bit(1), circuit_breaker::scope_bit(2)));
+}
@@ -0,0 +1,181 @@
+// Compute unit benchmarks: each case is simulated on its own and what it
+// consumed is compared against bench/baseline.csv.
+//
+//   anchor test                      measure, write target/bench/*, compare
+//   UPDATE_BASELINE=1 anchor test    same, then overwrite the baseline
+//
+// A case missing from the baseline fails the comparison too, so a new case
+// or a new baseline file has to be measured and committed with
+// UPDATE_BASELINE=1 before it can pass.
+//   CU_THRESHOLD=10 anchor test      tolerate up to 10% more than the baseline (default 5)
+//
+// With the bump allocator (src/allocator.rs) sized for the heap frame
+// requested below, which has its own baseline:
+//   ALLOCATOR=bump anchor test -- --features heap-frame-256k
+// Once both allocators were measured, target/bench/allocators.md compares
+// them on the `Vec` workloads.
+import * as anchor from "@coral-xyz/anchor";
+import * as fs from "fs";
+import * as path from "path";
+
+export const OUTPUT_DIR = path.join(__dirname, "..", "target", "bench");
+
+// HEAP_FRAME_BYTES in src/allocator.rs with its `heap-frame-256k` feature,
+// the frame every transaction must request. Requested with either allocator,
+// so that both pay for the same instructions.
+export const HEAP_FRAME_BYTES = 256 * 1024;
+
+// "default" or "bump", the allocator the program was built with
+export function allocator(): string {
+  return process.env.ALLOCATOR || "default";
+}
+
+// bench/baseline.csv, or bench/baseline-bump.csv
+export function baselineFile(allocator: string): string {
+  return path.join(__dirname, allocator === "default" ? "baseline.csv" : `baseline-${allocator}.csv`);
+}
+
+// target/bench/compute_units.csv, or compute_units-bump.csv
+export function resultsFile(allocator: string, extension: string): string {
+  const suffix = allocator === "default" ? "" : `-${allocator}`;
+  return path.join(OUTPUT_DIR, `compute_units${suffix}.${extension}`);
+}
+
+export type Row = { name: string; [metric: string]: number | string };
+
+// Simulates the instructions in one transaction, signature checks off. The
+// units consumed include the heap frame request.
+// `returnData` is what the last instruction returned, if anything.
+export async function simulate(
+  provider: anchor.AnchorProvider,
+  instructions: anchor.web3.TransactionInstruction[]
+): Promise<{ unitsConsumed: number; logs: string[]; returnData: Buffer | null }> {
+  const { blockhash } = await provider.connection.getLatestBlockhash();
+  const message = new anchor.web3.TransactionMessage({
+    payerKey       : provider.wallet.publicKey,
+    recentBlockhash: blockhash,
+    instructions   : [anchor.web3.ComputeBudgetProgram.requestHeapFrame({ bytes: HEAP_FRAME_BYTES }), ...instructions],
+  }).compileToV0Message();
+
+  const { value } = await provider.connection.simulateTransaction(
+    new anchor.web3.VersionedTransaction(message),
+    { sigVerify: false }
+  );
+  if (value.err) {
+    throw new Error(`simulation failed: ${JSON.stringify(value.err)}\n${(value.logs || []).join("\n")}`);
+  }
+
+  return {
+    unitsConsumed: value.unitsConsumed,
+    logs         : value.logs || [],
+    returnData   : value.returnData ? Buffer.from(value.returnData.data[0], "base64") : null,
+  };
+}
+
+export function readCsv(file: string): Row[] {
+  if (!fs.existsSync(file)) {
+    return [];
+  }
+
+  const [header, ...lines] = fs.readFileSync(file, "utf8").split("\n").filter((line) => line.trim() !== "");
+  const columns = header.split(",");
+
+  return lines.map((line) => {
+    const row: Row = { name: "" };
+    line.split(",").forEach((value, i) => {
+      row[columns[i]] = i === 0 ? value : Number(value);
+    });
+    return row;
+  });
+}
+
+export function writeCsv(file: string, rows: Row[], metrics: string[]) {
+  const lines = [["name", ...metrics].join(",")];
+  for (const row of rows) {
+    lines.push([row.name, ...metrics.map((metric) => row[metric])].join(","));
+  }
+  fs.mkdirSync(path.dirname(file), { recursive: true });
+  fs.writeFileSync(file, lines.join("\n") + "\n");
+}
+
+// One column per metric, plus its difference with the baseline
+export function writeMarkdown(file: string, rows: Row[], metrics: string[], baseline: Row[]) {
+  const lines = [
+    "| name | " + metrics.map((metric) => `${metric} | vs baseline`).join(" | ") + " |",
+    "|---|" + metrics.map(() => "---:|---:|").join(""),
+  ];
+
+  for (const row of rows) {
+    const base = baseline.find((b) => b.name === row.name);
+    const cells = metrics.map((metric) => {
+      const value = row[metric] as number;
+      if (!base || base[metric] === undefined) {
+        return `${value} | new`;
+      }
+      const diff = value - (base[metric] as number);
+      return `${value} | ${diff > 0 ? "+" : ""}${diff}`;
+    });
+    lines.push(`| ${row.name} | ${cells.join(" | ")} |`);
+  }
+
+  fs.mkdirSync(path.dirname(file), { recursive: true });
+  fs.writeFileSync(file, lines.join("\n") + "\n");
+}
+
+// The rows using more than `thresholdPercent` above their baseline, or
+// without a baseline to compare with, as messages
+export function regressions(rows: Row[], metrics: string[], baseline: Row[], thresholdPercent: number): string[] {
+  const found: string[] = [];
+
+  for (const row of rows) {
+    const base = baseline.find((b) => b.name === row.name);
+    for (const metric of metrics) {
+      if (!base || base[metric] === undefined || Number.isNaN(base[metric])) {
+        found.push(`${row.name}: no ${metric} in the baseline, measure it with UPDATE_BASELINE=1`);
+        continue;
+      }
+
+      const value = row[metric] as number;
+      const limit = (base[metric] as number) * (1 + thresholdPercent / 100);
+      if (value > limit) {
+        found.push(`${row.name}: ${metric} went from ${base[metric]} to ${value} (more than ${thresholdPercent}%)`);
+      }
+    }
+  }
+
+  return found;
+}
+
+export function threshold(): number {
+  return Number(process.env.CU_THRESHOLD || 5);
+}
+
+export function updateBaseline(): boolean {
+  return process.env.UPDATE_BASELINE === "1";
+}
+
+// Both allocators side by side, for the rows whose name starts with one of
+// `prefixes`. Only says which run is missing until both were measured.
+export function compareAllocators(file: string, prefixes: string[]) {
+  const defaults = readCsv(resultsFile("default", "csv"));
+  const bumps = readCsv(resultsFile("bump", "csv"));
+  if (defaults.length === 0 || bumps.length === 0) {
+    const missing = defaults.length === 0 ? "default" : "bump";
+    console.log(`no ${path.basename(file)} yet: run the benchmark with the ${missing} allocator too`);
+    return;
+  }
+
+  const lines = [
+    "| name | default CU | bump CU | default heap bytes | bump heap bytes |",
+    "|---|---:|---:|---:|---:|",
+  ];
+  for (const row of defaults) {
+    const bump = bumps.find((b) => b.name === row.name);
+    if (!bump || !prefixes.some((prefix) => row.name.startsWith(prefix))) {
+      continue;
+    }
+    lines.push(`| ${row.name} | ${row.compute_units} | ${bump.compute_units} | ${row.heap_bytes} | ${bump.heap_bytes} |`);
+  }
+
+  fs.writeFileSync(file, lines.join("\n") + "\n");
+}
@@ -0,0 +1,136 @@
+// A bump allocator for the whole requested heap frame, installed as the
+// global allocator with the `bump-allocator` feature.
+//
+// The default allocator only ever uses the first 32 KiB of the heap, even
+// when the transaction asks for more with `ComputeBudget::RequestHeapFrame`.
+// This one goes up to `HEAP_FRAME_BYTES` and returns null beyond, which the
+// program cannot check against the frame actually requested: every
+// transaction calling a program built with `heap-frame-256k` must request
+// exactly that frame, e.g. in TypeScript
+//     ComputeBudgetProgram.requestHeapFrame({ bytes: 256 * 1024 })
+// or an allocation past the mapped heap fails with an access violation.
+//
+// It allocates upward and frees, or grows in place, the last allocation
+// only, which is what a growing `Vec` needs. Its state lives in the first two
+// words of the heap: the runtime zeroes the heap for each instruction, so the
+// allocator starts over with every instruction, and `reset` starts it over
+// within one.
+use std::alloc::{GlobalAlloc, Layout};
+use std::mem::size_of;
+use std::ptr::null_mut;
+
+// 256 KiB, the largest frame a transaction can request, with the
+// `heap-frame-256k` feature, or else the 32 KiB every transaction gets
+// without asking
+#[cfg(feature = "heap-frame-256k")]
+pub const HEAP_FRAME_BYTES: usize = 256 * 1024;
+#[cfg(not(feature = "heap-frame-256k"))]
+pub const HEAP_FRAME_BYTES: usize = 32 * 1024;
+
+// Header at the start of the heap: [next free byte, high-water mark]
+const HEADER: usize = 2 * size_of::<usize>();
+
+pub struct BumpAllocator {
+    pub start: usize,
+    pub len:   usize,
+}
+
+impl BumpAllocator {
+    fn position(&self) -> *mut usize {
+        self.start as *mut usize
+    }
+
+    fn high_water(&self) -> *mut usize {
+        (self.start + size_of::<usize>()) as *mut usize
+    }
+
+    fn next_free(&self) -> usize {
+        match unsafe { *self.position() } {
+            0 => self.start + HEADER,
+            position => position,
+        }
+    }
+
+    fn set_next_free(&self, position: usize) {
+        unsafe {
+            *self.position() = position;
+            let used = position - self.start - HEADER;
+            if used > *self.high_water() {
+                *self.high_water() = used;
+            }
+        }
+    }
+
+    // Bytes currently allocated, alignment padding included
+    pub fn used(&self) -> usize {
+        self.next_free() - self.start - HEADER
+    }
+
+    // Largest `used` since the start of the instruction
+    pub fn high_water_mark(&self) -> usize {
+        unsafe { *self.high_water() }
+    }
+
+    /// Frees everything, the high-water mark is kept.
+    ///
+    /// # Safety
+    /// No allocation made so far may be used afterwards.
+    pub unsafe fn reset(&self) {
+        *self.position() = self.start + HEADER;
+    }
+}
+
+unsafe impl GlobalAlloc for BumpAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let start = (self.next_free() + layout.align() - 1) & !(layout.align() - 1);
+        let end = match start.checked_add(layout.size()) {
+            Some(end) if end <= self.start + self.len => end,
+            _ => return null_mut(),
+        };
+
+        self.set_next_free(end);
+        start as *mut u8
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        // Only the last allocation can be given back
+        if ptr as usize + layout.size() == self.next_free() {
+            *self.position() = ptr as usize;
+        }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        // The last allocation grows or shrinks in place, others only shrink
+        if ptr as usize + layout.size() == self.next_free() {
+            match (ptr as usize).checked_add(new_size) {
+                Some(end) if end <= self.start + self.len => {
+                    self.set_next_free(end);
+                    return ptr;
+                }
+                _ => return null_mut(),
+            }
+        }
+        if new_size <= layout.size() {
+            return ptr;
+        }
+
+        let new_layout = Layout::from_size_align_unchecked(new_size, layout.align());
+        let new_ptr = self.alloc(new_layout);
+        if !new_ptr.is_null() {
+            std::ptr::copy_nonoverlapping(ptr, new_ptr, layout.size());
+        }
+        new_ptr
+    }
+}
+
+#[cfg(all(feature = "bump-allocator", target_os = "solana"))]
+#[global_allocator]
+static ALLOCATOR: BumpAllocator = BumpAllocator {
+    start: anchor_lang::solana_program::entrypoint::HEAP_START_ADDRESS as usize,
+    len:   HEAP_FRAME_BYTES,
+};
+
+#[cfg(all(feature = "bump-allocator", target_os = "solana"))]
+pub fn global() -> &'static BumpAllocator {
+    &ALLOCATOR
+}
@@ -0,0 +1,29 @@
+// Heap usage, the high-water mark of the allocator.
+//
+// The default allocator, `solana_program::entrypoint::BumpAllocator`, hands
+// out memory downward from the end of the heap and never frees it. It keeps
+// its position in the first word of the heap, 0 until the first allocation,
+// so the bytes taken since the start of the instruction are the distance
+// from the end of the heap to that position.
+#[cfg(all(not(feature = "bump-allocator"), target_os = "solana"))]
+pub fn used() -> u64 {
+    use anchor_lang::solana_program::entrypoint::{HEAP_LENGTH, HEAP_START_ADDRESS};
+
+    let position = unsafe { *(HEAP_START_ADDRESS as *const usize) };
+    if position == 0 {
+        return 0;
+    }
+    (HEAP_START_ADDRESS as usize + HEAP_LENGTH - position) as u64
+}
+
+// The bump allocator keeps its own count, see allocator.rs
+#[cfg(all(feature = "bump-allocator", target_os = "solana"))]
+pub fn used() -> u64 {
+    crate::allocator::global().high_water_mark() as u64
+}
+
+// Off-chain (unit tests, IDL build) there is no SBF heap to look at
+#[cfg(not(target_os = "solana"))]
+pub fn used() -> u64 {
+    0
+}
@@ -1,4 +1,15 @@
+// The benchmarks measure `push` on purpose, rather than `vec![...]`
+#![allow(clippy::vec_init_then_push)]
+
 use anchor_lang::prelude::*;
+use std::collections::{BTreeMap, HashMap};
+use std::hint::black_box;
+
+pub mod allocator;
+pub mod heap;
+pub mod storage;
+
+use storage::*;
 
 declare_id!("H735G4dfsiFM6UdMURKvibWuzTX34NRjxbfK2EZhWnaY");
 
@@ -7,14 +18,29 @@ pub mod compute_unit {
     use super::*;
 
     pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
+        Ok(())
+    }
+
+    // One instruction per snippet, so that the benchmark in
+    // tests/compute_units.ts measures each of them on its own. The costs are
+    // recorded in bench/baseline.csv. `black_box` keeps the optimizer from
+    // removing vectors that are never read.
+    //
+    // Every benchmark returns the heap bytes used by the instruction.
+
+    // The element type is inferred: i32
+    pub fn push_inferred(_ctx: Context<Initialize>) -> Result<u64> {
         let mut a = Vec::new();
         a.push(1);
         a.push(2);
         a.push(3);
         a.push(4);
         a.push(5);
+        black_box(a);
+        Ok(heap::used())
+    }
 
-        // this costs 618 CU
+    pub fn push_u64(_ctx: Context<Initialize>) -> Result<u64> {
         let mut a: Vec<u64> = Vec::new();
         a.push(1);
         a.push(1);
@@ -22,8 +48,12 @@ pub mod compute_unit {
         a.push(1);
         a.push(1);
         a.push(1);
+        black_box(a);
+        Ok(heap::used())
+    }
 
-        // this costs 600 CU (same as the first one but the type was explicitly denoted)
+    // Same as push_inferred, but the type is explicit
+    pub fn push_i32(_ctx: Context<Initialize>) -> Result<u64> {
         let mut a: Vec<i32> = Vec::new();
         a.push(1);
         a.push(1);
@@ -31,8 +61,12 @@ pub mod compute_unit {
         a.push(1);
         a.push(1);
         a.push(1);
+        black_box(a);
+        Ok(heap::used())
+    }
 
-        // this costs 618 CU (takes the same space as u64)
+    // Takes the same space as u64
+    pub fn push_i64(_ctx: Context<Initialize>) -> Result<u64> {
         let mut a: Vec<i64> = Vec::new();
         a.push(1);
         a.push(1);
@@ -40,8 +74,11 @@ pub mod compute_unit {
         a.push(1);
         a.push(1);
         a.push(1);
+        black_box(a);
+        Ok(heap::used())
+    }
 
-        // this costs 459 CU
+    pub fn push_u8(_ctx: Context<Initialize>) -> Result<u64> {
         let mut a: Vec<u8> = Vec::new();
         a.push(1);
         a.push(1);
@@ -49,10 +86,136 @@ pub mod compute_unit {
         a.push(1);
         a.push(1);
         a.push(1);
+        black_box(a);
+        Ok(heap::used())
+    }
+
+    // Storage comparison: the same `n` values in different places
+
+    pub fn vec_new(_ctx: Context<Initialize>, n: u16) -> Result<u64> {
+        let mut values = Vec::new();
+        for i in 0..n as u64 {
+            values.push(i);
+        }
+        black_box(values);
+        Ok(heap::used())
+    }
+
+    pub fn vec_with_capacity(_ctx: Context<Initialize>, n: u16) -> Result<u64> {
+        let mut values = Vec::with_capacity(n as usize);
+        for i in 0..n as u64 {
+            values.push(i);
+        }
+        black_box(values);
+        Ok(heap::used())
+    }
+
+    pub fn fixed_array(_ctx: Context<Initialize>, n: u16) -> Result<u64> {
+        require!(n as usize <= MAX_ITEMS, Errors::TooManyItems);
+
+        let mut values = [0u64; MAX_ITEMS];
+        for (i, value) in values.iter_mut().take(n as usize).enumerate() {
+            *value = i as u64;
+        }
+        black_box(values);
+        Ok(heap::used())
+    }
+
+    pub fn inline_buffer(_ctx: Context<Initialize>, n: u16) -> Result<u64> {
+        let mut values = InlineBuffer::<u64, INLINE_ITEMS>::new();
+        for i in 0..n as u64 {
+            values.push(i);
+        }
+        black_box(values.as_slice());
+        Ok(heap::used())
+    }
 
+    // Lookup comparison: `n` entries, each looked up once
+
+    pub fn hash_map_lookup(_ctx: Context<Initialize>, n: u16) -> Result<u64> {
+        let map: HashMap<u64, u64> = (0..n as u64).map(|i| (lookup_key(i), i)).collect();
+
+        let mut sum = 0u64;
+        for i in 0..n as u64 {
+            sum += map[&lookup_key(black_box(i))];
+        }
+        black_box(sum);
+        Ok(heap::used())
+    }
+
+    pub fn btree_map_lookup(_ctx: Context<Initialize>, n: u16) -> Result<u64> {
+        let map: BTreeMap<u64, u64> = (0..n as u64).map(|i| (lookup_key(i), i)).collect();
+
+        let mut sum = 0u64;
+        for i in 0..n as u64 {
+            sum += map[&lookup_key(black_box(i))];
+        }
+        black_box(sum);
+        Ok(heap::used())
+    }
+
+    pub fn sorted_vec_lookup(_ctx: Context<Initialize>, n: u16) -> Result<u64> {
+        let mut entries: Vec<(u64, u64)> = (0..n as u64).map(|i| (lookup_key(i), i)).collect();
+        entries.sort_unstable();
+
+        let mut sum = 0u64;
+        for i in 0..n as u64 {
+            let key = lookup_key(black_box(i));
+            let index = entries.binary_search_by_key(&key, |&(k, _)| k).unwrap();
+            sum += entries[index].1;
+        }
+        black_box(sum);
+        Ok(heap::used())
+    }
+
+    // Account storage comparison, see storage.rs
+
+    pub fn initialize_storage(ctx: Context<InitializeStorage>) -> Result<()> {
+        ctx.accounts.zero_copy_storage.load_init()?;
         Ok(())
     }
+
+    // The heap is measured before Anchor's `exit` serializes the account
+    // back. That write goes straight into the account data, without
+    // allocating, so it adds nothing to heap_bytes; its cost only shows in
+    // compute_units, which the simulation counts to the end.
+    pub fn write_borsh(ctx: Context<WriteBorsh>, n: u16) -> Result<u64> {
+        require!(n as usize <= MAX_ITEMS, Errors::TooManyItems);
+
+        let values = &mut ctx.accounts.borsh_storage.values;
+        values.clear();
+        for i in 0..n as u64 {
+            values.push(i);
+        }
+        Ok(heap::used())
+    }
+
+    pub fn write_zero_copy(ctx: Context<WriteZeroCopy>, n: u16) -> Result<u64> {
+        require!(n as usize <= MAX_ITEMS, Errors::TooManyItems);
+
+        let mut storage = ctx.accounts.zero_copy_storage.load_mut()?;
+        for (i, value) in storage.values.iter_mut().take(n as usize).enumerate() {
+            *value = i as u64;
+        }
+        Ok(heap::used())
+    }
+
+    pub fn write_account_data(ctx: Context<WriteAccountData>, n: u16) -> Result<u64> {
+        require!(n as usize <= MAX_ITEMS, Errors::TooManyItems);
+
+        let mut data = ctx.accounts.raw_storage.try_borrow_mut_data()?;
+        for (i, chunk) in data.chunks_exact_mut(8).take(n as usize).enumerate() {
+            chunk.copy_from_slice(&(i as u64).to_le_bytes());
+        }
+        Ok(heap::used())
+    }
 }
 
 #[derive(Accounts)]
 pub struct Initialize {}
+
+#[error_code]
+pub enum Errors {
+    #[msg("More items than the storage can hold")]
+    TooManyItems,
+}
@@ -0,0 +1,121 @@
+// The same data on different storage, for the benchmarks in
+// tests/compute_units.ts: vectors, arrays and inline buffers in memory, and
+// three ways to lay values out in an account.
+use anchor_lang::prelude::*;
+use std::mem::size_of;
+
+// Capacity of the fixed-size arrays and of the storage accounts
+pub const MAX_ITEMS: usize = 128;
+
+// Items kept inline by `InlineBuffer` before it moves them to the heap
+pub const INLINE_ITEMS: usize = 32;
+
+// A `SmallVec`-style buffer: the first N items live in an array, on the
+// stack, and only a longer buffer spills to a heap-allocated `Vec`.
+pub struct InlineBuffer<T: Copy + Default, const N: usize> {
+    inline: [T; N],
+    len:    usize,
+    spill:  Vec<T>,
+}
+
+impl<T: Copy + Default, const N: usize> InlineBuffer<T, N> {
+    pub fn new() -> Self {
+        InlineBuffer { inline: [T::default(); N], len: 0, spill: Vec::new() }
+    }
+
+    pub fn push(&mut self, value: T) {
+        if self.len < N {
+            self.inline[self.len] = value;
+        } else {
+            if self.len == N {
+                self.spill.extend_from_slice(&self.inline);
+            }
+            self.spill.push(value);
+        }
+        self.len += 1;
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        if self.len <= N {
+            &self.inline[..self.len]
+        } else {
+            &self.spill
+        }
+    }
+}
+
+impl<T: Copy + Default, const N: usize> Default for InlineBuffer<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Lookup keys spread over the u64 range (Knuth's multiplicative hash), so
+// that the maps do not get them in order
+pub fn lookup_key(i: u64) -> u64 {
+    i.wrapping_mul(2_654_435_761)
+}
+
+// Deserialized and serialized again by Anchor, as a whole, every time
+#[account]
+pub struct BorshStorage {
+    pub values: Vec<u64>,
+}
+
+impl BorshStorage {
+    pub const SPACE: usize = 8 + 4 + MAX_ITEMS * size_of::<u64>();
+}
+
+// Read and written in place in the account data.
+//
+// In a module of its own for the `allow`: the `Pod` derive behind
+// `#[account(zero_copy)]` checks for padding with a tuple struct whose field
+// is never read, an item next to the struct that an `allow` on it misses.
+pub use zero_copy::ZeroCopyStorage;
+
+#[allow(dead_code)]
+mod zero_copy {
+    use super::*;
+
+    #[account(zero_copy)]
+    pub struct ZeroCopyStorage {
+        pub values: [u64; MAX_ITEMS],
+    }
+}
+
+#[derive(Accounts)]
+pub struct InitializeStorage<'info> {
+    #[account(init, payer = signer, space = BorshStorage::SPACE)]
+    pub borsh_storage: Account<'info, BorshStorage>,
+
+    #[account(init, payer = signer, space = size_of::<ZeroCopyStorage>() + 8)]
+    pub zero_copy_storage: AccountLoader<'info, ZeroCopyStorage>,
+
+    /// CHECK: plain bytes written by `write_account_data`, no discriminator
+    #[account(init, payer = signer, space = MAX_ITEMS * size_of::<u64>(), owner = crate::ID)]
+    pub raw_storage: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WriteBorsh<'info> {
+    #[account(mut)]
+    pub borsh_storage: Account<'info, BorshStorage>,
+}
+
+#[derive(Accounts)]
+pub struct WriteZeroCopy<'info> {
+    #[account(mut)]
+    pub zero_copy_storage: AccountLoader<'info, ZeroCopyStorage>,
+}
+
+#[derive(Accounts)]
+pub struct WriteAccountData<'info> {
+    /// CHECK: only the program can write to it, see `InitializeStorage`
+    #[account(mut, owner = crate::ID)]
+    pub raw_storage: UncheckedAccount<'info>,
+}
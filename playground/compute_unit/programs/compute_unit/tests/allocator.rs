@@ -0,0 +1,74 @@
+use compute_unit::allocator::BumpAllocator;
+use std::alloc::{GlobalAlloc, Layout};
+
+// An allocator over a zeroed buffer, as the runtime hands out the heap
+fn allocator(buffer: &mut [u64]) -> BumpAllocator {
+    BumpAllocator { start: buffer.as_mut_ptr() as usize, len: std::mem::size_of_val(buffer) }
+}
+
+#[test]
+fn allocates_upward_with_alignment() {
+    let mut buffer = vec![0u64; 64];
+    let heap = allocator(&mut buffer);
+
+    unsafe {
+        let a = heap.alloc(Layout::from_size_align(3, 1).unwrap());
+        let b = heap.alloc(Layout::from_size_align(8, 8).unwrap());
+        assert_eq!(b as usize - a as usize, 8);
+        assert_eq!(b as usize % 8, 0);
+        assert_eq!(heap.used(), 16);
+        assert_eq!(heap.high_water_mark(), 16);
+    }
+}
+
+#[test]
+fn frees_and_grows_the_last_allocation_in_place() {
+    let mut buffer = vec![0u64; 64];
+    let heap = allocator(&mut buffer);
+
+    unsafe {
+        let layout = Layout::from_size_align(16, 8).unwrap();
+        let a = heap.alloc(layout);
+        let grown = heap.realloc(a, layout, 64);
+        assert_eq!(grown, a);
+        assert_eq!(heap.used(), 64);
+
+        heap.dealloc(grown, Layout::from_size_align(64, 8).unwrap());
+        assert_eq!(heap.used(), 0);
+        assert_eq!(heap.high_water_mark(), 64);
+    }
+}
+
+#[test]
+fn moves_an_allocation_that_is_not_the_last() {
+    let mut buffer = vec![0u64; 64];
+    let heap = allocator(&mut buffer);
+
+    unsafe {
+        let layout = Layout::from_size_align(8, 8).unwrap();
+        let a = heap.alloc(layout);
+        *(a as *mut u64) = 42;
+        heap.alloc(layout);
+
+        let moved = heap.realloc(a, layout, 16);
+        assert_ne!(moved, a);
+        assert_eq!(*(moved as *mut u64), 42);
+        assert_eq!(heap.used(), 32);
+    }
+}
+
+#[test]
+fn runs_out_of_memory_and_resets() {
+    let mut buffer = vec![0u64; 8]; // 16 bytes of header, 48 for allocations
+    let heap = allocator(&mut buffer);
+
+    unsafe {
+        assert!(!heap.alloc(Layout::from_size_align(48, 8).unwrap()).is_null());
+        assert!(heap.alloc(Layout::from_size_align(1, 1).unwrap()).is_null());
+
+        heap.reset();
+        assert_eq!(heap.used(), 0);
+        assert_eq!(heap.high_water_mark(), 48);
+        assert!(!heap.alloc(Layout::from_size_align(1, 1).unwrap()).is_null());
+    }
+}
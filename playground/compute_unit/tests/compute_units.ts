@@ -0,0 +1,90 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { ComputeUnit } from "../target/types/compute_unit";
+import { expect } from "chai";
+import * as path from "path";
+import * as bench from "../bench/harness";
+
+describe("compute units", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.ComputeUnit as Program<ComputeUnit>;
+
+  const borshStorage    = anchor.web3.Keypair.generate();
+  const zeroCopyStorage = anchor.web3.Keypair.generate();
+  const rawStorage      = anchor.web3.Keypair.generate();
+
+  // name in the baseline -> instruction. Every benchmark returns the heap
+  // bytes it used.
+  const m = program.methods;
+  const cases: [string, () => Promise<anchor.web3.TransactionInstruction>][] = [
+    ["push_inferred", () => m.pushInferred().instruction()],
+    ["push_u64",      () => m.pushU64().instruction()],
+    ["push_i32",      () => m.pushI32().instruction()],
+    ["push_i64",      () => m.pushI64().instruction()],
+    ["push_u8",       () => m.pushU8().instruction()],
+  ];
+
+  // In memory, small enough for the inline buffer then not
+  for (const n of [16, 64]) {
+    cases.push(
+      [`vec_new_${n}`,           () => m.vecNew(n).instruction()],
+      [`vec_with_capacity_${n}`, () => m.vecWithCapacity(n).instruction()],
+      [`fixed_array_${n}`,       () => m.fixedArray(n).instruction()],
+      [`inline_buffer_${n}`,     () => m.inlineBuffer(n).instruction()],
+      [`hash_map_lookup_${n}`,   () => m.hashMapLookup(n).instruction()],
+      [`btree_map_lookup_${n}`,  () => m.btreeMapLookup(n).instruction()],
+      [`sorted_vec_lookup_${n}`, () => m.sortedVecLookup(n).instruction()],
+    );
+  }
+
+  // In an account
+  for (const n of [16, 128]) {
+    cases.push(
+      [`write_borsh_${n}`,        () => m.writeBorsh(n).accounts({ borshStorage: borshStorage.publicKey }).instruction()],
+      [`write_zero_copy_${n}`,    () => m.writeZeroCopy(n).accounts({ zeroCopyStorage: zeroCopyStorage.publicKey }).instruction()],
+      [`write_account_data_${n}`, () => m.writeAccountData(n).accounts({ rawStorage: rawStorage.publicKey }).instruction()],
+    );
+  }
+
+  const metrics   = ["compute_units", "heap_bytes"];
+  const allocator = bench.allocator();
+  const baseline  = bench.readCsv(bench.baselineFile(allocator));
+  const rows: bench.Row[] = [];
+
+  before(async () => {
+    await m
+      .initializeStorage()
+      .accounts({
+        borshStorage   : borshStorage.publicKey,
+        zeroCopyStorage: zeroCopyStorage.publicKey,
+        rawStorage     : rawStorage.publicKey,
+        signer         : provider.wallet.publicKey,
+      })
+      .signers([borshStorage, zeroCopyStorage, rawStorage])
+      .rpc();
+  });
+
+  for (const [name, instruction] of cases) {
+    it(`measures ${name}`, async () => {
+      const { unitsConsumed, returnData } = await bench.simulate(provider, [await instruction()]);
+      rows.push({ name, compute_units: unitsConsumed, heap_bytes: new anchor.BN(returnData, "le").toNumber() });
+    });
+  }
+
+  it("does not regress against the baseline", () => {
+    bench.writeCsv(bench.resultsFile(allocator, "csv"), rows, metrics);
+    bench.writeMarkdown(bench.resultsFile(allocator, "md"), rows, metrics, baseline);
+    bench.compareAllocators(path.join(bench.OUTPUT_DIR, "allocators.md"), ["push_", "vec_"]);
+    console.log(`allocator: ${allocator}`);
+    console.table(rows);
+
+    if (bench.updateBaseline()) {
+      bench.writeCsv(bench.baselineFile(allocator), rows, metrics);
+      return;
+    }
+
+    expect(bench.regressions(rows, metrics, baseline, bench.threshold())).to.deep.equal([]);
+  });
+});
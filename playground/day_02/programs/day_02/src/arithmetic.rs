@@ -0,0 +1,98 @@
+// Integer arithmetic with an explicit overflow policy.
+//
+// Every operation comes in three modes, as in the standard library:
+// - Checked: an overflow is an error, `IntegerOverflow` when the exact result
+//   is above the type's maximum, `IntegerUnderflow` when below its minimum
+// - Wrapping: the result wraps around (two's complement)
+// - Saturating: the result is clamped to the type's minimum or maximum
+//
+// Dividing by zero is `IntegerDivisionByZero` whatever the mode, since no
+// result makes sense. Plain `+`, `-`, `*` and `/` panic on overflow instead,
+// as `overflow-checks` is on in the release profile (see Cargo.toml).
+use anchor_lang::prelude::*;
+use std::fmt;
+
+use crate::Errors;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Op {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mode {
+    Checked,
+    Wrapping,
+    Saturating,
+}
+
+// The primitive integers, u8 to u128 and i8 to i128
+pub trait Integer: Copy + PartialEq + fmt::Display {
+    const ZERO: Self;
+    const MAX: Self;
+
+    fn checked(self, op: Op, rhs: Self) -> Option<Self>;
+    fn wrapping(self, op: Op, rhs: Self) -> Self;
+    fn saturating(self, op: Op, rhs: Self) -> Self;
+}
+
+macro_rules! impl_integer {
+    ($($t:ty),*) => {
+        $(
+            impl Integer for $t {
+                const ZERO: Self = 0;
+                const MAX: Self = <$t>::MAX;
+
+                fn checked(self, op: Op, rhs: Self) -> Option<Self> {
+                    match op {
+                        Op::Add => self.checked_add(rhs),
+                        Op::Sub => self.checked_sub(rhs),
+                        Op::Mul => self.checked_mul(rhs),
+                        Op::Div => self.checked_div(rhs),
+                    }
+                }
+
+                fn wrapping(self, op: Op, rhs: Self) -> Self {
+                    match op {
+                        Op::Add => self.wrapping_add(rhs),
+                        Op::Sub => self.wrapping_sub(rhs),
+                        Op::Mul => self.wrapping_mul(rhs),
+                        Op::Div => self.wrapping_div(rhs),
+                    }
+                }
+
+                fn saturating(self, op: Op, rhs: Self) -> Self {
+                    match op {
+                        Op::Add => self.saturating_add(rhs),
+                        Op::Sub => self.saturating_sub(rhs),
+                        Op::Mul => self.saturating_mul(rhs),
+                        Op::Div => self.saturating_div(rhs),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
+pub fn apply<T: Integer>(op: Op, mode: Mode, a: T, b: T) -> Result<T> {
+    require!(op != Op::Div || b != T::ZERO, Errors::IntegerDivisionByZero);
+
+    let result = match mode {
+        Mode::Checked => match a.checked(op, b) {
+            Some(result) => result,
+            // The saturated result tells on which side it overflowed
+            None if a.saturating(op, b) == T::MAX => return err!(Errors::IntegerOverflow),
+            None => return err!(Errors::IntegerUnderflow),
+        },
+        Mode::Wrapping => a.wrapping(op, b),
+        Mode::Saturating => a.saturating(op, b),
+    };
+
+    msg!("{:?} {:?}: {} and {} = {}", mode, op, a, b, result);
+    Ok(result)
+}
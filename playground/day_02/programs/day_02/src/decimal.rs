@@ -0,0 +1,292 @@
+// Fixed-point decimal numbers, a deterministic replacement for f32.
+//
+// A `Decimal` is `value / 10^scale`, with a sign: `{ value: 12345, scale: 2,
+// negative: true }` is -123.45. The scale goes up to `MAX_SCALE`, and when
+// two operands have different scales the result has the larger one.
+//
+// Everything is integer math on u128 and checked: an overflow, a division by
+// zero, the square root of a negative number or the log of a non-positive
+// one return an error instead of producing inf or NaN. mul, div and sqrt
+// scale their operands up before dividing back down, so their intermediate
+// products are 256 bits wide: only a result that does not fit overflows.
+//
+// Rounding: add, sub, mul, div and sqrt truncate toward zero, while ln and
+// log10, which are approximations, round to the nearest value at the scale of
+// their input (so `log10(10000)` is exactly 4, even at scale 0).
+use anchor_lang::prelude::*;
+use std::cmp::Ordering;
+use std::fmt;
+
+use crate::Errors;
+
+pub const MAX_SCALE: u8 = 18;
+
+// Internal precision of ln and log10: 18 decimals
+const ONE: u128 = 1_000_000_000_000_000_000;
+const LN_2: u128 = 693_147_180_559_945_309; // ln(2) * 10^18
+const LN_10: u128 = 2_302_585_092_994_045_684; // ln(10) * 10^18
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct Decimal {
+    pub value:    u128,
+    pub scale:    u8,
+    pub negative: bool,
+}
+
+fn pow10(exponent: u8) -> u128 {
+    10u128.pow(exponent as u32)
+}
+
+impl Decimal {
+    pub fn new(value: u128, scale: u8, negative: bool) -> Result<Decimal> {
+        require!(scale <= MAX_SCALE, Errors::InvalidScale);
+        // No negative zero, so that equal numbers have equal fields
+        Ok(Decimal { value, scale, negative: negative && value != 0 })
+    }
+
+    pub fn from_integer(value: u64) -> Decimal {
+        Decimal { value: value as u128, scale: 0, negative: false }
+    }
+
+    // Decimals deserialized from instruction arguments skip `new`, so every
+    // operation checks the scale before computing 10^scale
+    fn check_scale(&self) -> Result<()> {
+        require!(self.scale <= MAX_SCALE, Errors::InvalidScale);
+        Ok(())
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.value == 0
+    }
+
+    // Same number with another scale, truncated when the scale decreases
+    pub fn rescale(self, scale: u8) -> Result<Decimal> {
+        self.check_scale()?;
+        require!(scale <= MAX_SCALE, Errors::InvalidScale);
+
+        let value = match scale.cmp(&self.scale) {
+            Ordering::Equal => self.value,
+            Ordering::Greater => self.value.checked_mul(pow10(scale - self.scale)).ok_or(Errors::DecimalOverflow)?,
+            Ordering::Less => self.value / pow10(self.scale - scale),
+        };
+
+        Decimal::new(value, scale, self.negative)
+    }
+
+    // Both operands at their common (larger) scale
+    fn aligned(self, other: Decimal) -> Result<(Decimal, Decimal)> {
+        self.check_scale()?;
+        other.check_scale()?;
+        let scale = self.scale.max(other.scale);
+        Ok((self.rescale(scale)?, other.rescale(scale)?))
+    }
+
+    pub fn checked_add(self, other: Decimal) -> Result<Decimal> {
+        let (a, b) = self.aligned(other)?;
+
+        if a.negative == b.negative {
+            let value = a.value.checked_add(b.value).ok_or(Errors::DecimalOverflow)?;
+            return Decimal::new(value, a.scale, a.negative);
+        }
+
+        // Opposite signs: the larger magnitude wins
+        match a.value.cmp(&b.value) {
+            Ordering::Less => Decimal::new(b.value - a.value, a.scale, b.negative),
+            _ => Decimal::new(a.value - b.value, a.scale, a.negative),
+        }
+    }
+
+    pub fn checked_sub(self, other: Decimal) -> Result<Decimal> {
+        self.checked_add(Decimal { negative: !other.negative, ..other })
+    }
+
+    pub fn checked_mul(self, other: Decimal) -> Result<Decimal> {
+        let (a, b) = self.aligned(other)?;
+        let value = mul_div(a.value, b.value, pow10(a.scale)).ok_or(Errors::DecimalOverflow)?;
+
+        Decimal::new(value, a.scale, a.negative != b.negative)
+    }
+
+    pub fn checked_div(self, other: Decimal) -> Result<Decimal> {
+        let (a, b) = self.aligned(other)?;
+        require!(!b.is_zero(), Errors::DecimalDivisionByZero);
+
+        let value = mul_div(a.value, pow10(a.scale), b.value).ok_or(Errors::DecimalOverflow)?;
+        Decimal::new(value, a.scale, a.negative != b.negative)
+    }
+
+    // Square root, at the same scale
+    pub fn sqrt(self) -> Result<Decimal> {
+        self.check_scale()?;
+        require!(!self.negative, Errors::NegativeSqrt);
+
+        // sqrt(v / 10^s) = sqrt(v * 10^s) / 10^s
+        let (high, low) = mul_wide(self.value, pow10(self.scale));
+        Decimal::new(isqrt(high, low), self.scale, false)
+    }
+
+    // Natural logarithm, at the same scale
+    pub fn ln(self) -> Result<Decimal> {
+        let ln = self.ln_fixed()?;
+        Decimal::from_fixed(ln, self.scale)
+    }
+
+    // Base 10 logarithm, at the same scale
+    pub fn log10(self) -> Result<Decimal> {
+        let ln = self.ln_fixed()?;
+        let magnitude = ln.unsigned_abs().checked_mul(ONE).ok_or(Errors::DecimalOverflow)? / LN_10;
+        Decimal::from_fixed(if ln < 0 { -(magnitude as i128) } else { magnitude as i128 }, self.scale)
+    }
+
+    // ln(self) * 10^18, accurate to about 10^-17.
+    //
+    // self = m * 2^k with m in [1, 2), so ln(self) = k * ln(2) + ln(m), and
+    // ln(m) = 2 * atanh(t) = 2 * (t + t^3/3 + t^5/5 + ...) with
+    // t = (m - 1) / (m + 1), which is below 1/3 so the series converges fast.
+    fn ln_fixed(self) -> Result<i128> {
+        self.check_scale()?;
+        require!(!self.negative && !self.is_zero(), Errors::LogOfNonPositive);
+
+        // self * 10^18 is up to 188 bits wide: shift it into a u128 first, the
+        // shifted bits going to k, instead of rescaling self to MAX_SCALE,
+        // which overflows above about 3.4 * 10^20
+        let (high, low) = mul_wide(self.value, pow10(MAX_SCALE - self.scale));
+        let shift = 128 - high.leading_zeros();
+        let mut m = if shift == 0 { low } else { (low >> shift) | (high << (128 - shift)) };
+        let mut k = shift as i128;
+        while m >= 2 * ONE {
+            m /= 2;
+            k += 1;
+        }
+        while m < ONE {
+            m *= 2;
+            k -= 1;
+        }
+
+        let t = (m - ONE) * ONE / (m + ONE);
+        let t_squared = t * t / ONE;
+
+        let mut sum = t;
+        let mut power = t;
+        let mut n = 3;
+        loop {
+            power = power * t_squared / ONE;
+            if power == 0 {
+                break;
+            }
+            sum += power / n;
+            n += 2;
+        }
+
+        Ok(k * LN_2 as i128 + 2 * sum as i128)
+    }
+
+    // From a value * 10^18, rounded to the nearest at `scale`
+    fn from_fixed(fixed: i128, scale: u8) -> Result<Decimal> {
+        let divisor = pow10(MAX_SCALE - scale);
+        let value = (fixed.unsigned_abs() + divisor / 2) / divisor;
+
+        Decimal::new(value, scale, fixed < 0)
+    }
+}
+
+// a * b as 256 bits, (high, low)
+fn mul_wide(a: u128, b: u128) -> (u128, u128) {
+    const MASK: u128 = u64::MAX as u128;
+    let (a_high, a_low) = (a >> 64, a & MASK);
+    let (b_high, b_low) = (b >> 64, b & MASK);
+
+    let low_low = a_low * b_low;
+    let high_low = a_high * b_low;
+    let low_high = a_low * b_high;
+    let high_high = a_high * b_high;
+
+    // The middle partial products, plus the carry out of the low one
+    let middle = (low_low >> 64) + (high_low & MASK) + (low_high & MASK);
+    let low = (middle << 64) | (low_low & MASK);
+    let high = high_high + (high_low >> 64) + (low_high >> 64) + (middle >> 64);
+    (high, low)
+}
+
+// (high, low) / divisor, truncated, or None when it does not fit in u128
+fn div_wide(high: u128, low: u128, divisor: u128) -> Option<u128> {
+    if high >= divisor {
+        return None;
+    }
+
+    // Long division, one bit at a time: `remainder` stays below `divisor`,
+    // and `carry` is its bit shifted out of the u128
+    let mut remainder = high;
+    let mut quotient = 0;
+    for bit in (0..128).rev() {
+        let carry = remainder >> 127;
+        remainder = (remainder << 1) | ((low >> bit) & 1);
+        quotient <<= 1;
+        if carry == 1 || remainder >= divisor {
+            remainder = remainder.wrapping_sub(divisor);
+            quotient |= 1;
+        }
+    }
+    Some(quotient)
+}
+
+// a * b / divisor, truncated, without overflowing in between
+fn mul_div(a: u128, b: u128, divisor: u128) -> Option<u128> {
+    let (high, low) = mul_wide(a, b);
+    div_wide(high, low, divisor)
+}
+
+// Largest r with r * r <= (high, low) (Newton's method)
+fn isqrt(high: u128, low: u128) -> u128 {
+    if high == 0 && low < 2 {
+        return low;
+    }
+
+    // Start above the root: 2^ceil(bits / 2), which is at most 2^128, so the
+    // largest u128 when it would not fit. `div_ceil` is newer than the Solana
+    // toolchain's rustc.
+    let bits = if high == 0 { 128 - low.leading_zeros() } else { 256 - high.leading_zeros() };
+    #[allow(clippy::manual_div_ceil)]
+    let mut x = 1u128.checked_shl((bits + 1) / 2).unwrap_or(u128::MAX);
+    loop {
+        // x is above the root, so n / x is below x and fits
+        let quotient = div_wide(high, low, x).unwrap();
+        // (x + n / x) / 2, without overflowing
+        let y = x / 2 + quotient / 2 + (x % 2 + quotient % 2) / 2;
+        if y >= x {
+            return x;
+        }
+        x = y;
+    }
+}
+
+// Compares the numbers, not the fields: 1.50 == 1.5
+impl PartialEq for Decimal {
+    fn eq(&self, other: &Self) -> bool {
+        match self.aligned(*other) {
+            Ok((a, b)) => a.value == b.value && (a.negative == b.negative || a.value == 0),
+            // One side does not fit at the common scale, so they differ, or a
+            // scale is invalid and only the same fields are equal
+            Err(_) => (self.value, self.scale, self.negative) == (other.value, other.scale, other.negative),
+        }
+    }
+}
+
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.negative { "-" } else { "" };
+        if self.scale == 0 {
+            return write!(f, "{}{}", sign, self.value);
+        }
+
+        let divisor = pow10(self.scale.min(MAX_SCALE));
+        write!(
+            f,
+            "{}{}.{:0width$}",
+            sign,
+            self.value / divisor,
+            self.value % divisor,
+            width = self.scale as usize
+        )
+    }
+}
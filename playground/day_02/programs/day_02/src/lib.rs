@@ -1,5 +1,12 @@
 use anchor_lang::prelude::*;
 
+pub mod arithmetic;
+pub mod decimal;
+
+use arithmetic::{Mode, Op};
+use decimal::Decimal;
+use macro_demo::trace;
+
 declare_id!("6Q3jZR97LHruhME6ZLG2fyyxPf64hS2jcnZr5eCvt8iB");
 
 #[program]
@@ -22,41 +29,148 @@ pub mod day_02 {
         Ok(())
     }
 
+    // Plain arithmetic: with `overflow-checks` on in the release profile,
+    // 0 - 1 panics instead of wrapping around to u64::MAX
     pub fn exercises(ctx: Context<Initialize>, a: u64, b: u64) -> Result<()> {
         let result: u64 = a - b;
-        //let result: u64 = a.checked_sub(b).unwrap();
         msg!("Your result, {} - {} {:?}", a, b, result);
         Ok(())
     }
 
-    pub fn opplus(ctx: Context<Initialize>, a: f32, b: f32) -> Result<()> {
-        let result:f32 = a + b;
+    // `op` in the overflow `mode` of your choice, see arithmetic.rs. The
+    // result is returned, also readable with `.view()`.
+    #[trace]
+    pub fn math_u8(_ctx: Context<Initialize>, op: Op, mode: Mode, a: u8, b: u8) -> Result<u8> {
+        arithmetic::apply(op, mode, a, b)
+    }
+
+    #[trace]
+    pub fn math_u16(_ctx: Context<Initialize>, op: Op, mode: Mode, a: u16, b: u16) -> Result<u16> {
+        arithmetic::apply(op, mode, a, b)
+    }
+
+    #[trace]
+    pub fn math_u32(_ctx: Context<Initialize>, op: Op, mode: Mode, a: u32, b: u32) -> Result<u32> {
+        arithmetic::apply(op, mode, a, b)
+    }
+
+    #[trace]
+    pub fn math_u64(_ctx: Context<Initialize>, op: Op, mode: Mode, a: u64, b: u64) -> Result<u64> {
+        arithmetic::apply(op, mode, a, b)
+    }
+
+    #[trace]
+    pub fn math_u128(_ctx: Context<Initialize>, op: Op, mode: Mode, a: u128, b: u128) -> Result<u128> {
+        arithmetic::apply(op, mode, a, b)
+    }
+
+    #[trace]
+    pub fn math_i8(_ctx: Context<Initialize>, op: Op, mode: Mode, a: i8, b: i8) -> Result<i8> {
+        arithmetic::apply(op, mode, a, b)
+    }
+
+    #[trace]
+    pub fn math_i16(_ctx: Context<Initialize>, op: Op, mode: Mode, a: i16, b: i16) -> Result<i16> {
+        arithmetic::apply(op, mode, a, b)
+    }
+
+    #[trace]
+    pub fn math_i32(_ctx: Context<Initialize>, op: Op, mode: Mode, a: i32, b: i32) -> Result<i32> {
+        arithmetic::apply(op, mode, a, b)
+    }
+
+    #[trace]
+    pub fn math_i64(_ctx: Context<Initialize>, op: Op, mode: Mode, a: i64, b: i64) -> Result<i64> {
+        arithmetic::apply(op, mode, a, b)
+    }
+
+    #[trace]
+    pub fn math_i128(_ctx: Context<Initialize>, op: Op, mode: Mode, a: i128, b: i128) -> Result<i128> {
+        arithmetic::apply(op, mode, a, b)
+    }
+
+    // The op* instructions use `Decimal`, checked fixed-point arithmetic, and
+    // return their result (also readable with `.view()`). Where f32 gave inf
+    // or NaN they fail with an error.
+    #[trace]
+    pub fn opplus(_ctx: Context<Initialize>, a: Decimal, b: Decimal) -> Result<Decimal> {
+        let result = a.checked_add(b)?;
+        msg!("Your result, {} + {} = {}", a, b, result);
+        Ok(result)
+    }
+
+    #[trace]
+    pub fn opminus(_ctx: Context<Initialize>, a: Decimal, b: Decimal) -> Result<Decimal> {
+        let result = a.checked_sub(b)?;
+        msg!("Your result, {} - {} = {}", a, b, result);
+        Ok(result)
+    }
+
+    #[trace]
+    pub fn opmul(_ctx: Context<Initialize>, a: Decimal, b: Decimal) -> Result<Decimal> {
+        let result = a.checked_mul(b)?;
+        msg!("Your result, {} * {} = {}", a, b, result);
+        Ok(result)
+    }
+
+    #[trace]
+    pub fn opdiv(_ctx: Context<Initialize>, a: Decimal, b: Decimal) -> Result<Decimal> {
+        let result = a.checked_div(b)?;
+        msg!("Your result, {} / {} = {}", a, b, result);
+        Ok(result)
+    }
+
+    #[trace]
+    pub fn opsqrt(_ctx: Context<Initialize>, a: Decimal) -> Result<Decimal> {
+        let result = a.sqrt()?;
+        msg!("Your result, sqrt({}) = {}", a, result);
+        Ok(result)
+    }
+
+    #[trace]
+    pub fn oplog10(_ctx: Context<Initialize>, a: Decimal) -> Result<Decimal> {
+        let result = a.log10()?;
+        msg!("Your result, log10({}) = {}", a, result);
+        Ok(result)
+    }
+
+    #[trace]
+    pub fn opln(_ctx: Context<Initialize>, a: Decimal) -> Result<Decimal> {
+        let result = a.ln()?;
+        msg!("Your result, ln({}) = {}", a, result);
+        Ok(result)
+    }
+
+    // The former f32 versions, kept as a baseline for the compute unit
+    // benchmarks in tests/day_02.ts
+    pub fn opplus_f32(_ctx: Context<Initialize>, a: f32, b: f32) -> Result<f32> {
+        let result: f32 = a + b;
         msg!("Your result, {} + {} = {:?}", a, b, result);
-        Ok(())
+        Ok(result)
     }
 
-    pub fn opminus(ctx: Context<Initialize>, a: f32, b: f32) -> Result<()> {
+    pub fn opminus_f32(_ctx: Context<Initialize>, a: f32, b: f32) -> Result<f32> {
         let result: f32 = a - b;
         msg!("Your result, {} - {} = {:?}", a, b, result);
-        Ok(())
+        Ok(result)
     }
 
-    pub fn opdiv(ctx: Context<Initialize>, a: f32, b: f32) -> Result<()> {
+    pub fn opdiv_f32(_ctx: Context<Initialize>, a: f32, b: f32) -> Result<f32> {
         let result: f32 = a / b;
         msg!("Your result, {} / {} = {:?}", a, b, result);
-        Ok(())
+        Ok(result)
     }
 
-    pub fn opsqrt(ctx: Context<Initialize>, a: f32) -> Result<()> {
+    pub fn opsqrt_f32(_ctx: Context<Initialize>, a: f32) -> Result<f32> {
         let result: f32 = a.sqrt();
         msg!("Your result, sqrt({}) = {:?}", a, result);
-        Ok(())
+        Ok(result)
     }
 
-    pub fn oplog10(ctx: Context<Initialize>, a: f32) -> Result<()> {
+    pub fn oplog10_f32(_ctx: Context<Initialize>, a: f32) -> Result<f32> {
         let result: f32 = a.log10();
         msg!("Your result, log10({}) = {:?}", a, result);
-        Ok(())
+        Ok(result)
     }
 
 
@@ -65,3 +179,23 @@ pub mod day_02 {
 
 #[derive(Accounts)]
 pub struct Initialize {}
+
+#[error_code]
+pub enum Errors {
+    #[msg("Scale is larger than the maximum of 18 decimals")]
+    InvalidScale,
+    #[msg("Decimal overflow")]
+    DecimalOverflow,
+    #[msg("Division by zero")]
+    DecimalDivisionByZero,
+    #[msg("Square root of a negative number")]
+    NegativeSqrt,
+    #[msg("Logarithm of a number that is not positive")]
+    LogOfNonPositive,
+    #[msg("Integer overflow: the result is above the maximum of its type")]
+    IntegerOverflow,
+    #[msg("Integer underflow: the result is below the minimum of its type")]
+    IntegerUnderflow,
+    #[msg("Integer division by zero")]
+    IntegerDivisionByZero,
+}
@@ -0,0 +1,58 @@
+use day_02::arithmetic::{apply, Mode, Op};
+use day_02::Errors;
+use std::hint::black_box;
+
+fn assert_error<T: std::fmt::Debug>(result: anchor_lang::Result<T>, expected: Errors) {
+    assert_eq!(result.unwrap_err(), expected.into());
+}
+
+#[test]
+fn checked_reports_the_side_of_the_overflow() {
+    assert_eq!(apply(Op::Add, Mode::Checked, 200u8, 55).unwrap(), 255);
+    assert_error(apply(Op::Add, Mode::Checked, 200u8, 56), Errors::IntegerOverflow);
+    assert_error(apply(Op::Sub, Mode::Checked, 0u64, 1), Errors::IntegerUnderflow);
+    assert_error(apply(Op::Mul, Mode::Checked, u128::MAX, 2), Errors::IntegerOverflow);
+
+    assert_error(apply(Op::Add, Mode::Checked, i8::MIN, -1), Errors::IntegerUnderflow);
+    assert_error(apply(Op::Sub, Mode::Checked, i16::MAX, -1), Errors::IntegerOverflow);
+    assert_error(apply(Op::Mul, Mode::Checked, i32::MAX, -2), Errors::IntegerUnderflow);
+    assert_error(apply(Op::Mul, Mode::Checked, i64::MIN, -1), Errors::IntegerOverflow);
+    assert_error(apply(Op::Div, Mode::Checked, i128::MIN, -1), Errors::IntegerOverflow);
+    assert_eq!(apply(Op::Div, Mode::Checked, -7i32, 2).unwrap(), -3);
+}
+
+#[test]
+fn wrapping_and_saturating_never_overflow() {
+    assert_eq!(apply(Op::Add, Mode::Wrapping, 200u8, 56).unwrap(), 0);
+    assert_eq!(apply(Op::Add, Mode::Saturating, 200u8, 56).unwrap(), u8::MAX);
+    assert_eq!(apply(Op::Sub, Mode::Wrapping, 0u64, 1).unwrap(), u64::MAX);
+    assert_eq!(apply(Op::Sub, Mode::Saturating, 0u64, 1).unwrap(), 0);
+
+    assert_eq!(apply(Op::Add, Mode::Wrapping, i8::MAX, 1).unwrap(), i8::MIN);
+    assert_eq!(apply(Op::Mul, Mode::Saturating, i32::MAX, -2).unwrap(), i32::MIN);
+    assert_eq!(apply(Op::Div, Mode::Wrapping, i64::MIN, -1).unwrap(), i64::MIN);
+    assert_eq!(apply(Op::Div, Mode::Saturating, i64::MIN, -1).unwrap(), i64::MAX);
+}
+
+#[test]
+fn division_by_zero_fails_in_every_mode() {
+    for mode in [Mode::Checked, Mode::Wrapping, Mode::Saturating] {
+        assert_error(apply(Op::Div, mode, 1u16, 0), Errors::IntegerDivisionByZero);
+        assert_error(apply(Op::Div, mode, -1i128, 0), Errors::IntegerDivisionByZero);
+    }
+}
+
+// Plain arithmetic, as in the `exercises` instruction, must panic rather than
+// wrap. Debug builds always check; `cargo test --release` checks the release
+// profile, which the program is built with: it sets `overflow-checks = true`.
+#[test]
+#[should_panic(expected = "attempt to subtract with overflow")]
+fn plain_subtraction_panics_on_underflow() {
+    black_box(black_box(0u64) - black_box(1u64));
+}
+
+#[test]
+#[should_panic(expected = "attempt to add with overflow")]
+fn plain_addition_panics_on_overflow() {
+    black_box(black_box(i8::MAX) + black_box(1i8));
+}
@@ -0,0 +1,154 @@
+use day_02::decimal::{Decimal, MAX_SCALE};
+use day_02::Errors;
+
+fn dec(value: u128, scale: u8) -> Decimal {
+    Decimal::new(value, scale, false).unwrap()
+}
+
+fn neg(value: u128, scale: u8) -> Decimal {
+    Decimal::new(value, scale, true).unwrap()
+}
+
+fn to_f64(d: Decimal) -> f64 {
+    let magnitude = d.value as f64 / 10f64.powi(d.scale as i32);
+    if d.negative {
+        -magnitude
+    } else {
+        magnitude
+    }
+}
+
+fn assert_error(result: anchor_lang::Result<Decimal>, expected: Errors) {
+    assert_eq!(result.unwrap_err(), expected.into());
+}
+
+#[test]
+fn add_and_sub_align_scales_and_signs() {
+    assert_eq!(dec(150, 2).checked_add(dec(25, 1)).unwrap(), dec(4, 0)); // 1.50 + 2.5
+    assert_eq!(dec(3, 0).checked_sub(dec(13, 0)).unwrap(), neg(10, 0));
+    assert_eq!(neg(5, 1).checked_add(dec(5, 1)).unwrap(), dec(0, 0));
+    assert_eq!(neg(2, 0).checked_sub(neg(7, 0)).unwrap(), dec(5, 0));
+
+    let sum = dec(1, 1).checked_add(dec(2, 1)).unwrap();
+    assert_eq!(sum.to_string(), "0.3");
+}
+
+#[test]
+fn mul_and_div() {
+    assert_eq!(dec(15, 1).checked_mul(neg(4, 0)).unwrap(), neg(6, 0));
+    assert_eq!(dec(70, 0).checked_div(dec(7, 0)).unwrap(), dec(10, 0));
+    // truncated at the scale of the operands
+    assert_eq!(dec(1_000_000, 6).checked_div(dec(3, 0)).unwrap().to_string(), "0.333333");
+    assert_eq!(dec(2, 0).checked_div(dec(3, 0)).unwrap(), dec(0, 0));
+}
+
+#[test]
+fn errors_instead_of_inf_and_nan() {
+    assert_error(dec(70, 0).checked_div(dec(0, 3)), Errors::DecimalDivisionByZero);
+    assert_error(neg(4, 0).sqrt(), Errors::NegativeSqrt);
+    assert_error(dec(0, 0).log10(), Errors::LogOfNonPositive);
+    assert_error(neg(1, 0).ln(), Errors::LogOfNonPositive);
+    assert_error(dec(u128::MAX, 0).checked_add(dec(1, 0)), Errors::DecimalOverflow);
+    assert_error(dec(u128::MAX, 0).checked_mul(dec(2, 0)), Errors::DecimalOverflow);
+    assert_error(Decimal::new(1, 19, false), Errors::InvalidScale);
+}
+
+#[test]
+fn oversized_scales_are_rejected() {
+    // as deserialized from an instruction argument, without `Decimal::new`
+    let oversized = Decimal { value: 1, scale: 39, negative: false };
+
+    assert_error(oversized.sqrt(), Errors::InvalidScale);
+    assert_error(oversized.ln(), Errors::InvalidScale);
+    assert_error(oversized.log10(), Errors::InvalidScale);
+    assert_error(oversized.rescale(0), Errors::InvalidScale);
+    assert_error(oversized.checked_add(dec(1, 0)), Errors::InvalidScale);
+    assert_error(dec(1, 0).checked_sub(oversized), Errors::InvalidScale);
+    assert_error(oversized.checked_mul(dec(1, 0)), Errors::InvalidScale);
+    assert_error(dec(1, 0).checked_div(oversized), Errors::InvalidScale);
+    assert_error(dec(1, 0).rescale(MAX_SCALE + 1), Errors::InvalidScale);
+    assert_eq!(oversized, oversized);
+    assert_ne!(oversized, dec(1, 0));
+
+    // the largest scale still works
+    let smallest = dec(1, MAX_SCALE);
+    assert_eq!(smallest.sqrt().unwrap().to_string(), "0.000000001000000000");
+    assert!(smallest.ln().unwrap().negative);
+}
+
+#[test]
+fn full_precision_operands_above_340() {
+    // 10^18 * 10^18 does not fit in u128: the intermediates are wider
+    let at_max_scale = |value: u128| dec(value * 10u128.pow(MAX_SCALE as u32), MAX_SCALE);
+
+    assert_eq!(at_max_scale(1000).checked_div(at_max_scale(2)).unwrap(), dec(500, 0));
+    assert_eq!(at_max_scale(20).checked_mul(at_max_scale(20)).unwrap(), dec(400, 0));
+    assert_eq!(at_max_scale(1000).sqrt().unwrap().to_string(), "31.622776601683793319");
+    assert_eq!(at_max_scale(1_000_000).sqrt().unwrap(), dec(1000, 0));
+    assert_eq!(at_max_scale(1).checked_div(at_max_scale(3)).unwrap().to_string(), "0.333333333333333333");
+    assert_eq!(
+        at_max_scale(12_345_678_901).checked_mul(dec(25, 1)).unwrap(),
+        at_max_scale(30_864_197_252).checked_add(dec(5, 1)).unwrap()
+    );
+
+    // only a result that does not fit overflows
+    let largest = dec(u128::MAX, MAX_SCALE);
+    assert_error(largest.checked_mul(at_max_scale(2)), Errors::DecimalOverflow);
+    assert_error(largest.checked_div(dec(5, 1)), Errors::DecimalOverflow);
+    assert_eq!(largest.checked_div(at_max_scale(1)).unwrap(), largest);
+    let root = largest.sqrt().unwrap();
+    assert_eq!(root.value, 18_446_744_073_709_551_615_999_999_999); // just below 2^64 * 10^9
+}
+
+#[test]
+fn sqrt_is_floored() {
+    assert_eq!(dec(100, 0).sqrt().unwrap(), dec(10, 0));
+    assert_eq!(dec(2, 0).sqrt().unwrap(), dec(1, 0));
+    assert_eq!(dec(2_000_000, 6).sqrt().unwrap().to_string(), "1.414213");
+    assert_eq!(dec(2, 18).sqrt().unwrap().to_string(), "0.000000001414213562");
+
+    for n in [0u128, 1, 3, 4, 15, 16, 17, u64::MAX as u128, u128::MAX] {
+        let root = dec(n, 0).sqrt().unwrap().value;
+        assert!(root * root <= n);
+        assert!((root + 1).checked_mul(root + 1).is_none_or(|square| square > n));
+    }
+}
+
+#[test]
+fn logarithms_round_to_the_input_scale() {
+    assert_eq!(dec(10000, 0).log10().unwrap(), dec(4, 0));
+    assert_eq!(dec(1, 0).ln().unwrap(), dec(0, 0));
+    assert_eq!(dec(1, 3).log10().unwrap(), neg(3, 0)); // log10(0.001)
+    assert_eq!(dec(2, 0).log10().unwrap(), dec(0, 0));
+    assert_eq!(dec(2_000_000, 6).log10().unwrap().to_string(), "0.301030");
+}
+
+#[test]
+fn logarithms_match_f64() {
+    let values = [1u128, 2, 3, 7, 10, 99, 12345, 1_000_000_007, u64::MAX as u128];
+
+    for scale in [0u8, 6, 9] {
+        for &value in &values {
+            let x = dec(value * 10u128.pow(scale as u32) + 1, scale);
+            let tolerance = 10f64.powi(-(scale as i32)) * 0.5 + 1e-12;
+
+            let ln = to_f64(x.ln().unwrap());
+            assert!((ln - to_f64(x).ln()).abs() <= tolerance, "ln({}) = {}", x, ln);
+
+            let log10 = to_f64(x.log10().unwrap());
+            assert!((log10 - to_f64(x).log10()).abs() <= tolerance, "log10({}) = {}", x, log10);
+        }
+    }
+
+    // above u128::MAX / 10^18, the largest value rescaled to MAX_SCALE
+    for x in [dec(10u128.pow(30), 0), dec(u128::MAX, 0), dec(u128::MAX, 6)] {
+        assert!((to_f64(x.ln().unwrap()) - to_f64(x).ln()).abs() <= 0.5, "ln({})", x);
+        assert!((to_f64(x.log10().unwrap()) - to_f64(x).log10()).abs() <= 0.5, "log10({})", x);
+    }
+    assert_eq!(dec(10u128.pow(30), 0).log10().unwrap(), dec(30, 0));
+    assert_eq!(dec(10u128.pow(32), 2).ln().unwrap().to_string(), "69.08");
+
+    // below 1, at full precision
+    let x = dec(123_456_789, 18);
+    assert!((to_f64(x.ln().unwrap()) - to_f64(x).ln()).abs() < 1e-12);
+}
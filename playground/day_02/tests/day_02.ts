@@ -1,6 +1,7 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { Day02 } from "../target/types/day_02";
+import { expect } from "chai";
 
 describe("day_02", () => {
   // Configure the client to use the local cluster.
@@ -8,6 +9,15 @@ describe("day_02", () => {
 
   const program = anchor.workspace.Day02 as Program<Day02>;
 
+  const expectError = async (promise: Promise<any>, code: string) => {
+    try {
+      await promise;
+      expect.fail(`should have failed with ${code}`);
+    } catch (e) {
+      expect(e.toString()).to.contain(code);
+    }
+  };
+
   it("Is initialized!", async () => {
     // Add your test here.
     const tx = await program.methods.initialize(
@@ -23,46 +33,134 @@ describe("day_02", () => {
     console.log("Your transaction signature", tx);
   });
   
-  it("Exercise", async () => {
+  it("Exercise: the release build rejects overflow", async () => {
     const tx = await program.methods.exercises(
-      new anchor.BN(0),
-      new anchor.BN(1)).rpc();
+      new anchor.BN(1),
+      new anchor.BN(0)).rpc();
     console.log("Your transaction signature", tx);
+
+    // overflow-checks = true in the release profile: 0 - 1 panics, which only
+    // shows in the program logs
+    try {
+      await program.methods.exercises(new anchor.BN(0), new anchor.BN(1)).rpc();
+      expect.fail("0 - 1 should have panicked");
+    } catch (e) {
+      expect(e.toString()).to.contain("Program failed to complete");
+      expect(e.logs.join("\n")).to.contain("attempt to subtract with overflow");
+    }
   });
-  
+
+  it("Integer arithmetic in every overflow mode", async () => {
+    const m = program.methods;
+    const checked = { checked: {} }, wrapping = { wrapping: {} }, saturating = { saturating: {} };
+
+    expect(await m.mathU8({ add: {} }, checked, 200, 55).view()).to.equal(255);
+    expect(await m.mathU8({ add: {} }, wrapping, 200, 56).view()).to.equal(0);
+    expect(await m.mathU8({ add: {} }, saturating, 200, 56).view()).to.equal(255);
+    expect(await m.mathI8({ sub: {} }, wrapping, -128, 1).view()).to.equal(127);
+    expect(await m.mathI32({ mul: {} }, saturating, 2147483647, -2).view()).to.equal(-2147483648);
+
+    const u64Max = new anchor.BN("18446744073709551615");
+    expect((await m.mathU64({ sub: {} }, wrapping, new anchor.BN(0), new anchor.BN(1)).view()).eq(u64Max)).to.be.true;
+    const product = await m.mathU128({ mul: {} }, checked, u64Max, u64Max).view();
+    expect(product.eq(u64Max.mul(u64Max))).to.be.true;
+    const quotient = await m.mathI64({ div: {} }, checked, new anchor.BN(-7), new anchor.BN(2)).view();
+    expect(quotient.toNumber()).to.equal(-3);
+  });
+
+  it("Checked arithmetic has a distinct error per overflow", async () => {
+    const m = program.methods;
+    const checked = { checked: {} };
+
+    await expectError(m.mathU8({ add: {} }, checked, 200, 56).view(), "IntegerOverflow");
+    await expectError(m.mathU16({ sub: {} }, checked, 0, 1).view(), "IntegerUnderflow");
+    await expectError(m.mathI16({ mul: {} }, checked, -32768, 2).view(), "IntegerUnderflow");
+    await expectError(m.mathI8({ div: {} }, checked, -128, -1).view(), "IntegerOverflow");
+    await expectError(m.mathU32({ div: {} }, { wrapping: {} }, 1, 0).view(), "IntegerDivisionByZero");
+    await expectError(m.mathI128({ div: {} }, { saturating: {} }, new anchor.BN(1), new anchor.BN(0)).view(), "IntegerDivisionByZero");
+  });
+
+  // A fixed-point number: value / 10^scale
+  const decimal = (value: number | string, scale = 0, negative = false) => ({
+    value: new anchor.BN(value),
+    scale,
+    negative,
+  });
+
+  const expectDecimal = (result: any, expected: string) => {
+    let digits = result.value.toString();
+    while (digits.length <= result.scale) {
+      digits = "0" + digits;
+    }
+    const integer = digits.slice(0, digits.length - result.scale);
+    const fraction = result.scale > 0 ? "." + digits.slice(digits.length - result.scale) : "";
+    expect((result.negative ? "-" : "") + integer + fraction).to.equal(expected);
+  };
+
   it("Plus", async () => {
-    const tx = await program.methods.opplus(
-      new anchor.BN(3),
-      new anchor.BN(7)).rpc();
-    console.log("Your transaction signature", tx);
+    expectDecimal(await program.methods.opplus(decimal(3), decimal(7)).view(), "10");
+    expectDecimal(await program.methods.opplus(decimal(150, 2), decimal(25, 1)).view(), "4.00");
   });
-  
+
   it("Minus", async () => {
-    const tx = await program.methods.opminus(
-      new anchor.BN(13),
-      new anchor.BN(3)).rpc();
-    console.log("Your transaction signature", tx);
+    expectDecimal(await program.methods.opminus(decimal(13), decimal(3)).view(), "10");
+    expectDecimal(await program.methods.opminus(decimal(3), decimal(13)).view(), "-10");
   });
-  
+
+  it("Multiplication", async () => {
+    expectDecimal(await program.methods.opmul(decimal(15, 1), decimal(4, 0, true)).view(), "-6.0");
+  });
+
   it("Division", async () => {
-    const tx = await program.methods.opdiv(
-      new anchor.BN(70),
-      new anchor.BN(7)).rpc();
-    console.log("Your transaction signature", tx);
+    expectDecimal(await program.methods.opdiv(decimal(70), decimal(7)).view(), "10");
+    expectDecimal(await program.methods.opdiv(decimal(1_000_000, 6), decimal(3)).view(), "0.333333");
+    await expectError(program.methods.opdiv(decimal(70), decimal(0)).view(), "DecimalDivisionByZero");
   });
-  
+
   it("Square root", async () => {
-    const tx = await program.methods.opsqrt(
-      new anchor.BN(100)
-      ).rpc();
-    console.log("Your transaction signature", tx);
+    expectDecimal(await program.methods.opsqrt(decimal(100)).view(), "10");
+    expectDecimal(await program.methods.opsqrt(decimal(2_000_000, 6)).view(), "1.414213");
+    await expectError(program.methods.opsqrt(decimal(4, 0, true)).view(), "NegativeSqrt");
   });
-  
+
   it("Log 10", async () => {
-    const tx = await program.methods.oplog10(
-      new anchor.BN(10000)
-      ).rpc();
-    console.log("Your transaction signature", tx);
+    expectDecimal(await program.methods.oplog10(decimal(10000)).view(), "4");
+    expectDecimal(await program.methods.oplog10(decimal(2_000_000, 6)).view(), "0.301030");
+    await expectError(program.methods.oplog10(decimal(0)).view(), "LogOfNonPositive");
+  });
+
+  it("Natural log", async () => {
+    expectDecimal(await program.methods.opln(decimal(2_000_000_000, 9)).view(), "0.693147181");
+    expectDecimal(await program.methods.opln(decimal(1, 3)).view(), "-6.908");
+  });
+
+  it("f32 silently returns inf and NaN", async () => {
+    expect(await program.methods.opdivF32(70, 0).view()).to.equal(Infinity);
+    expect(await program.methods.opsqrtF32(-4).view()).to.be.NaN;
+    expect(await program.methods.oplog10F32(0).view()).to.equal(-Infinity);
+  });
+
+  it("Compute units: Decimal vs f32", async () => {
+    const computeUnits = async (builder: any) => {
+      const tx = await builder.rpc({ commitment: "confirmed" });
+      const result = await anchor.getProvider().connection.getTransaction(tx, {
+        commitment                    : "confirmed",
+        maxSupportedTransactionVersion: 0,
+      });
+      return result.meta.computeUnitsConsumed;
+    };
+
+    const m = program.methods;
+    const cases = [
+      { name: "plus",  f32: m.opplusF32(3.25, 7.5),  fixed: m.opplus(decimal(325, 2), decimal(75, 1)) },
+      { name: "minus", f32: m.opminusF32(3.25, 7.5), fixed: m.opminus(decimal(325, 2), decimal(75, 1)) },
+      { name: "div",   f32: m.opdivF32(70, 7),       fixed: m.opdiv(decimal(70_000_000, 6), decimal(7)) },
+      { name: "sqrt",  f32: m.opsqrtF32(2),          fixed: m.opsqrt(decimal(2_000_000, 6)) },
+      { name: "log10", f32: m.oplog10F32(2),         fixed: m.oplog10(decimal(2_000_000, 6)) },
+    ];
+
+    for (const { name, f32, fixed } of cases) {
+      console.log(`${name}\tf32: ${await computeUnits(f32)} CU\tDecimal: ${await computeUnits(fixed)} CU`);
+    }
   });
-  
 });
@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use macro_demo::{validate, validation_errors};
 
 declare_id!("2XSRcHuq3HZR5tPyrxzqcn6W4paK99271ZnstnYVR3ei");
 
@@ -6,16 +7,27 @@ declare_id!("2XSRcHuq3HZR5tPyrxzqcn6W4paK99271ZnstnYVR3ei");
 pub mod day_04 {
 	use super::*;
 
+	// Same as
+	//     require!(a >= 10, Day04Error::AisTooSmall);
+	//     require!(a <= 100, Day04Error::AisTooBig);
+	#[validate(a(min = 10 @ Day04Error::AisTooSmall, max = 100 @ Day04Error::AisTooBig))]
 	pub fn limit_range(ctx: Context<LimitRange>, a: u64) -> Result<()> {
-		// require!(a >= 10, MyError::AisTooSmall);
-		// require!(a <= 100, MyError::AisTooBig);
-		require!(a >= 10, Day04Error::AisTooSmall);
-		require!(a <= 100, Day04Error::AisTooBig);
-
 		msg!("Result = {}", a);
 		Ok(())
 	}
 
+	#[validate(
+		errors = Day04Error,
+		name(max_len = 32),
+		fee(non_zero, range = ..10_000),
+		scores(len = 1..=8),
+		tier(one_of = [Tier::Silver, Tier::Gold]),
+	)]
+	pub fn register(_ctx: Context<LimitRange>, name: String, fee: u64, scores: Vec<u8>, tier: Tier) -> Result<()> {
+		msg!("{} registered in {:?} with {} scores, fee {}", name, tier, scores.len(), fee);
+		Ok(())
+	}
+
 	pub fn func(ctx: Context<ReturnError>) -> Result<()> {
 		msg!("Will this print?");
 		//return err!(MyError::AlwaysErrors);
@@ -29,6 +41,16 @@ pub struct LimitRange {}
 #[derive(Accounts)]
 pub struct ReturnError {}
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Tier {
+	Bronze,
+	Silver,
+	Gold,
+}
+
+// ValueOutOfRange, ValueIsZero, StringTooLong, LengthOutOfRange and
+// ValueNotAllowed follow AlwaysErrors, from `#[validation_errors]`
+#[validation_errors]
 #[error_code]
 //pub enum MyError {
 pub enum Day04Error {
@@ -43,4 +43,51 @@ describe("day_04", () => { // Configure the client to use the local cluster.
     }
   }); 
 
+  describe("validate", () => {
+    // Bounds exported to the IDL by #[validate]
+    const constant = (name: string) => Number(program.idl.constants.find((c) => c.name === name).value);
+
+    const register = (name: string, fee: number, scores: number[], tier: any) =>
+      program.methods.register(name, new anchor.BN(fee), Buffer.from(scores), tier).rpc();
+
+    const expectValidationError = async (promise: Promise<string>, code: string, number: number) => {
+      try {
+        await promise;
+        assert.fail(`should have failed with ${code}`);
+      } catch (_err) {
+        assert.isTrue(_err instanceof AnchorError);
+        const err: AnchorError = _err;
+        assert.strictEqual(err.error.errorCode.code, code);
+        // After the program's own errors: AisTooSmall, AisTooBig, AlwaysErrors
+        assert.strictEqual(err.error.errorCode.number, number);
+      }
+    };
+
+    it("Exports the bounds", async () => {
+      assert.strictEqual(constant("LIMIT_RANGE_A_MIN"), 10);
+      assert.strictEqual(constant("LIMIT_RANGE_A_MAX"), 100);
+      assert.strictEqual(constant("REGISTER_NAME_MAX_LEN"), 32);
+      assert.strictEqual(constant("REGISTER_FEE_MAX"), 9_999);
+      assert.strictEqual(constant("REGISTER_SCORES_LEN_MIN"), 1);
+      assert.strictEqual(constant("REGISTER_SCORES_LEN_MAX"), 8);
+    });
+
+    it("Accepts valid arguments", async () => {
+      await program.methods.limitRange(new anchor.BN(constant("LIMIT_RANGE_A_MAX"))).rpc();
+      await register("alice", 1, [1, 2, 3], { gold: {} });
+    });
+
+    it("Rejects invalid arguments", async () => {
+      await expectValidationError(program.methods.limitRange(new anchor.BN(9)).rpc(), "AisTooSmall", 6000);
+      await expectValidationError(program.methods.limitRange(new anchor.BN(101)).rpc(), "AisTooBig", 6001);
+      await expectValidationError(program.methods.func().rpc(), "AlwaysErrors", 6002);
+      await expectValidationError(register("alice", 10_000, [1], { gold: {} }), "ValueOutOfRange", 6003);
+      await expectValidationError(register("alice", 0, [1], { gold: {} }), "ValueIsZero", 6004);
+      await expectValidationError(register("a".repeat(33), 1, [1], { gold: {} }), "StringTooLong", 6005);
+      await expectValidationError(register("alice", 1, [], { gold: {} }), "LengthOutOfRange", 6006);
+      await expectValidationError(register("alice", 1, new Array(9).fill(1), { gold: {} }), "LengthOutOfRange", 6006);
+      await expectValidationError(register("alice", 1, [1], { bronze: {} }), "ValueNotAllowed", 6007);
+    });
+  });
+
 });
@@ -0,0 +1,102 @@
+// Role-based access control, stored on-chain instead of a hard-coded owner.
+//
+// The `AccessControl` PDA is created by the program's upgrade authority,
+// its first admin. It lists up to `MAX_MEMBERS` accounts with the roles
+// each of them holds. Admins grant and revoke roles, anyone can renounce
+// their own roles, and the last admin cannot be removed so the program never
+// ends up without one.
+//
+// Instructions check roles either with `#[access_control]`:
+//
+//     #[access_control(only_role(&ctx.accounts.access_control, &ctx.accounts.signer, Role::Admin))]
+//
+// or with an account constraint:
+//
+//     #[account(constraint = access_control.has_role(&signer.key(), Role::Operator) @ Errors::MissingRole)]
+use anchor_lang::prelude::*;
+
+use crate::Errors;
+
+pub const ACCESS_CONTROL_SEED: &[u8] = b"access_control";
+
+// Length of `AccessControl::members`
+pub const MAX_MEMBERS: usize = 16;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Role {
+    Admin,    // grants and revokes roles
+    Operator, // runs day to day instructions
+    Pauser,   // can stop the program in an emergency
+}
+
+impl Role {
+    fn bit(self) -> u8 {
+        1 << self as u8
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct Member {
+    pub key:   Pubkey, // Pubkey::default() for a free slot
+    pub roles: u8,     // Role bits
+}
+
+#[account]
+pub struct AccessControl {
+    pub members: [Member; MAX_MEMBERS],
+    pub bump:    u8,
+}
+
+impl AccessControl {
+    pub fn has_role(&self, key: &Pubkey, role: Role) -> bool {
+        self.members
+            .iter()
+            .any(|member| member.key == *key && member.roles & role.bit() != 0)
+    }
+
+    pub fn grant(&mut self, key: Pubkey, role: Role) -> Result<()> {
+        require_keys_neq!(key, Pubkey::default(), Errors::InvalidMember);
+
+        let index = match self.members.iter().position(|member| member.key == key) {
+            Some(index) => index,
+            None => self
+                .members
+                .iter()
+                .position(|member| member.roles == 0)
+                .ok_or(Errors::TooManyMembers)?,
+        };
+
+        let member = &mut self.members[index];
+        member.key = key;
+        member.roles |= role.bit();
+        Ok(())
+    }
+
+    pub fn revoke(&mut self, key: &Pubkey, role: Role) -> Result<()> {
+        require!(self.has_role(key, role), Errors::MissingRole);
+        require!(role != Role::Admin || self.count(Role::Admin) > 1, Errors::LastAdmin);
+
+        for member in self.members.iter_mut().filter(|member| member.key == *key) {
+            member.roles &= !role.bit();
+
+            if member.roles == 0 {
+                *member = Member::default();
+            }
+        }
+
+        Ok(())
+    }
+
+    fn count(&self, role: Role) -> usize {
+        self.members
+            .iter()
+            .filter(|member| member.roles & role.bit() != 0)
+            .count()
+    }
+}
+
+// For `#[access_control]`
+pub fn only_role(access_control: &AccessControl, signer: &Signer, role: Role) -> Result<()> {
+    require!(access_control.has_role(signer.key, role), Errors::MissingRole);
+    Ok(())
+}
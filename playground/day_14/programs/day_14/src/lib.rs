@@ -1,20 +1,170 @@
 use anchor_lang::prelude::*;
+use std::mem::size_of;
+
+pub mod access;
+pub mod multisig;
+
+use access::{only_role, AccessControl, Role, ACCESS_CONTROL_SEED};
+use anchor_lang::solana_program::program::invoke_signed;
+use multisig::*;
 
 declare_id!("BWBtaUY6gZBZKEYvPF1vgnU5vM7DuCxCduAHnEUSuiqs");
-// NOTE: Replace with your wallet's public key
-const OWNER: &str = "2mcDUMsXbfzeiyr8cNd4XrTp2uwKySC6ujGmCVfBfQ3j";
 
 #[program]
 pub mod day_14 {
     use super::*;
 
-    #[access_control(check(&ctx))]
+    // Only the program's upgrade authority can initialize it, and becomes
+    // the first admin
+    pub fn initialize_access_control(ctx: Context<InitializeAccessControl>) -> Result<()> {
+        let access_control = &mut ctx.accounts.access_control;
+        access_control.bump = ctx.bumps.access_control;
+        access_control.grant(ctx.accounts.signer.key(), Role::Admin)
+    }
+
+    #[access_control(only_role(&ctx.accounts.access_control, &ctx.accounts.admin, Role::Admin))]
+    pub fn grant_role(ctx: Context<ManageRoles>, role: Role, account: Pubkey) -> Result<()> {
+        ctx.accounts.access_control.grant(account, role)?;
+        msg!("Granted {:?} to {}", role, account);
+
+        Ok(())
+    }
+
+    #[access_control(only_role(&ctx.accounts.access_control, &ctx.accounts.admin, Role::Admin))]
+    pub fn revoke_role(ctx: Context<ManageRoles>, role: Role, account: Pubkey) -> Result<()> {
+        ctx.accounts.access_control.revoke(&account, role)?;
+        msg!("Revoked {:?} from {}", role, account);
+
+        Ok(())
+    }
+
+    pub fn renounce_role(ctx: Context<RenounceRole>, role: Role) -> Result<()> {
+        let signer = ctx.accounts.signer.key();
+        ctx.accounts.access_control.revoke(&signer, role)?;
+        msg!("{} renounced {:?}", signer, role);
+
+        Ok(())
+    }
+
+    #[access_control(only_role(&ctx.accounts.access_control, &ctx.accounts.signer_account, Role::Admin))]
     pub fn owner_only(ctx: Context<OnlyOwner>) -> Result<()> {
         msg!("I'm the Owner !");
 
         Ok(())
     }
 
+    // Same kind of check, done by an account constraint
+    pub fn operator_only(_ctx: Context<OnlyOperator>) -> Result<()> {
+        msg!("I'm an operator !");
+
+        Ok(())
+    }
+
+    // MULTISIG
+    // --------
+
+    pub fn create_multisig(ctx: Context<CreateMultisig>, owners: Vec<Pubkey>, threshold: u8) -> Result<()> {
+        check_owners(&owners, threshold)?;
+
+        let (_, signer_bump) = Pubkey::find_program_address(
+            &[MULTISIG_SEED, ctx.accounts.multisig.key().as_ref()],
+            ctx.program_id,
+        );
+
+        let multisig = &mut ctx.accounts.multisig;
+        multisig.owners          = owners;
+        multisig.threshold       = threshold;
+        multisig.signer_bump     = signer_bump;
+        multisig.owner_set_seqno = 0;
+
+        Ok(())
+    }
+
+    // The proposer approves its own proposal
+    pub fn propose(
+        ctx: Context<Propose>,
+        program_id: Pubkey,
+        accounts: Vec<TransactionAccount>,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        let multisig = &ctx.accounts.multisig;
+        let proposer = multisig.owner_index(ctx.accounts.proposer.key)?;
+
+        let transaction = &mut ctx.accounts.transaction;
+        transaction.multisig        = multisig.key();
+        transaction.proposer        = ctx.accounts.proposer.key();
+        transaction.program_id      = program_id;
+        transaction.accounts        = accounts;
+        transaction.data            = data;
+        transaction.approvals       = vec![false; multisig.owners.len()];
+        transaction.did_execute     = false;
+        transaction.owner_set_seqno = multisig.owner_set_seqno;
+
+        transaction.approvals[proposer] = true;
+
+        Ok(())
+    }
+
+    pub fn approve(ctx: Context<Approve>) -> Result<()> {
+        let multisig = &ctx.accounts.multisig;
+        let transaction = &mut ctx.accounts.transaction;
+        require_eq!(transaction.owner_set_seqno, multisig.owner_set_seqno, Errors::OwnerSetChanged);
+        require!(!transaction.did_execute, Errors::AlreadyExecuted);
+
+        let owner = multisig.owner_index(ctx.accounts.owner.key)?;
+        transaction.approvals[owner] = true;
+
+        Ok(())
+    }
+
+    pub fn execute(ctx: Context<Execute>) -> Result<()> {
+        let multisig = &ctx.accounts.multisig;
+        let transaction = &mut ctx.accounts.transaction;
+        require_eq!(transaction.owner_set_seqno, multisig.owner_set_seqno, Errors::OwnerSetChanged);
+        require!(!transaction.did_execute, Errors::AlreadyExecuted);
+        require!(transaction.approval_count() >= multisig.threshold as usize, Errors::NotEnoughApprovals);
+
+        // Written to the account before the CPI: Anchor only serializes it when
+        // the instruction returns, so a proposal calling `execute` on itself
+        // would otherwise still read `did_execute == false`
+        transaction.did_execute = true;
+        transaction.exit(&crate::ID)?;
+
+        let instruction = transaction.instruction(ctx.accounts.multisig_signer.key);
+        let multisig_key = multisig.key();
+        let seeds = &[MULTISIG_SEED, multisig_key.as_ref(), &[multisig.signer_bump]];
+        invoke_signed(&instruction, ctx.remaining_accounts, &[seeds])?;
+
+        Ok(())
+    }
+
+    pub fn cancel(ctx: Context<Cancel>) -> Result<()> {
+        msg!("Cancelled {}", ctx.accounts.transaction.key());
+        Ok(())
+    }
+
+    pub fn set_owners(ctx: Context<Auth>, owners: Vec<Pubkey>) -> Result<()> {
+        let multisig = &mut ctx.accounts.multisig;
+
+        // Keep the threshold reachable with fewer owners
+        let threshold = multisig.threshold.min(owners.len() as u8);
+        check_owners(&owners, threshold)?;
+
+        multisig.threshold = threshold;
+        multisig.owners    = owners;
+        multisig.owner_set_seqno += 1;
+
+        Ok(())
+    }
+
+    pub fn change_threshold(ctx: Context<Auth>, threshold: u8) -> Result<()> {
+        let multisig = &mut ctx.accounts.multisig;
+        check_owners(&multisig.owners, threshold)?;
+        multisig.threshold = threshold;
+
+        Ok(())
+    }
+
     pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
         let the_signer1: &mut Signer = &mut ctx.accounts.signer1;
         let the_signer2: &mut Signer = &mut ctx.accounts.signer2;
@@ -38,17 +188,6 @@ pub mod day_14 {
     }
 }
 
-fn check(ctx: &Context<OnlyOwner>) -> Result<()> {
-    // Check if signer === owner
-    require_keys_eq!(
-        ctx.accounts.signer_account.key(),
-        OWNER.parse::<Pubkey>().unwrap(),
-        OnlyOwnerError::NotOwner
-    );
-
-    Ok(())
-}
-
 #[derive(Accounts)]
 pub struct Initialize<'info> {
     pub signer1: Signer<'info>,
@@ -57,14 +196,82 @@ pub struct Initialize<'info> {
 
 }
 
+#[derive(Accounts)]
+pub struct InitializeAccessControl<'info> {
+    #[account(init, payer = signer, space = size_of::<AccessControl>() + 8, seeds = [ACCESS_CONTROL_SEED], bump)]
+    pub access_control: Account<'info, AccessControl>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, crate::program::Day14>,
+
+    #[account(constraint = program_data.upgrade_authority_address == Some(signer.key()) @ Errors::NotUpgradeAuthority)]
+    pub program_data: Account<'info, ProgramData>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ManageRoles<'info> {
+    #[account(mut, seeds = [ACCESS_CONTROL_SEED], bump = access_control.bump)]
+    pub access_control: Account<'info, AccessControl>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RenounceRole<'info> {
+    #[account(mut, seeds = [ACCESS_CONTROL_SEED], bump = access_control.bump)]
+    pub access_control: Account<'info, AccessControl>,
+
+    pub signer: Signer<'info>,
+}
+
 #[derive(Accounts)]
 pub struct OnlyOwner<'info> {
+    #[account(seeds = [ACCESS_CONTROL_SEED], bump = access_control.bump)]
+    access_control: Account<'info, AccessControl>,
+
     signer_account: Signer<'info>,
 }
 
+#[derive(Accounts)]
+pub struct OnlyOperator<'info> {
+    #[account(
+        seeds = [ACCESS_CONTROL_SEED],
+        bump = access_control.bump,
+        constraint = access_control.has_role(&signer.key(), Role::Operator) @ Errors::MissingRole
+    )]
+    access_control: Account<'info, AccessControl>,
+
+    signer: Signer<'info>,
+}
+
 // An enum for custom error codes
 #[error_code]
-pub enum OnlyOwnerError {
-    #[msg("Only owner can call this function!")]
-    NotOwner,
+pub enum Errors {
+    #[msg("Signer does not have the required role")]
+    MissingRole,
+    #[msg("The last admin cannot be removed")]
+    LastAdmin,
+    #[msg("No free slot left for a new member")]
+    TooManyMembers,
+    #[msg("Roles cannot be granted to the default public key")]
+    InvalidMember,
+    #[msg("Owners must be unique, between 1 and 10 of them")]
+    InvalidOwners,
+    #[msg("Threshold must be between 1 and the number of owners")]
+    InvalidThreshold,
+    #[msg("Signer is not an owner of the multisig")]
+    NotAnOwner,
+    #[msg("The owners changed since the proposal")]
+    OwnerSetChanged,
+    #[msg("The proposal was already executed")]
+    AlreadyExecuted,
+    #[msg("Not enough owners approved the proposal")]
+    NotEnoughApprovals,
+    #[msg("Signer is not the program's upgrade authority")]
+    NotUpgradeAuthority,
 }
\ No newline at end of file
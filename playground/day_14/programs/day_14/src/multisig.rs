@@ -0,0 +1,169 @@
+// M-of-N multisig executing arbitrary instructions.
+//
+// A `Multisig` has a set of owners and a threshold. Any owner can `propose`
+// an instruction, stored in a `Transaction` account, owners `approve` it, and
+// once `threshold` owners did, anyone can `execute` it. The instruction then
+// runs through CPI, signed by the multisig PDA (seeds `[b"multisig",
+// multisig]`), so whatever trusts that PDA is gated by the multisig:
+//
+// - a `day_26_crowdfund` style withdrawal, by using the PDA as the allowed
+//   withdrawal signer,
+// - program upgrades, by making the PDA the program's upgrade authority and
+//   proposing a BPF upgradeable loader `Upgrade` instruction.
+//
+// The owner set and threshold are changed the same way, by proposing
+// `set_owners` or `change_threshold` on this program: both require the
+// multisig PDA as signer. Changing the owners invalidates pending proposals.
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+
+use crate::Errors;
+
+pub const MULTISIG_SEED: &[u8] = b"multisig";
+pub const MAX_OWNERS: usize = 10;
+
+#[account]
+pub struct Multisig {
+    pub owners:          Vec<Pubkey>,
+    pub threshold:       u8,
+    pub signer_bump:     u8,  // bump of the multisig PDA
+    pub owner_set_seqno: u32, // bumped on every owner change
+}
+
+impl Multisig {
+    pub const SPACE: usize = 8 + 4 + 32 * MAX_OWNERS + 1 + 1 + 4;
+
+    pub fn owner_index(&self, key: &Pubkey) -> Result<usize> {
+        self.owners
+            .iter()
+            .position(|owner| owner == key)
+            .ok_or_else(|| error!(Errors::NotAnOwner))
+    }
+}
+
+// Owners must be unique, at most MAX_OWNERS, and the threshold reachable
+pub fn check_owners(owners: &[Pubkey], threshold: u8) -> Result<()> {
+    require!(!owners.is_empty() && owners.len() <= MAX_OWNERS, Errors::InvalidOwners);
+
+    for (i, owner) in owners.iter().enumerate() {
+        require!(!owners[..i].contains(owner), Errors::InvalidOwners);
+    }
+
+    require!(threshold > 0 && threshold as usize <= owners.len(), Errors::InvalidThreshold);
+    Ok(())
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct TransactionAccount {
+    pub pubkey:      Pubkey,
+    pub is_signer:   bool,
+    pub is_writable: bool,
+}
+
+#[account]
+pub struct Transaction {
+    pub multisig:        Pubkey,
+    pub proposer:        Pubkey,
+    pub program_id:      Pubkey,
+    pub accounts:        Vec<TransactionAccount>,
+    pub data:            Vec<u8>,
+    pub approvals:       Vec<bool>, // one per owner, same order
+    pub did_execute:     bool,
+    pub owner_set_seqno: u32,
+}
+
+impl Transaction {
+    pub fn space(accounts: usize, data: usize) -> usize {
+        8 + 32 + 32 + 32 + 4 + accounts * (32 + 1 + 1) + 4 + data + 4 + MAX_OWNERS + 1 + 4
+    }
+
+    pub fn approval_count(&self) -> usize {
+        self.approvals.iter().filter(|approved| **approved).count()
+    }
+
+    // The instruction to run, with the multisig PDA as a signer
+    pub fn instruction(&self, multisig_signer: &Pubkey) -> Instruction {
+        Instruction {
+            program_id: self.program_id,
+            accounts:   self
+                .accounts
+                .iter()
+                .map(|account| AccountMeta {
+                    pubkey:      account.pubkey,
+                    is_signer:   account.is_signer || account.pubkey == *multisig_signer,
+                    is_writable: account.is_writable,
+                })
+                .collect(),
+            data:       self.data.clone(),
+        }
+    }
+}
+
+#[derive(Accounts)]
+pub struct CreateMultisig<'info> {
+    #[account(init, payer = payer, space = Multisig::SPACE)]
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(program_id: Pubkey, accounts: Vec<TransactionAccount>, data: Vec<u8>)]
+pub struct Propose<'info> {
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(init, payer = proposer, space = Transaction::space(accounts.len(), data.len()))]
+    pub transaction: Account<'info, Transaction>,
+
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Approve<'info> {
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(mut, has_one = multisig)]
+    pub transaction: Account<'info, Transaction>,
+
+    pub owner: Signer<'info>,
+}
+
+// The accounts of the proposed instruction, its program included, go in
+// `remaining_accounts`
+#[derive(Accounts)]
+pub struct Execute<'info> {
+    pub multisig: Account<'info, Multisig>,
+
+    /// CHECK: only used as the CPI signer
+    #[account(seeds = [MULTISIG_SEED, multisig.key().as_ref()], bump = multisig.signer_bump)]
+    pub multisig_signer: UncheckedAccount<'info>,
+
+    #[account(mut, has_one = multisig)]
+    pub transaction: Account<'info, Transaction>,
+}
+
+// Closes the proposal and refunds its rent to the proposer
+#[derive(Accounts)]
+pub struct Cancel<'info> {
+    #[account(mut, has_one = proposer, close = proposer)]
+    pub transaction: Account<'info, Transaction>,
+
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+}
+
+// Only reachable through `execute`
+#[derive(Accounts)]
+pub struct Auth<'info> {
+    #[account(mut)]
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(seeds = [MULTISIG_SEED, multisig.key().as_ref()], bump = multisig.signer_bump)]
+    pub multisig_signer: Signer<'info>,
+}
@@ -1,6 +1,7 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { Day14 } from "../target/types/day_14";
+import { expect } from "chai";
 
 describe("day_14", () => {
   // Configure the client to use the local cluster.
@@ -13,6 +14,15 @@ describe("day_14", () => {
   let myKeypair2 = anchor.web3.Keypair.generate();
   let notOwner   = anchor.web3.Keypair.generate();
 
+  const expectError = async (promise: Promise<unknown>, code: string) => {
+    try {
+      await promise;
+      expect.fail(`should have failed with ${code}`);
+    } catch (e) {
+      expect(e.toString()).to.contain(code);
+    }
+  };
+
   it("Is signed by multiple signers", async () => {
     // Add your test here.
     const tx = await program.methods
@@ -45,29 +55,323 @@ describe("day_14", () => {
     console.log("The signer3: ", myKeypair2.publicKey.toBase58());
   });
 
-  it("Is called by the owner", async () => {
-    // Add your test here.
-    const tx = await program.methods
-      .ownerOnly()
-      .accounts({
-        signerAccount: program.provider.publicKey,
-      })
-      .rpc();
+  describe("access control", () => {
+    const admin    = program.provider.publicKey;
+    const operator = anchor.web3.Keypair.generate();
+
+    const [accessControl] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("access_control")],
+      program.programId
+    );
+
+    const grant = (role: any, account: anchor.web3.PublicKey) =>
+      program.methods.grantRole(role, account).accounts({ accessControl, admin }).rpc();
+
+    const revoke = (role: any, account: anchor.web3.PublicKey) =>
+      program.methods.revokeRole(role, account).accounts({ accessControl, admin }).rpc();
+
+    const ownerOnly = (signer: anchor.web3.Keypair) =>
+      program.methods
+        .ownerOnly()
+        .accounts({ accessControl, signerAccount: signer.publicKey })
+        .signers([signer])
+        .rpc();
+
+    const operatorOnly = (signer: anchor.web3.Keypair) =>
+      program.methods
+        .operatorOnly()
+        .accounts({ accessControl, signer: signer.publicKey })
+        .signers([signer])
+        .rpc();
+
+    const rolesOf = async (account: anchor.web3.PublicKey) => {
+      const { members } = await program.account.accessControl.fetch(accessControl);
+      const member = members.find((m) => m.key.equals(account));
+      return member ? member.roles : 0;
+    };
+
+    // `anchor test` deploys with the upgradeable loader (`[test] upgradeable`
+    // in Anchor.toml), which writes this account with the wallet as upgrade
+    // authority
+    const [programData] = anchor.web3.PublicKey.findProgramAddressSync(
+      [program.programId.toBuffer()],
+      new anchor.web3.PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+    );
+
+    const initialize = (signer: anchor.web3.PublicKey) =>
+      program.methods
+        .initializeAccessControl()
+        .accounts({
+          accessControl,
+          signer,
+          program      : program.programId,
+          programData,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        });
+
+    it("Is only initialized by the upgrade authority", async () => {
+      const provider = program.provider as anchor.AnchorProvider;
+      await provider.sendAndConfirm(
+        new anchor.web3.Transaction().add(
+          anchor.web3.SystemProgram.transfer({
+            fromPubkey: admin,
+            toPubkey  : notOwner.publicKey,
+            lamports  : anchor.web3.LAMPORTS_PER_SOL,
+          })
+        )
+      );
 
-    console.log("Transaction hash:", tx);
+      await expectError(initialize(notOwner.publicKey).signers([notOwner]).rpc(), "NotUpgradeAuthority");
+
+      await initialize(admin).rpc();
+      expect(await rolesOf(admin)).to.equal(1);
+    });
+
+    it("Is called by the owner", async () => {
+      const tx = await program.methods
+        .ownerOnly()
+        .accounts({
+          accessControl,
+          signerAccount: admin,
+        })
+        .rpc();
+
+      console.log("Transaction hash:", tx);
+    });
+
+    it("Is NOT called by the owner", async () => {
+      await expectError(ownerOnly(notOwner), "MissingRole");
+    });
+
+    it("Grants and revokes a role", async () => {
+      await expectError(operatorOnly(operator), "MissingRole");
+
+      await grant({ operator: {} }, operator.publicKey);
+      expect(await rolesOf(operator.publicKey)).to.equal(0b010);
+      await operatorOnly(operator);
+
+      // Being an operator does not make an admin
+      await expectError(ownerOnly(operator), "MissingRole");
+
+      await revoke({ operator: {} }, operator.publicKey);
+      expect(await rolesOf(operator.publicKey)).to.equal(0);
+      await expectError(operatorOnly(operator), "MissingRole");
+    });
+
+    it("Only lets admins grant roles", async () => {
+      await expectError(
+        program.methods
+          .grantRole({ admin: {} }, notOwner.publicKey)
+          .accounts({ accessControl, admin: notOwner.publicKey })
+          .signers([notOwner])
+          .rpc(),
+        "MissingRole"
+      );
+    });
+
+    it("Hands the admin role over", async () => {
+      const newAdmin = anchor.web3.Keypair.generate();
+      await grant({ admin: {} }, newAdmin.publicKey);
+      await ownerOnly(newAdmin);
+
+      await program.methods
+        .renounceRole({ admin: {} })
+        .accounts({ accessControl, signer: newAdmin.publicKey })
+        .signers([newAdmin])
+        .rpc();
+      await expectError(ownerOnly(newAdmin), "MissingRole");
+    });
+
+    it("Keeps the last admin", async () => {
+      await expectError(revoke({ admin: {} }, admin), "LastAdmin");
+      await expectError(
+        program.methods.renounceRole({ admin: {} }).accounts({ accessControl, signer: admin }).rpc(),
+        "LastAdmin"
+      );
+    });
   });
 
-  it("Is NOT called by the owner", async () => {
-    // Add your test here.
-    const tx = await program.methods
-      .ownerOnly()
-      .accounts({
-        signerAccount: notOwner.publicKey,
-      })
-      .signers([notOwner])
-      .rpc();
 
-    console.log("Transaction hash:", tx);
+  describe("multisig", () => {
+    const owners   = [anchor.web3.Keypair.generate(), anchor.web3.Keypair.generate(), anchor.web3.Keypair.generate()];
+    const multisig = anchor.web3.Keypair.generate();
+
+    const [multisigSigner] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("multisig"), multisig.publicKey.toBuffer()],
+      program.programId
+    );
+
+    const propose = async (
+      instruction: anchor.web3.TransactionInstruction,
+      proposer = owners[0],
+      transaction = anchor.web3.Keypair.generate()
+    ) => {
+      const accounts = instruction.keys.map((key) => ({
+        pubkey    : key.pubkey,
+        isSigner  : key.isSigner,
+        isWritable: key.isWritable,
+      }));
+
+      await program.methods
+        .propose(instruction.programId, accounts, instruction.data)
+        .accounts({
+          multisig     : multisig.publicKey,
+          transaction  : transaction.publicKey,
+          proposer     : proposer.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([transaction, proposer])
+        .rpc();
+      return transaction.publicKey;
+    };
+
+    const approve = (transaction: anchor.web3.PublicKey, owner: anchor.web3.Keypair) =>
+      program.methods
+        .approve()
+        .accounts({ multisig: multisig.publicKey, transaction, owner: owner.publicKey })
+        .signers([owner])
+        .rpc();
+
+    // The multisig PDA signs through invoke_signed, so it is not a signer here
+    const execute = (transaction: anchor.web3.PublicKey, instruction: anchor.web3.TransactionInstruction) =>
+      program.methods
+        .execute()
+        .accounts({ multisig: multisig.publicKey, multisigSigner, transaction })
+        .remainingAccounts([
+          ...instruction.keys.map((key) => ({ ...key, isSigner: false })),
+          { pubkey: instruction.programId, isSigner: false, isWritable: false },
+        ])
+        .rpc();
+
+    before(async () => {
+      const provider = program.provider as anchor.AnchorProvider;
+
+      await program.methods
+        .createMultisig(owners.map((owner) => owner.publicKey), 2)
+        .accounts({
+          multisig     : multisig.publicKey,
+          payer        : provider.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([multisig])
+        .rpc();
+
+      // Owners pay for their proposals, the multisig PDA holds funds to move
+      const funding = new anchor.web3.Transaction().add(
+        ...[...owners.map((owner) => owner.publicKey), multisigSigner].map((toPubkey) =>
+          anchor.web3.SystemProgram.transfer({
+            fromPubkey: provider.publicKey,
+            toPubkey,
+            lamports  : anchor.web3.LAMPORTS_PER_SOL,
+          })
+        )
+      );
+      await provider.sendAndConfirm(funding);
+    });
+
+    it("Rejects an invalid owner set", async () => {
+      const owner = anchor.web3.Keypair.generate().publicKey;
+      const other = anchor.web3.Keypair.generate();
+
+      await expectError(
+        program.methods
+          .createMultisig([owner, owner], 1)
+          .accounts({ multisig: other.publicKey, payer: program.provider.publicKey })
+          .signers([other])
+          .rpc(),
+        "InvalidOwners"
+      );
+      await expectError(
+        program.methods
+          .createMultisig([owner], 2)
+          .accounts({ multisig: other.publicKey, payer: program.provider.publicKey })
+          .signers([other])
+          .rpc(),
+        "InvalidThreshold"
+      );
+    });
+
+    it("Executes a transfer once the threshold is met", async () => {
+      const recipient = anchor.web3.Keypair.generate().publicKey;
+      const lamports  = 10_000_000;
+      const transfer  = anchor.web3.SystemProgram.transfer({ fromPubkey: multisigSigner, toPubkey: recipient, lamports });
+
+      const transaction = await propose(transfer);
+      await expectError(execute(transaction, transfer), "NotEnoughApprovals");
+
+      await approve(transaction, owners[1]);
+      await execute(transaction, transfer);
+      expect(await program.provider.connection.getBalance(recipient)).to.equal(lamports);
+
+      await expectError(execute(transaction, transfer), "AlreadyExecuted");
+    });
+
+    // `did_execute` is saved before the CPI, so the nested call sees it
+    it("Cannot execute a proposal from within itself", async () => {
+      const transaction = anchor.web3.Keypair.generate();
+      const reenter = await program.methods
+        .execute()
+        .accounts({ multisig: multisig.publicKey, multisigSigner, transaction: transaction.publicKey })
+        .instruction();
+
+      await propose(reenter, owners[0], transaction);
+      await approve(transaction.publicKey, owners[1]);
+
+      await expectError(execute(transaction.publicKey, reenter), "AlreadyExecuted");
+      expect((await program.account.transaction.fetch(transaction.publicKey)).didExecute).to.be.false;
+    });
+
+    it("Only lets owners propose and approve", async () => {
+      const stranger = anchor.web3.Keypair.generate();
+      const transfer = anchor.web3.SystemProgram.transfer({
+        fromPubkey: multisigSigner,
+        toPubkey  : stranger.publicKey,
+        lamports  : 1,
+      });
+
+      const transaction = await propose(transfer);
+      await expectError(approve(transaction, stranger), "NotAnOwner");
+    });
+
+    it("Cancels a proposal", async () => {
+      const transfer = anchor.web3.SystemProgram.transfer({
+        fromPubkey: multisigSigner,
+        toPubkey  : owners[0].publicKey,
+        lamports  : 1,
+      });
+
+      const transaction = await propose(transfer);
+      await program.methods
+        .cancel()
+        .accounts({ transaction, proposer: owners[0].publicKey })
+        .signers([owners[0]])
+        .rpc();
+
+      expect(await program.provider.connection.getAccountInfo(transaction)).to.be.null;
+    });
+
+    it("Changes its own threshold", async () => {
+      const changeThreshold = await program.methods
+        .changeThreshold(3)
+        .accounts({ multisig: multisig.publicKey, multisigSigner })
+        .instruction();
+
+      const transaction = await propose(changeThreshold);
+      await approve(transaction, owners[2]);
+      await execute(transaction, changeThreshold);
+
+      expect((await program.account.multisig.fetch(multisig.publicKey)).threshold).to.equal(3);
+    });
+
+    it("Cannot change its settings without a proposal", async () => {
+      await expectError(
+        program.methods
+          .changeThreshold(1)
+          .accounts({ multisig: multisig.publicKey, multisigSigner })
+          .rpc(),
+        "Signature verification failed"
+      );
+    });
   });
 
 });
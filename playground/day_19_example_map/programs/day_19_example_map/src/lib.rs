@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use macro_demo::{pda, pda_accounts};
 use std::mem::size_of;
 
 declare_id!("x5MkyBjzr9UynGFks6HmSQgWD4qAcsdmff8JTvgHyv5");
@@ -22,6 +23,7 @@ pub mod day_19_example_map {
     
 }
 
+#[pda_accounts]
 #[derive(Accounts)]
 #[instruction(key1: u64, key2: u64, key3: u64)]    // new key args added
 //#[instruction(key1: u64, key2: u64)]    // new key args added
@@ -31,7 +33,7 @@ pub struct Initialize<'info> {
     #[account(init,
               payer = signer,
               space = size_of::<Val>() + 8,
-              seeds =[&key1.to_le_bytes().as_ref(), &key2.to_le_bytes().as_ref(), &key3.to_le_bytes().as_ref()], // 3 seeds
+              seeds = [Val::seeds(key1, key2, key3)], // 3 seeds, see `Val`
               //seeds =[&key1.to_le_bytes().as_ref(), &key2.to_le_bytes().as_ref()], // 2 seeds
               //seeds =[&key.to_le_bytes().as_ref()],
               bump)]
@@ -43,7 +45,9 @@ pub struct Initialize<'info> {
     system_program: Program<'info, System>,
 }
 
+// The value at (key1, key2, key3) in the map
 #[account]
+#[pda(seeds = [key1: u64, key2: u64, key3: u64])]
 pub struct Val {
     value: u64,
 }
@@ -0,0 +1,77 @@
+// Off-chain helpers for day_24_points: the addresses of its accounts, from
+// the same seeds as the program, and its instructions with those accounts
+// filled in, ready to be signed and sent.
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::{system_program, InstructionData};
+
+use day_24_points::pause::PauseState;
+use day_24_points::{accounts, instruction, Player};
+
+pub use day_24_points::ID;
+
+// The account of the player playing with `authority`
+pub fn player_address(authority: &Pubkey) -> Pubkey {
+	Player::find_address(authority).0
+}
+
+pub fn pause_state_address() -> Pubkey {
+	PauseState::find_address().0
+}
+
+// Written by the upgradeable loader when the program is deployed with it
+// (`[test] upgradeable` in Anchor.toml for `anchor test`)
+pub fn program_data_address() -> Pubkey {
+	Pubkey::find_program_address(&[ID.as_ref()], &bpf_loader_upgradeable::ID).0
+}
+
+// Signed by `signer`, who pays for their player account
+pub fn initialize(signer: &Pubkey) -> Instruction {
+	let accounts = accounts::Initialize {
+		player:         player_address(signer),
+		signer:         *signer,
+		pause_state:    pause_state_address(),
+		system_program: system_program::ID,
+	};
+
+	Instruction {
+		program_id: ID,
+		accounts:   accounts.to_account_metas(None),
+		data:       instruction::Initialize {}.data(),
+	}
+}
+
+// Signed by `from`, the authority of the player giving the points
+pub fn transfer_points(from: &Pubkey, to: &Pubkey, amount: u32) -> Instruction {
+	let accounts = accounts::TransferPoints {
+		from:        player_address(from),
+		to:          player_address(to),
+		signer:      *from,
+		pause_state: pause_state_address(),
+	};
+
+	Instruction {
+		program_id: ID,
+		accounts:   accounts.to_account_metas(None),
+		data:       instruction::TransferPoints { amount }.data(),
+	}
+}
+
+// Signed by the program's upgrade authority, right after deploying it:
+// every other instruction fails until the pause state exists
+pub fn initialize_pause_state(upgrade_authority: &Pubkey) -> Instruction {
+	let accounts = accounts::InitializePauseState {
+		pause_state:    pause_state_address(),
+		signer:         *upgrade_authority,
+		program:        ID,
+		program_data:   program_data_address(),
+		system_program: system_program::ID,
+	};
+
+	Instruction {
+		program_id: ID,
+		accounts:   accounts.to_account_metas(None),
+		data:       instruction::InitializePauseState {}.data(),
+	}
+}
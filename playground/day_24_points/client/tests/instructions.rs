@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+
+use day_24_points_client::*;
+
+#[test]
+fn instructions() {
+	let alice = Pubkey::new_unique();
+	let bob = Pubkey::new_unique();
+
+	let initialize = initialize(&alice);
+	let keys: Vec<Pubkey> = initialize.accounts.iter().map(|meta| meta.pubkey).collect();
+	assert_eq!(keys[..3], [player_address(&alice), alice, pause_state_address()]);
+	assert!(initialize.accounts[1].is_signer);
+
+	let transfer = transfer_points(&alice, &bob, 5);
+	let keys: Vec<Pubkey> = transfer.accounts.iter().map(|meta| meta.pubkey).collect();
+	assert_eq!(keys, [player_address(&alice), player_address(&bob), alice, pause_state_address()]);
+	assert_eq!(transfer.data[8..], 5u32.to_le_bytes());
+
+	let initialize_pause_state = initialize_pause_state(&alice);
+	let keys: Vec<Pubkey> = initialize_pause_state.accounts.iter().map(|meta| meta.pubkey).collect();
+	assert_eq!(keys[..4], [pause_state_address(), alice, ID, program_data_address()]);
+}
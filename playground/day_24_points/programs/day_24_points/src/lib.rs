@@ -1,5 +1,9 @@
 use anchor_lang::prelude::*;
-use std::mem::size_of;
+use macro_demo::{pda, pda_accounts, Space};
+
+pub mod pause;
+
+use pause::*;
 
 declare_id!("9QH4YgSpf13KgknT7N4UfNsKyNjXT9YrkU4BgTaW55pL");
 
@@ -10,6 +14,8 @@ pub mod day_24_points {
 	use super::*;
 
 	pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
+		ctx.accounts.pause_state.require_not_paused(Scope::Initialize)?;
+
 		ctx.accounts.player.points = STARTING_POINTS;
 		ctx.accounts.player.authority = ctx.accounts.signer.key();
 		Ok(())
@@ -17,6 +23,8 @@ pub mod day_24_points {
 
 	pub fn transfer_points(ctx: Context<TransferPoints>,
 						   amount: u32) -> Result<()> {
+		ctx.accounts.pause_state.require_not_paused(Scope::TransferPoints)?;
+
 		require!(ctx.accounts.from.authority == ctx.accounts.signer.key(),
 								 Errors::SignerIsNotAuthority);
 		require!(ctx.accounts.from.points >= amount,
@@ -26,6 +34,25 @@ pub mod day_24_points {
 		ctx.accounts.to.points += amount;
 		Ok(())
 	}
+
+	// PAUSE
+	// -----
+
+	pub fn initialize_pause_state(ctx: Context<InitializePauseState>) -> Result<()> {
+		ctx.accounts.initialize(ctx.bumps.pause_state)
+	}
+
+	pub fn pause(ctx: Context<SetPause>, scope: Scope) -> Result<()> {
+		ctx.accounts.pause(scope)
+	}
+
+	pub fn unpause(ctx: Context<SetPause>, scope: Scope) -> Result<()> {
+		ctx.accounts.unpause(scope)
+	}
+
+	pub fn set_pauser(ctx: Context<SetPause>, new_pauser: Pubkey) -> Result<()> {
+		ctx.accounts.set_pauser(new_pauser)
+	}
 }
 
 #[error_code]
@@ -33,19 +60,26 @@ pub enum Errors {
 	#[msg("SignerIsNotAuthority")]
 	SignerIsNotAuthority,
 	#[msg("InsufficientPoints")]
-	InsufficientPoints
+	InsufficientPoints,
+	#[msg("This instruction is paused")]
+	Paused,
+	#[msg("Signer is not the program's upgrade authority")]
+	NotUpgradeAuthority,
 }
 
+#[pda_accounts]
 #[derive(Accounts)]
 pub struct Initialize<'info> {
 	#[account(init,
 			  payer = signer,
-			  space = size_of::<Player>() + 8,
-			  seeds = [&(signer.as_ref().key().to_bytes())],
+			  space = Player::INIT_SPACE + 8,
+			  seeds = [Player::seeds(signer.key())],
 			  bump)]
 	player: Account<'info, Player>,
 	#[account(mut)]
 	signer: Signer<'info>,
+	#[account(seeds = [PauseState::SEED], bump = pause_state.bump)]
+	pause_state: Account<'info, PauseState>,
 	system_program: Program<'info, System>,
 }
 
@@ -57,9 +91,14 @@ pub struct TransferPoints<'info> {
 	to: Account<'info, Player>,
 	#[account(mut)]
 	signer: Signer<'info>,
+	#[account(seeds = [PauseState::SEED], bump = pause_state.bump)]
+	pause_state: Account<'info, PauseState>,
 }
 
+// One player per authority, at the address of the authority's key
 #[account]
+#[derive(Space)]
+#[pda(seeds = [authority: Pubkey])]
 pub struct Player {
 	points: u32,
 	authority: Pubkey
@@ -1,6 +1,7 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { Day24Points } from "../target/types/day_24_points";
+import { expect } from "chai";
 
 async function confirmTransaction(tx) {
   const latestBlockHash = await anchor.getProvider().connection.getLatestBlockhash();
@@ -17,6 +18,65 @@ describe("day_24_points", () => {
 
   const program = anchor.workspace.Day24Points as Program<Day24Points>;
 
+  const pauser = anchor.getProvider().publicKey;
+  const [pauseState] = anchor.web3.PublicKey.findProgramAddressSync([Buffer.from("pause_state")], program.programId);
+
+  // `anchor test` deploys with the upgradeable loader (`[test] upgradeable`
+  // in Anchor.toml), which writes this account with the wallet as upgrade
+  // authority, the only one allowed to initialize the pause state
+  const [programData] = anchor.web3.PublicKey.findProgramAddressSync(
+    [program.programId.toBuffer()],
+    new anchor.web3.PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+  );
+
+  const expectError = async (promise: Promise<unknown>, code: string) => {
+    try {
+      await promise;
+      expect.fail(`should have failed with ${code}`);
+    } catch (e) {
+      expect(e.toString()).to.contain(code);
+    }
+  };
+
+  // A player with an account and some SOL
+  const newPlayer = async () => {
+    const keypair = anchor.web3.Keypair.generate();
+    await confirmTransaction(
+      await anchor.getProvider().connection.requestAirdrop(keypair.publicKey, anchor.web3.LAMPORTS_PER_SOL)
+    );
+
+    const [player] = anchor.web3.PublicKey.findProgramAddressSync([keypair.publicKey.toBytes()], program.programId);
+    await program.methods.initialize().accounts({
+      player,
+      signer: keypair.publicKey,
+      pauseState,
+    }).signers([keypair]).rpc();
+
+    return { keypair, player };
+  };
+
+  const initializePauseState = (signer: anchor.web3.Keypair) =>
+    program.methods
+      .initializePauseState()
+      .accounts({ pauseState, signer: signer.publicKey, program: program.programId, programData })
+      .signers([signer])
+      .rpc();
+
+  before(async () => {
+    // nobody can take the pauser role before the upgrade authority
+    const intruder = anchor.web3.Keypair.generate();
+    await confirmTransaction(
+      await anchor.getProvider().connection.requestAirdrop(intruder.publicKey, anchor.web3.LAMPORTS_PER_SOL)
+    );
+    await expectError(initializePauseState(intruder), "NotUpgradeAuthority");
+
+    await program.methods
+      .initializePauseState()
+      .accounts({ pauseState, program: program.programId, programData })
+      .rpc();
+    expect((await program.account.pauseState.fetch(pauseState)).pauser).to.deep.equal(pauser);
+  });
+
   it("Alice transfers points to Bob", async () => {
     const alice   = anchor.web3.Keypair.generate();
     const bob     = anchor.web3.Keypair.generate();
@@ -38,11 +98,13 @@ describe("day_24_points", () => {
     await program.methods.initialize().accounts({
       player: playerAlice,
       signer: alice.publicKey,
+      pauseState,
     }).signers([alice]).rpc();
 
     await program.methods.initialize().accounts({
       player: playerBob,
       signer: bob.publicKey,
+      pauseState,
     }).signers([bob]).rpc();
 
     // Alice transfers 5 points to Bob. Note that this is a u32
@@ -51,9 +113,39 @@ describe("day_24_points", () => {
       from: playerAlice,
       to: playerBob,
       signer: alice.publicKey,
+      pauseState,
     }).signers([alice]).rpc();
 
     console.log(`Alice has ${(await program.account.player.fetch(playerAlice)).points} points`);
     console.log(`Bob   has ${(await program.account.player.fetch(playerBob)).points} points`)
   });
+
+  it("Cannot transfer points while paused", async () => {
+    const alice = await newPlayer();
+    const bob   = await newPlayer();
+
+    const transfer = () =>
+      program.methods.transferPoints(1).accounts({
+        from: alice.player,
+        to: bob.player,
+        signer: alice.keypair.publicKey,
+        pauseState,
+      }).signers([alice.keypair]).rpc();
+
+    await program.methods.pause({ transferPoints: {} }).accounts({ pauseState, pauser }).rpc();
+    await expectError(transfer(), "Paused");
+
+    // New players can still join
+    await newPlayer();
+
+    await program.methods.unpause({ transferPoints: {} }).accounts({ pauseState, pauser }).rpc();
+    await transfer();
+    expect((await program.account.player.fetch(bob.player)).points).to.equal(11);
+  });
+
+  it("Cannot create players while everything is paused", async () => {
+    await program.methods.pause({ all: {} }).accounts({ pauseState, pauser }).rpc();
+    await expectError(newPlayer(), "Paused");
+    await program.methods.unpause({ all: {} }).accounts({ pauseState, pauser }).rpc();
+  });
 });
@@ -3,12 +3,15 @@ use anchor_lang::system_program;
 use std::mem::size_of;
 use std::str::FromStr;
 
+pub mod pause;
+
+use pause::*;
+
 declare_id!("GWgQs8RpM9WcefHwSyCZsgy459bKiwMXtqh4dv5h7FaX");
 
 #[program]
 pub mod day_26_crowdfund {
     use super::*;
-    use super::*;
 
     pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
         let initialized_pda = &mut ctx.accounts.pda;
@@ -16,6 +19,8 @@ pub mod day_26_crowdfund {
     }
 
     pub fn donate(ctx: Context<Donate>, amount: u64) -> Result<()> {
+        ctx.accounts.pause_state.require_not_paused(Scope::Donate)?;
+
         let cpi_context = CpiContext::new(
             ctx.accounts.system_program.to_account_info(),
             system_program::Transfer {
@@ -30,6 +35,8 @@ pub mod day_26_crowdfund {
     }
 
     pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        ctx.accounts.pause_state.require_not_paused(Scope::Withdraw)?;
+
         // Work fine with Anchor 0.29
         ctx.accounts.pda.sub_lamports(amount)?;
         ctx.accounts.signer.add_lamports(amount)?;
@@ -39,6 +46,25 @@ pub mod day_26_crowdfund {
         // **ctx.accounts.signer.to_account_info().try_borrow_mut_lamports()? += amount;
         Ok(())
     }
+
+    // PAUSE
+    // -----
+
+    pub fn initialize_pause_state(ctx: Context<InitializePauseState>) -> Result<()> {
+        ctx.accounts.initialize(ctx.bumps.pause_state)
+    }
+
+    pub fn pause(ctx: Context<SetPause>, scope: Scope) -> Result<()> {
+        ctx.accounts.pause(scope)
+    }
+
+    pub fn unpause(ctx: Context<SetPause>, scope: Scope) -> Result<()> {
+        ctx.accounts.unpause(scope)
+    }
+
+    pub fn set_pauser(ctx: Context<SetPause>, new_pauser: Pubkey) -> Result<()> {
+        ctx.accounts.set_pauser(new_pauser)
+    }
 }
 
 #[derive(Accounts)]
@@ -58,6 +84,9 @@ pub struct Donate<'info> {
 
     #[account(mut)]
     pub pda: Account<'info, Pda>,
+
+    #[account(seeds = [PauseState::SEED], bump = pause_state.bump)]
+    pub pause_state: Account<'info, PauseState>,
     pub system_program: Program<'info, System>,
 }
 
@@ -68,7 +97,18 @@ pub struct Withdraw<'info> {
 
     #[account(mut)]
     pub pda: Account<'info, Pda>,
+
+    #[account(seeds = [PauseState::SEED], bump = pause_state.bump)]
+    pub pause_state: Account<'info, PauseState>,
 }
 
 #[account]
 pub struct Pda {}
+
+#[error_code]
+pub enum Errors {
+    #[msg("This instruction is paused")]
+    Paused,
+    #[msg("Signer is not the program's upgrade authority")]
+    NotUpgradeAuthority,
+}
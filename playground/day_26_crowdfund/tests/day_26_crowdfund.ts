@@ -1,6 +1,7 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { Day26Crowdfund } from "../target/types/day_26_crowdfund";
+import { expect } from "chai";
 
 describe("day_26_crowdfund", () => {
   // Configure the client to use the local cluster.
@@ -8,19 +9,40 @@ describe("day_26_crowdfund", () => {
 
   const program = anchor.workspace.Day26Crowdfund as Program<Day26Crowdfund>;
 
-  it("Is initialized!", async () => {
-    const programId = await program.account.pda.programId;
+  const pauser = anchor.getProvider().publicKey;
+  const [pauseState] = anchor.web3.PublicKey.findProgramAddressSync([Buffer.from("pause_state")], program.programId);
+  // `anchor test` deploys with the upgradeable loader (`[test] upgradeable`
+  // in Anchor.toml), which writes this account with the wallet as upgrade
+  // authority, the only one allowed to initialize the pause state
+  const [programData] = anchor.web3.PublicKey.findProgramAddressSync(
+    [program.programId.toBuffer()],
+    new anchor.web3.PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+  );
+  const [pdaAccount] = anchor.web3.PublicKey.findProgramAddressSync([], program.programId);
 
-    let seeds = [];
-    let pdaAccount = anchor.web3.PublicKey.findProgramAddressSync(seeds, programId)[0];
+  const expectError = async (promise: Promise<unknown>, code: string) => {
+    try {
+      await promise;
+      expect.fail(`should have failed with ${code}`);
+    } catch (e) {
+      expect(e.toString()).to.contain(code);
+    }
+  };
 
+  it("Is initialized!", async () => {
     const tx = await program.methods.initialize().accounts({
       pda: pdaAccount
     }).rpc();
 
+    await program.methods
+      .initializePauseState()
+      .accounts({ pauseState, program: program.programId, programData })
+      .rpc();
+
     // transfer 2 SOL
     const tx2 = await program.methods.donate(new anchor.BN(2_000_000_000)).accounts({
-      pda: pdaAccount
+      pda: pdaAccount,
+      pauseState,
     }).rpc();
 
     console.log("lamport balance of pdaAccount",
@@ -29,7 +51,8 @@ describe("day_26_crowdfund", () => {
     // transfer back 1 SOL
     // the signer is the permitted address
     await program.methods.withdraw(new anchor.BN(1_000_000_000)).accounts({
-      pda: pdaAccount
+      pda: pdaAccount,
+      pauseState,
     }).rpc();
 
     console.log("lamport balance of pdaAccount",
@@ -37,4 +60,22 @@ describe("day_26_crowdfund", () => {
 
   });
 
+  it("Freezes withdrawals during an incident", async () => {
+    const withdraw = () =>
+      program.methods.withdraw(new anchor.BN(1_000)).accounts({ pda: pdaAccount, pauseState }).rpc();
+    const donate = () =>
+      program.methods.donate(new anchor.BN(1_000)).accounts({ pda: pdaAccount, pauseState }).rpc();
+
+    await program.methods.pause({ withdraw: {} }).accounts({ pauseState, pauser }).rpc();
+    await expectError(withdraw(), "Paused");
+    await donate();
+
+    await program.methods.pause({ all: {} }).accounts({ pauseState, pauser }).rpc();
+    await expectError(donate(), "Paused");
+
+    await program.methods.unpause({ all: {} }).accounts({ pauseState, pauser }).rpc();
+    await program.methods.unpause({ withdraw: {} }).accounts({ pauseState, pauser }).rpc();
+    await withdraw();
+  });
+
 });
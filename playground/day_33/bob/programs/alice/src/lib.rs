@@ -19,7 +19,9 @@ pub mod alice {
 		let cpi_ctx: CpiContext<BobAddOp> = CpiContext::new(
 			ctx.accounts.bob_program.to_account_info(),
 			BobAddOp {
-				bob_data_account: ctx.accounts.bob_data_account.to_account_info(),
+				bob_data_account  : ctx.accounts.bob_data_account.to_account_info(),
+				instruction_sysvar: ctx.accounts.instruction_sysvar.to_account_info(),
+				pause_state       : ctx.accounts.pause_state.to_account_info(),
 			}
 		);
 
@@ -32,6 +34,45 @@ pub mod alice {
 			return err!(Errors::CPIToBobFailed);
 		}
 	}
+
+	pub fn ask_bob_to_apply(ctx: Context<AliceOp>, op: Operation, a: u64, b: u64) -> Result<()> {
+		let cpi_ctx: CpiContext<BobAddOp> = CpiContext::new(
+			ctx.accounts.bob_program.to_account_info(),
+			BobAddOp {
+				bob_data_account  : ctx.accounts.bob_data_account.to_account_info(),
+				instruction_sysvar: ctx.accounts.instruction_sysvar.to_account_info(),
+				pause_state       : ctx.accounts.pause_state.to_account_info(),
+			}
+		);
+
+		bob::cpi::apply_op(cpi_ctx, op.into(), a, b)
+	}
+}
+
+// Mirror of `bob::Op`: the IDL only describes the types declared in this
+// crate, so using `bob::Op` directly as an argument would leave clients
+// without a definition for it
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Operation {
+	Add,
+	Sub,
+	Mul,
+	Div,
+	Mod,
+	Pow,
+}
+
+impl From<Operation> for bob::Op {
+	fn from(op: Operation) -> Self {
+		match op {
+			Operation::Add => bob::Op::Add,
+			Operation::Sub => bob::Op::Sub,
+			Operation::Mul => bob::Op::Mul,
+			Operation::Div => bob::Op::Div,
+			Operation::Mod => bob::Op::Mod,
+			Operation::Pow => bob::Op::Pow,
+		}
+	}
 }
 
 #[error_code]
@@ -46,4 +87,10 @@ pub struct AliceOp<'info> {
 	pub bob_data_account: Account<'info, BobData>,
 
 	pub bob_program: Program<'info, Bob>,
+
+	/// CHECK: forwarded to Bob, which checks the address
+	pub instruction_sysvar: AccountInfo<'info>,
+
+	/// CHECK: forwarded to Bob, which checks it is his pause state
+	pub pause_state: AccountInfo<'info>,
 }
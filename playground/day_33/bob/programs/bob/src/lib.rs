@@ -1,36 +1,229 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{get_stack_height, TRANSACTION_LEVEL_STACK_HEIGHT};
+use anchor_lang::solana_program::sysvar::instructions;
+use macro_demo::Accessors;
 use std::mem::size_of;
 
+pub mod pause;
+
+use pause::*;
+
 declare_id!("FNvGvvH3eHRUBRnSNxyrBjQsxnDipCdFox9NtQMm2sTi");
 
+// Alice depends on Bob (not the other way around), so her program id is
+// declared here instead of being imported from the alice crate
+pub mod alice_program {
+	use super::*;
+
+	declare_id!("FLuUHoSt8DqMW4JVnWRjSbz6JtZq6xxqMc6A3PWehP6q");
+}
+
 #[program]
 pub mod bob {
 	use super::*;
 
 	pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
-		msg!("Data Account Initialized: {}", ctx.accounts.bob_data_account.key());
+		let bob_data = &mut ctx.accounts.bob_data_account;
+		bob_data.authority   = ctx.accounts.signer.key();
+		bob_data.call_policy = CallPolicy::Any;
+
+		msg!("Data Account Initialized: {}", bob_data.key());
 
 		Ok(())
 	}
 
-	pub fn add_and_store(ctx: Context<BobAddOp>, a: u64, b: u64) -> Result<()> {
-		let result: u64 = a + b;
+	pub fn set_call_policy(ctx: Context<SetBobDataCallPolicy>, call_policy: CallPolicy) -> Result<()> {
+		ctx.accounts.apply(call_policy)?;
+		msg!("Call policy set to {:?}", call_policy);
 
-		// MODIFY/UPDATE THE DATA ACCOUNT
-		ctx.accounts.bob_data_account.result = result;
 		Ok(())
 	}
+
+	pub fn add_and_store(ctx: Context<BobAddOp>, a: u64, b: u64) -> Result<()> {
+		ctx.accounts.pause_state.require_not_paused(Scope::AddAndStore)?;
+		apply_and_store(ctx, Op::Add, a, b)
+	}
+
+	pub fn apply_op(ctx: Context<BobAddOp>, op: Op, a: u64, b: u64) -> Result<()> {
+		ctx.accounts.pause_state.require_not_paused(Scope::ApplyOp)?;
+		apply_and_store(ctx, op, a, b)
+	}
+
+	// PAUSE
+	// -----
+
+	pub fn initialize_pause_state(ctx: Context<InitializePauseState>) -> Result<()> {
+		ctx.accounts.initialize(ctx.bumps.pause_state)
+	}
+
+	pub fn pause(ctx: Context<SetPause>, scope: Scope) -> Result<()> {
+		ctx.accounts.pause(scope)
+	}
+
+	pub fn unpause(ctx: Context<SetPause>, scope: Scope) -> Result<()> {
+		ctx.accounts.unpause(scope)
+	}
+
+	pub fn set_pauser(ctx: Context<SetPause>, new_pauser: Pubkey) -> Result<()> {
+		ctx.accounts.set_pauser(new_pauser)
+	}
+}
+
+fn apply_and_store(ctx: Context<BobAddOp>, op: Op, a: u64, b: u64) -> Result<()> {
+	check_caller(
+		ctx.accounts.bob_data_account.call_policy,
+		&ctx.accounts.instruction_sysvar,
+	)?;
+	lock(&mut ctx.accounts.bob_data_account)?;
+
+	let result: u64 = op.apply(a, b)?;
+	msg!("{:?}({}, {}) = {}", op, a, b, result);
+
+	// MODIFY/UPDATE THE DATA ACCOUNT
+	let bob_data = &mut ctx.accounts.bob_data_account;
+	bob_data.result = result;
+	bob_data.op     = op;
+	bob_data.slot   = Clock::get()?.slot;
+
+	unlock(bob_data);
+	Ok(())
+}
+
+// Check who is calling Bob against the policy stored in the data account.
+//
+// The stack height tells how deep in the CPI chain we are (1 = top-level
+// instruction of the transaction, 2 = first CPI, ...) and the instructions
+// sysvar tells which program owns the top-level instruction being executed.
+fn check_caller(call_policy: CallPolicy, instruction_sysvar: &AccountInfo) -> Result<()> {
+	let stack_height = get_stack_height();
+
+	match call_policy {
+		CallPolicy::Any => {}
+		CallPolicy::TopLevelOnly => {
+			require_eq!(stack_height, TRANSACTION_LEVEL_STACK_HEIGHT, Errors::NotTopLevel);
+		}
+		CallPolicy::AliceOnly => {
+			// Exactly one CPI deep, and the top-level instruction belongs to Alice
+			require_eq!(stack_height, TRANSACTION_LEVEL_STACK_HEIGHT + 1, Errors::CallerNotAllowed);
+
+			let current_index = instructions::load_current_index_checked(instruction_sysvar)?;
+			let current_instruction = instructions::load_instruction_at_checked(
+				current_index as usize,
+				instruction_sysvar,
+			)?;
+
+			require_keys_eq!(current_instruction.program_id, alice_program::ID, Errors::CallerNotAllowed);
+		}
+	}
+
+	Ok(())
+}
+
+// The runtime already rejects indirect reentrancy (bob -> x -> bob), but not
+// a program calling itself. The lock is written back to the account right away
+// so any nested call into Bob sees it, even though Anchor would otherwise only
+// serialize the account when the instruction returns.
+fn lock(bob_data_account: &mut Account<BobData>) -> Result<()> {
+	require!(!bob_data_account.locked, Errors::Reentrancy);
+
+	bob_data_account.locked = true;
+	bob_data_account.exit(&crate::ID)
+}
+
+fn unlock(bob_data_account: &mut Account<BobData>) {
+	bob_data_account.locked = false;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CallPolicy {
+	#[default]
+	Any,          // Anyone, at any CPI depth
+	TopLevelOnly, // Only as a top-level instruction of the transaction
+	AliceOnly,    // Only through a direct CPI from Alice
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Op {
+	#[default]
+	Add,
+	Sub,
+	Mul,
+	Div,
+	Mod,
+	Pow,
+}
+
+impl Op {
+	// Every operation is checked, so a bad input fails the instruction
+	// instead of panicking (debug) or silently wrapping (release)
+	pub fn apply(self, a: u64, b: u64) -> Result<u64> {
+		let result = match self {
+			Op::Add => a.checked_add(b).ok_or(Errors::AddOverflow)?,
+			Op::Sub => a.checked_sub(b).ok_or(Errors::SubUnderflow)?,
+			Op::Mul => a.checked_mul(b).ok_or(Errors::MulOverflow)?,
+			Op::Div => a.checked_div(b).ok_or(Errors::DivisionByZero)?,
+			Op::Mod => a.checked_rem(b).ok_or(Errors::ModuloByZero)?,
+			Op::Pow => u32::try_from(b)
+				.ok()
+				.and_then(|exponent| a.checked_pow(exponent))
+				.ok_or(Errors::PowOverflow)?,
+		};
+
+		Ok(result)
+	}
+}
+
+#[error_code]
+pub enum Errors {
+	#[msg("Bob must be called as a top-level instruction")]
+	NotTopLevel,
+	#[msg("Bob can only be called through Alice")]
+	CallerNotAllowed,
+	#[msg("Bob is already executing")]
+	Reentrancy,
+	#[msg("a + b overflows u64")]
+	AddOverflow,
+	#[msg("a - b underflows u64")]
+	SubUnderflow,
+	#[msg("a * b overflows u64")]
+	MulOverflow,
+	#[msg("a / b with b = 0")]
+	DivisionByZero,
+	#[msg("a % b with b = 0")]
+	ModuloByZero,
+	#[msg("a ^ b overflows u64")]
+	PowOverflow,
+	#[msg("This instruction is paused")]
+	Paused,
+	#[msg("Signer is not the program's upgrade authority")]
+	NotUpgradeAuthority,
 }
 
+// Only the authority can change the call policy, see `SetBobDataCallPolicy`
 #[account]
+#[derive(Accessors)]
+#[accessors(authority = authority, account = bob_data_account)]
 pub struct BobData {
-	pub result: u64,
+	pub result:      u64,
+	pub op:          Op,  // operation that produced `result`
+	pub slot:        u64, // slot at which `result` was stored
+	pub authority:   Pubkey,
+	#[accessors(instruction)]
+	pub call_policy: CallPolicy,
+	pub locked:      bool,
 }
 
 #[derive(Accounts)]
 pub struct BobAddOp<'info> {
 	#[account(mut)]
 	pub bob_data_account: Account<'info, BobData>,
+
+	/// CHECK: address is checked against the instructions sysvar id
+	#[account(address = instructions::ID)]
+	pub instruction_sysvar: AccountInfo<'info>,
+
+	#[account(seeds = [PauseState::SEED], bump = pause_state.bump)]
+	pub pause_state: Account<'info, PauseState>,
 }
 
 #[derive(Accounts)]
@@ -44,4 +237,3 @@ pub struct Initialize<'info> {
 
 	pub system_program: Program<'info, System>,
 }
-
@@ -0,0 +1,12 @@
+// Scopes of the circuit breaker, see the circuit-breaker crate. A paused
+// instruction fails with `Errors::Paused`.
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Scope {
+	All,         // every scope below
+	AddAndStore, // add_and_store
+	ApplyOp,     // apply_op
+}
+
+circuit_breaker::pause_state!(program = Bob, scope = Scope { All, AddAndStore, ApplyOp });
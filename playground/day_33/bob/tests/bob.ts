@@ -14,6 +14,61 @@ describe("CPI from Alice to Bob", () => {
   const aliceProgram       = anchor.workspace.Alice as Program<Alice>;
   const dataAccountKeypair = anchor.web3.Keypair.generate();
 
+  const [pauseState] = anchor.web3.PublicKey.findProgramAddressSync(
+	[Buffer.from("pause_state")],
+	bobProgram.programId
+  );
+
+  // `anchor test` deploys with the upgradeable loader (`[test] upgradeable`
+  // in Anchor.toml), which writes this account with the wallet as upgrade
+  // authority, the only one allowed to initialize the pause state
+  const [programData] = anchor.web3.PublicKey.findProgramAddressSync(
+	[bobProgram.programId.toBuffer()],
+	new anchor.web3.PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+  );
+
+  const askBobToAdd = (a: number, b: number) =>
+	aliceProgram.methods
+	  .askBobToAdd(new anchor.BN(a), new anchor.BN(b))
+	  .accounts({
+		bobDataAccount   : dataAccountKeypair.publicKey,
+		bobProgram       : bobProgram.programId,
+		instructionSysvar: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+		pauseState,
+	  })
+	  .rpc();
+
+  const addAndStore = (a: number, b: number) =>
+	bobProgram.methods
+	  .addAndStore(new anchor.BN(a), new anchor.BN(b))
+	  .accounts({
+		bobDataAccount   : dataAccountKeypair.publicKey,
+		instructionSysvar: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+		pauseState,
+	  })
+	  .rpc();
+
+  const setCallPolicy = (callPolicy: any) =>
+	bobProgram.methods
+	  .setCallPolicy(callPolicy)
+	  .accounts({
+		bobDataAccount: dataAccountKeypair.publicKey,
+		authority     : provider.wallet.publicKey,
+	  })
+	  .rpc();
+
+  const fetchResult = async () =>
+	(await bobProgram.account.bobData.fetch(dataAccountKeypair.publicKey)).result.toNumber();
+
+  const expectError = async (promise: Promise<string>, code: string) => {
+	try {
+	  await promise;
+	  expect.fail(`should have failed with ${code}`);
+	} catch (e) {
+	  expect(e.toString()).to.contain(code);
+	}
+  };
+
   it("Is initialized!", async () => {
 	// Add your test here.
 	const tx = await bobProgram.methods
@@ -25,24 +80,194 @@ describe("CPI from Alice to Bob", () => {
 	  })
 	  .signers([dataAccountKeypair])
 	  .rpc();
+
+	await bobProgram.methods
+	  .initializePauseState()
+	  .accounts({
+		pauseState,
+		signer     : provider.wallet.publicKey,
+		program    : bobProgram.programId,
+		programData,
+	  })
+	  .rpc();
+
+	const bobData = await bobProgram.account.bobData.fetch(dataAccountKeypair.publicKey);
+	expect(bobData.callPolicy).to.deep.equal({ any: {} });
+	expect(bobData.locked).to.equal(false);
   });
 
   it("Can add numbers then double!", async () => {
 	// Add your test here.
-	const tx = await aliceProgram.methods
 //  .askBobToAddThenDouble(new anchor.BN(4), new anchor.BN(2))
-  .askBobToAdd(new anchor.BN(4), new anchor.BN(2))
-  .accounts({
-		bobDataAccount: dataAccountKeypair.publicKey,
-		bobProgram    : bobProgram.programId,
+	await askBobToAdd(4, 2);
+  });
+
+   it("Can assert value in Bob's data account equals 4 + 2", async () => {
+
+	expect(await fetchResult()).to.equal(6);
+  });
+
+  it("Can call Bob directly with the default policy", async () => {
+	await addAndStore(1, 2);
+	expect(await fetchResult()).to.equal(3);
+
+	// the reentrancy lock is released once the instruction returns
+	const bobData = await bobProgram.account.bobData.fetch(dataAccountKeypair.publicKey);
+	expect(bobData.locked).to.equal(false);
+  });
+
+  it("Bob refuses to be re-entered while locked, even through Alice", async () => {
+	// Loaded by the test validator (Anchor.toml), with `locked` set as if Bob
+	// were in the middle of another call on it
+	const lockedDataAccount = new anchor.web3.PublicKey("9P2R1kdkjPCWqDTjTgx4zU9WnAVb8okiU7p9zoMdcEkv");
+	expect((await bobProgram.account.bobData.fetch(lockedDataAccount)).locked).to.equal(true);
+
+	await expectError(
+	  aliceProgram.methods
+		.askBobToAdd(new anchor.BN(1), new anchor.BN(1))
+		.accounts({
+		  bobDataAccount   : lockedDataAccount,
+		  bobProgram       : bobProgram.programId,
+		  instructionSysvar: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+		  pauseState,
+		})
+		.rpc(),
+	  "Reentrancy"
+	);
+
+	const bobData = await bobProgram.account.bobData.fetch(lockedDataAccount);
+	expect(bobData.locked).to.equal(true);
+	expect(bobData.result.toNumber()).to.equal(6);
+  });
+
+  it("Only the authority can change the call policy", async () => {
+	const stranger = anchor.web3.Keypair.generate();
+
+	await expectError(
+	  bobProgram.methods
+		.setCallPolicy({ topLevelOnly: {} })
+		.accounts({
+		  bobDataAccount: dataAccountKeypair.publicKey,
+		  authority     : stranger.publicKey,
+		})
+		.signers([stranger])
+		.rpc(),
+	  "ConstraintHasOne"
+	);
+  });
+
+  it("Top-level only: direct call succeeds, nested call through Alice fails", async () => {
+	await setCallPolicy({ topLevelOnly: {} });
+
+	await addAndStore(5, 5);
+	expect(await fetchResult()).to.equal(10);
+
+	await expectError(askBobToAdd(7, 7), "NotTopLevel");
+	expect(await fetchResult()).to.equal(10);
+  });
+
+  it("Alice only: nested call through Alice succeeds, direct call fails", async () => {
+	await setCallPolicy({ aliceOnly: {} });
+
+	await askBobToAdd(20, 22);
+	expect(await fetchResult()).to.equal(42);
+
+	await expectError(addAndStore(1, 1), "CallerNotAllowed");
+	expect(await fetchResult()).to.equal(42);
+  });
+
+  const askBobToApply = (op: any, a: anchor.BN, b: anchor.BN) =>
+	aliceProgram.methods
+	  .askBobToApply(op, a, b)
+	  .accounts({
+		bobDataAccount   : dataAccountKeypair.publicKey,
+		bobProgram       : bobProgram.programId,
+		instructionSysvar: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+		pauseState,
+	  })
+	  .rpc();
+
+  const applyOp = (op: any, a: anchor.BN, b: anchor.BN) =>
+	bobProgram.methods
+	  .applyOp(op, a, b)
+	  .accounts({
+		bobDataAccount   : dataAccountKeypair.publicKey,
+		instructionSysvar: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+		pauseState,
 	  })
 	  .rpc();
+
+  it("Alice can ask Bob to apply every operation", async () => {
+	const cases = [
+	  { op: { add: {} }, a: 7, b: 3, result: 10 },
+	  { op: { sub: {} }, a: 7, b: 3, result: 4 },
+	  { op: { mul: {} }, a: 7, b: 3, result: 21 },
+	  { op: { div: {} }, a: 7, b: 3, result: 2 },
+	  { op: { mod: {} }, a: 7, b: 3, result: 1 },
+	  { op: { pow: {} }, a: 7, b: 3, result: 343 },
+	];
+
+	for (const { op, a, b, result } of cases) {
+	  await askBobToApply(op, new anchor.BN(a), new anchor.BN(b));
+
+	  const bobData = await bobProgram.account.bobData.fetch(dataAccountKeypair.publicKey);
+	  expect(bobData.result.toNumber()).to.equal(result);
+	  expect(bobData.op).to.deep.equal(op);
+	  expect(bobData.slot.toNumber()).to.be.greaterThan(0);
+	}
   });
 
-   it("Can assert value in Bob's data account equals 4 + 2", async () => {
+  it("Bob rejects overflows and divisions by zero", async () => {
+	await setCallPolicy({ any: {} });
+
+	const max = new anchor.BN("18446744073709551615"); // u64::MAX
+	const one = new anchor.BN(1);
+	const zero = new anchor.BN(0);
+
+	await expectError(applyOp({ add: {} }, max, one), "AddOverflow");
+	await expectError(applyOp({ sub: {} }, zero, one), "SubUnderflow");
+	await expectError(applyOp({ mul: {} }, max, new anchor.BN(2)), "MulOverflow");
+	await expectError(applyOp({ div: {} }, one, zero), "DivisionByZero");
+	await expectError(applyOp({ mod: {} }, one, zero), "ModuloByZero");
+	await expectError(applyOp({ pow: {} }, new anchor.BN(2), new anchor.BN(64)), "PowOverflow");
+
+	// add_and_store goes through the same checked path
+	await expectError(
+	  bobProgram.methods
+		.addAndStore(max, one)
+		.accounts({
+		  bobDataAccount   : dataAccountKeypair.publicKey,
+		  instructionSysvar: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+		  pauseState,
+		})
+		.rpc(),
+	  "AddOverflow"
+	);
+
+	// the last stored result is left untouched
+	expect(await fetchResult()).to.equal(343);
+  });
+
+  it("Bob refuses paused operations, even through Alice", async () => {
+	const setPause = (paused: boolean, scope: any) =>
+	  bobProgram.methods[paused ? "pause" : "unpause"](scope)
+		.accounts({ pauseState, pauser: provider.wallet.publicKey })
+		.rpc();
+
+	await setPause(true, { applyOp: {} });
+	await expectError(applyOp({ add: {} }, new anchor.BN(1), new anchor.BN(1)), "Paused");
+	await expectError(askBobToApply({ add: {} }, new anchor.BN(1), new anchor.BN(1)), "Paused");
+
+	// add_and_store has its own scope
+	await addAndStore(2, 2);
+	expect(await fetchResult()).to.equal(4);
+
+	await setPause(true, { all: {} });
+	await expectError(addAndStore(3, 3), "Paused");
 
-	const BobAccountValue = (
-	  await bobProgram.account.bobData.fetch(dataAccountKeypair.publicKey)    ).result.toNumber();
-	expect(BobAccountValue).to.equal(6);
+	await setPause(false, { all: {} });
+	await setPause(false, { applyOp: {} });
+	await applyOp({ mul: {} }, new anchor.BN(3), new anchor.BN(3));
+	expect(await fetchResult()).to.equal(9);
   });
 });
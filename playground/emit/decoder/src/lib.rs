@@ -0,0 +1,150 @@
+use anchor_lang::event::EVENT_IX_TAG_LE;
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use emit::{MyEvent, MySecondEvent, EVENT_SCHEMA_VERSION};
+use serde_json::{json, Value};
+
+// Prefix of the log lines written by `emit!`
+pub const PROGRAM_DATA: &str = "Program data: ";
+
+#[derive(Debug)]
+pub enum DecodeError {
+    Base64(base64::DecodeError),
+    TooShort(usize),
+    UnknownDiscriminator([u8; 8]),
+    UnsupportedVersion { name: &'static str, version: u8 },
+    NotCpiEvent,
+    Borsh(std::io::Error),
+}
+
+impl std::fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DecodeError::Base64(e) => write!(f, "invalid base64: {}", e),
+            DecodeError::TooShort(len) => write!(f, "{} bytes is too short for an event", len),
+            DecodeError::UnknownDiscriminator(d) => write!(f, "unknown discriminator {:?}", d),
+            DecodeError::UnsupportedVersion { name, version } => {
+                write!(f, "{} has schema version {}, only {} is supported", name, version, EVENT_SCHEMA_VERSION)
+            }
+            DecodeError::NotCpiEvent => write!(f, "instruction data does not start with the event tag"),
+            DecodeError::Borsh(e) => write!(f, "invalid event data: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+// Returns the base64 payload of a `Program data: ` line, if it is one.
+// Surrounding whitespace is ignored so indented `solana logs` output works too.
+pub fn program_data(line: &str) -> Option<&str> {
+    line.trim().strip_prefix(PROGRAM_DATA)
+}
+
+// The events of the emit program in a transaction's logs, fed line by
+// line. The invoke, success and failed lines tell which program is running,
+// so that the `Program data: ` lines of the others, whether they run in
+// another instruction or were invoked through CPI, are left alone:
+//     Program <emit> invoke [1]
+//     Program data: ...             the emit program's
+//     Program <other> invoke [2]
+//     Program data: ...             the other program's
+//     Program <other> success
+#[derive(Debug, Default)]
+pub struct LogDecoder {
+    programs: Vec<String>, // invoke stack, innermost last
+}
+
+impl LogDecoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // The program running after the lines fed so far
+    pub fn current_program(&self) -> Option<&str> {
+        self.programs.last().map(String::as_str)
+    }
+
+    // The event of `line` if it is a `Program data: ` line written by the
+    // emit program, otherwise only follows the invoke stack
+    pub fn decode_line(&mut self, line: &str) -> Option<Result<Value, DecodeError>> {
+        if let Some(payload) = program_data(line) {
+            return (self.current_program() == Some(emit::ID.to_string().as_str())).then(|| decode_event(payload));
+        }
+
+        let mut words = line.split_whitespace();
+        if words.next() != Some("Program") {
+            return None;
+        }
+        let (Some(program_id), Some(event)) = (words.next(), words.next()) else {
+            return None;
+        };
+        match event {
+            "invoke" => self.programs.push(program_id.to_string()),
+            "success" | "failed:" => {
+                self.programs.pop();
+            }
+            _ => {}
+        }
+        None
+    }
+}
+
+// Decodes one event (8 bytes discriminator + Borsh body) into
+// `{"name": ..., "data": {...}}`, with the same field names as the IDL.
+// Only events of the current `EVENT_SCHEMA_VERSION` are decoded.
+pub fn decode_event(payload: &str) -> Result<Value, DecodeError> {
+    let bytes = STANDARD.decode(payload).map_err(DecodeError::Base64)?;
+    decode_event_bytes(&bytes)
+}
+
+// Decodes the data of the self-CPI sent by `emit_cpi!`, found in the
+// transaction's inner instructions: the event tag, then the same bytes as
+// a `Program data: ` line
+pub fn decode_cpi_event(data: &[u8]) -> Result<Value, DecodeError> {
+    match data.strip_prefix(&EVENT_IX_TAG_LE[..]) {
+        Some(bytes) => decode_event_bytes(bytes),
+        None => Err(DecodeError::NotCpiEvent),
+    }
+}
+
+fn decode_event_bytes(bytes: &[u8]) -> Result<Value, DecodeError> {
+    if bytes.len() < 8 {
+        return Err(DecodeError::TooShort(bytes.len()));
+    }
+
+    let (discriminator, mut body) = bytes.split_at(8);
+
+    if discriminator == MyEvent::discriminator() {
+        check_version("MyEvent", body)?;
+        let event = MyEvent::deserialize(&mut body).map_err(DecodeError::Borsh)?;
+        Ok(json!({
+            "name": "MyEvent",
+            "data": {
+                "version": event.version,
+                "value":   event.value,
+            },
+        }))
+    } else if discriminator == MySecondEvent::discriminator() {
+        check_version("MySecondEvent", body)?;
+        let event = MySecondEvent::deserialize(&mut body).map_err(DecodeError::Borsh)?;
+        Ok(json!({
+            "name": "MySecondEvent",
+            "data": {
+                "version": event.version,
+                "value":   event.value,
+                "message": event.message,
+            },
+        }))
+    } else {
+        Err(DecodeError::UnknownDiscriminator(discriminator.try_into().unwrap()))
+    }
+}
+
+// Every event starts with its `version`: another one lays its fields out
+// differently, and would decode as garbage
+fn check_version(name: &'static str, body: &[u8]) -> Result<(), DecodeError> {
+    match body.first() {
+        Some(&version) if version != EVENT_SCHEMA_VERSION => Err(DecodeError::UnsupportedVersion { name, version }),
+        _ => Ok(()),
+    }
+}
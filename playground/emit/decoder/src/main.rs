@@ -0,0 +1,53 @@
+// Usage:
+//   emit-decoder [LOG_FILE]
+//
+// Reads transaction log lines from LOG_FILE (or stdin when omitted or `-`),
+// decodes every `Program data: ` entry emitted by the emit program, as told
+// by the `Program <id> invoke` lines before it, and prints one JSON object
+// per line:
+//   {"line":3,"name":"MyEvent","data":{"value":42,"version":1}}
+use emit_decoder::LogDecoder;
+use serde_json::json;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Write};
+
+fn main() {
+    let path = std::env::args().nth(1);
+    let reader: Box<dyn BufRead> = match path.as_deref() {
+        None | Some("-") => Box::new(BufReader::new(io::stdin())),
+        Some(path) => match File::open(path) {
+            Ok(file) => Box::new(BufReader::new(file)),
+            Err(e) => {
+                eprintln!("cannot open {}: {}", path, e);
+                std::process::exit(1);
+            }
+        },
+    };
+
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+    let mut decoder = LogDecoder::new();
+
+    for (index, line) in reader.lines().enumerate() {
+        let line_number = index + 1;
+        let line = match line {
+            Ok(line) => line,
+            Err(e) => {
+                eprintln!("line {}: {}", line_number, e);
+                std::process::exit(1);
+            }
+        };
+
+        match decoder.decode_line(&line) {
+            Some(Ok(mut event)) => {
+                event["line"] = json!(line_number);
+                if writeln!(out, "{}", event).is_err() {
+                    // stdout closed (e.g. piped into `head`)
+                    return;
+                }
+            }
+            Some(Err(e)) => eprintln!("line {}: {}", line_number, e),
+            None => {}
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use anchor_lang::event::EVENT_IX_TAG_LE;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use emit::EVENT_SCHEMA_VERSION;
+use emit_decoder::{decode_cpi_event, decode_event, program_data, DecodeError, LogDecoder};
+use serde_json::{json, Value};
+
+// Logs of `initialize`, as printed by `solana logs`
+const SAMPLE: &str = include_str!("../sample.log");
+
+fn events(logs: &str) -> Vec<Value> {
+    let mut decoder = LogDecoder::new();
+    logs.lines().filter_map(|line| decoder.decode_line(line)).map(Result::unwrap).collect()
+}
+
+fn sample_events() -> Vec<Value> {
+    events(SAMPLE)
+}
+
+#[test]
+fn decodes_the_sample_log() {
+    assert_eq!(
+        sample_events(),
+        vec![
+            json!({ "name": "MyEvent", "data": { "version": 1, "value": 42 } }),
+            json!({ "name": "MySecondEvent", "data": { "version": 1, "value": 3, "message": "hello world" } }),
+        ]
+    );
+}
+
+#[test]
+fn sample_events_carry_the_schema_version() {
+    for event in sample_events() {
+        assert_eq!(event["data"]["version"], json!(EVENT_SCHEMA_VERSION));
+    }
+}
+
+#[test]
+fn only_program_data_lines_are_payloads() {
+    assert_eq!(SAMPLE.lines().filter_map(program_data).count(), 2);
+    assert_eq!(program_data("    Program data: YLjF84sCWpQBKgAAAAAAAAA="), Some("YLjF84sCWpQBKgAAAAAAAAA="));
+    assert_eq!(program_data("Program log: Instruction: Initialize"), None);
+}
+
+// `initialize_cpi` emits the same events as the data of its self-CPI
+#[test]
+fn decodes_the_cpi_form() {
+    for (payload, event) in SAMPLE.lines().filter_map(program_data).zip(sample_events()) {
+        let mut data = EVENT_IX_TAG_LE.to_vec();
+        data.extend(STANDARD.decode(payload).unwrap());
+
+        assert_eq!(decode_cpi_event(&data).unwrap(), event);
+    }
+}
+
+#[test]
+fn cpi_data_needs_the_event_tag() {
+    let data = STANDARD.decode("YLjF84sCWpQBKgAAAAAAAAA=").unwrap();
+
+    assert!(matches!(decode_cpi_event(&data), Err(DecodeError::NotCpiEvent)));
+}
+
+#[test]
+fn rejects_malformed_payloads() {
+    assert!(matches!(decode_event("not base64!"), Err(DecodeError::Base64(_))));
+    assert!(matches!(decode_event("AQID"), Err(DecodeError::TooShort(3))));
+    // Another program's event
+    assert!(matches!(decode_event("AAAAAAAAAAABAgM="), Err(DecodeError::UnknownDiscriminator(d)) if d == [0; 8]));
+    // `MyEvent` cut short
+    assert!(matches!(decode_event("YLjF84sCWpQBKgA="), Err(DecodeError::Borsh(_))));
+}
+
+#[test]
+fn rejects_unknown_schema_versions() {
+    // `MyEvent` of the sample, at version 2
+    let mut bytes = STANDARD.decode("YLjF84sCWpQBKgAAAAAAAAA=").unwrap();
+    bytes[8] = 2;
+
+    assert!(matches!(
+        decode_event(&STANDARD.encode(bytes)),
+        Err(DecodeError::UnsupportedVersion { name: "MyEvent", version: 2 })
+    ));
+}
+
+// The same payloads, written by programs other than the emit program
+#[test]
+fn leaves_other_programs_events_alone() {
+    let emit = emit::ID.to_string();
+    let other = "Other11111111111111111111111111111111111111";
+    let logs = [
+        format!("Program {} invoke [1]", other),
+        "Program data: YLjF84sCWpQBKgAAAAAAAAA=".to_string(),
+        format!("Program {} success", other),
+        format!("Program {} invoke [1]", emit),
+        format!("Program {} invoke [2]", other),
+        "Program data: YLjF84sCWpQBKgAAAAAAAAA=".to_string(),
+        format!("Program {} success", other),
+        "Program data: MNelqUKcCaQBAwAAAAAAAAALAAAAaGVsbG8gd29ybGQ=".to_string(),
+        format!("Program {} success", emit),
+        // outside any instruction
+        "Program data: YLjF84sCWpQBKgAAAAAAAAA=".to_string(),
+    ];
+
+    assert_eq!(
+        events(&logs.join("\n")),
+        vec![json!({ "name": "MySecondEvent", "data": { "version": 1, "value": 3, "message": "hello world" } })]
+    );
+}
+
+#[test]
+fn follows_the_invoke_stack() {
+    let mut decoder = LogDecoder::new();
+    let lines: Vec<&str> = SAMPLE.lines().collect();
+
+    decoder.decode_line(lines[0]);
+    assert_eq!(decoder.current_program(), Some(emit::ID.to_string().as_str()));
+
+    for line in &lines[1..] {
+        decoder.decode_line(line);
+    }
+    assert_eq!(decoder.current_program(), None);
+}
@@ -2,13 +2,26 @@ use anchor_lang::prelude::*;
 
 declare_id!("G1WytS2jEeTFFWydfoifiRKbLVL8DPXJChBQvzv3ivqJ");
 
+// Bump this whenever a field is added, removed or reordered in one of the
+// events below, so indexers know which layout they are decoding
+pub const EVENT_SCHEMA_VERSION: u8 = 1;
+
 #[program]
 pub mod emit {
     use super::*;
 
+    // Events are written to the program logs, which validators can truncate
     pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
-        emit!(MyEvent { value: 42 });
-        emit!(MySecondEvent { value: 3, message: "hello world".to_string() });
+        emit!(MyEvent { version: EVENT_SCHEMA_VERSION, value: 42 });
+        emit!(MySecondEvent { version: EVENT_SCHEMA_VERSION, value: 3, message: "hello world".to_string() });
+        Ok(())
+    }
+
+    // Events are sent as the data of a self-CPI signed by the event authority
+    // PDA, so they are kept in the transaction's inner instructions instead
+    pub fn initialize_cpi(ctx: Context<InitializeCpi>) -> Result<()> {
+        emit_cpi!(MyEvent { version: EVENT_SCHEMA_VERSION, value: 42 });
+        emit_cpi!(MySecondEvent { version: EVENT_SCHEMA_VERSION, value: 3, message: "hello world".to_string() });
         Ok(())
     }
 }
@@ -16,13 +29,21 @@ pub mod emit {
 #[derive(Accounts)]
 pub struct Initialize {}
 
+// `#[event_cpi]` adds the `event_authority` and `program` accounts
+// needed by `emit_cpi!`
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitializeCpi {}
+
 #[event]
 pub struct MyEvent {
-    pub value: u64,
+    pub version: u8,
+    pub value:   u64,
 }
 
 #[event]
 pub struct MySecondEvent {
+    pub version: u8,
     pub value:   u64,
     pub message: String,
 }
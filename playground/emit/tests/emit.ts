@@ -1,12 +1,87 @@
 import * as anchor from "@coral-xyz/anchor";
 import { BorshCoder, EventParser, Program } from "@coral-xyz/anchor";
 import { Emit } from "../target/types/emit";
+import { expect } from "chai";
+
+// Same value as `EVENT_SCHEMA_VERSION` in the program
+const EVENT_SCHEMA_VERSION = 1;
+
+// Same value as `anchor_lang::event::EVENT_IX_TAG_LE`, the 8 bytes prefixing
+// the data of every self-CPI made by `emit_cpi!`
+const EVENT_IX_TAG_LE = Buffer.from("e445a52e51cb9a1d", "hex");
+
+type DecodedEvent = { name: string; data: any };
 
 describe("emit", () => {
+  const provider = anchor.AnchorProvider.env();
+
   // Configure the client to use the local cluster.
-  anchor.setProvider(anchor.AnchorProvider.env());
+  anchor.setProvider(provider);
 
   const program = anchor.workspace.Emit as Program<Emit>;
+  const coder   = new BorshCoder(program.idl);
+
+  const [eventAuthority] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("__event_authority")],
+    program.programId
+  );
+
+  const fetchTransaction = async (signature: string) => {
+    const tx = await provider.connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    expect(tx, `transaction ${signature} not found`).to.not.be.null;
+    return tx;
+  };
+
+  // Events emitted with `emit!`: "Program data: <base64>" log lines
+  const collectLogEvents = async (signature: string): Promise<DecodedEvent[]> => {
+    const tx = await fetchTransaction(signature);
+    const parser = new EventParser(program.programId, coder);
+
+    return Array.from(parser.parseLogs(tx.meta.logMessages));
+  };
+
+  // Events emitted with `emit_cpi!`: inner instructions from the program to
+  // itself whose data is EVENT_IX_TAG_LE followed by the event
+  const collectCpiEvents = async (signature: string): Promise<DecodedEvent[]> => {
+    const tx = await fetchTransaction(signature);
+    const accountKeys = tx.transaction.message.getAccountKeys().staticAccountKeys;
+    const events: DecodedEvent[] = [];
+
+    for (const inner of tx.meta.innerInstructions) {
+      for (const ix of inner.instructions) {
+        if (!accountKeys[ix.programIdIndex].equals(program.programId)) {
+          continue;
+        }
+
+        const data = Buffer.from(anchor.utils.bytes.bs58.decode(ix.data));
+        if (!data.subarray(0, 8).equals(EVENT_IX_TAG_LE)) {
+          continue;
+        }
+
+        events.push(coder.events.decode(data.subarray(8).toString("base64")));
+      }
+    }
+
+    return events;
+  };
+
+  // BN fields are compared by value
+  const normalize = (events: DecodedEvent[]) =>
+    events.map(({ name, data }) => ({
+      name,
+      data: Object.keys(data).reduce((fields, k) => {
+        fields[k] = anchor.BN.isBN(data[k]) ? data[k].toString() : data[k];
+        return fields;
+      }, {}),
+    }));
+
+  const expectedEvents = [
+    { name: "MyEvent", data: { version: EVENT_SCHEMA_VERSION, value: "42" } },
+    { name: "MySecondEvent", data: { version: EVENT_SCHEMA_VERSION, value: "3", message: "hello world" } },
+  ];
 
   it("Is initialized!", async () => {
     const listenerMyEvent = program.addEventListener('MyEvent', (event, slot) => {
@@ -26,4 +101,25 @@ describe("emit", () => {
     program.removeEventListener(listenerMyEvent);
     program.removeEventListener(listenerMySecondEvent);
   });
+
+  it("Decodes log events", async () => {
+    const signature = await program.methods.initialize().rpc({ commitment: "confirmed" });
+
+    expect(normalize(await collectLogEvents(signature))).to.deep.equal(expectedEvents);
+  });
+
+  it("Decodes CPI events", async () => {
+    const signature = await program.methods
+      .initializeCpi()
+      .accounts({
+        eventAuthority: eventAuthority,
+        program       : program.programId,
+      })
+      .rpc({ commitment: "confirmed" });
+
+    expect(normalize(await collectCpiEvents(signature))).to.deep.equal(expectedEvents);
+
+    // nothing goes through the logs in CPI mode
+    expect(await collectLogEvents(signature)).to.be.empty;
+  });
 });
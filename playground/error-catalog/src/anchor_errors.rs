@@ -0,0 +1,96 @@
+// Errors raised by Anchor itself, shared by every Anchor program: codes below
+// `ERROR_CODE_OFFSET`. Codes, names and messages come from
+// `anchor_lang::error::ErrorCode`; only its variants are listed, and the
+// match below stops compiling when anchor-lang adds one.
+use anchor_lang::error::ErrorCode;
+use std::sync::OnceLock;
+
+use crate::ErrorEntry;
+
+macro_rules! variants {
+    ($($variant:ident),+ $(,)?) => {
+        const VARIANTS: &[ErrorCode] = &[$(ErrorCode::$variant),+];
+
+        #[allow(dead_code)]
+        fn listed(error: ErrorCode) {
+            match error {
+                $(ErrorCode::$variant)|+ => {}
+            }
+        }
+    };
+}
+
+variants! {
+    InstructionMissing,
+    InstructionFallbackNotFound,
+    InstructionDidNotDeserialize,
+    InstructionDidNotSerialize,
+    IdlInstructionStub,
+    IdlInstructionInvalidProgram,
+    IdlAccountNotEmpty,
+    EventInstructionStub,
+    ConstraintMut,
+    ConstraintHasOne,
+    ConstraintSigner,
+    ConstraintRaw,
+    ConstraintOwner,
+    ConstraintRentExempt,
+    ConstraintSeeds,
+    ConstraintExecutable,
+    ConstraintState,
+    ConstraintAssociated,
+    ConstraintAssociatedInit,
+    ConstraintClose,
+    ConstraintAddress,
+    ConstraintZero,
+    ConstraintTokenMint,
+    ConstraintTokenOwner,
+    ConstraintMintMintAuthority,
+    ConstraintMintFreezeAuthority,
+    ConstraintMintDecimals,
+    ConstraintSpace,
+    ConstraintAccountIsNone,
+    ConstraintTokenTokenProgram,
+    ConstraintMintTokenProgram,
+    ConstraintAssociatedTokenTokenProgram,
+    RequireViolated,
+    RequireEqViolated,
+    RequireKeysEqViolated,
+    RequireNeqViolated,
+    RequireKeysNeqViolated,
+    RequireGtViolated,
+    RequireGteViolated,
+    AccountDiscriminatorAlreadySet,
+    AccountDiscriminatorNotFound,
+    AccountDiscriminatorMismatch,
+    AccountDidNotDeserialize,
+    AccountDidNotSerialize,
+    AccountNotEnoughKeys,
+    AccountNotMutable,
+    AccountOwnedByWrongProgram,
+    InvalidProgramId,
+    InvalidProgramExecutable,
+    AccountNotSigner,
+    AccountNotSystemOwned,
+    AccountNotInitialized,
+    AccountNotProgramData,
+    AccountNotAssociatedTokenAccount,
+    AccountSysvarMismatch,
+    AccountReallocExceedsLimit,
+    AccountDuplicateReallocs,
+    DeclaredProgramIdMismatch,
+    TryingToInitPayerAsProgramAccount,
+    Deprecated,
+}
+
+// Anchor's errors, by code
+pub fn anchor_errors() -> &'static [ErrorEntry] {
+    static ERRORS: OnceLock<Vec<ErrorEntry>> = OnceLock::new();
+
+    ERRORS.get_or_init(|| {
+        VARIANTS
+            .iter()
+            .map(|&error| ErrorEntry { code: error.into(), name: error.name(), msg: Some(error.to_string()) })
+            .collect()
+    })
+}
@@ -0,0 +1,312 @@
+// Error catalog of the playground programs.
+//
+// Every program numbers its `#[error_code]` variants from 6000, whatever the
+// enum is called (`Errors`, `Day04Error`, `MyError`, ...), so the same code
+// means something different in each program. The catalog keeps one error
+// table per program id, read from the IDLs written by `anchor build`, plus
+// Anchor's own errors (below 6000), which only these Anchor programs raise,
+// and turns the errors returned by a transaction back into names and
+// messages.
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+mod anchor_errors;
+
+pub use anchor_errors::anchor_errors;
+
+// First code of the errors declared by a program
+pub const ERROR_CODE_OFFSET: u32 = anchor_lang::error::ERROR_CODE_OFFSET;
+
+#[derive(Debug)]
+pub enum CatalogError {
+    Io(PathBuf, std::io::Error),
+    Json(PathBuf, serde_json::Error),
+    Toml(PathBuf, toml::de::Error),
+    InvalidIdl(String), // program name or path
+}
+
+impl fmt::Display for CatalogError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CatalogError::Io(path, e) => write!(f, "{}: {}", path.display(), e),
+            CatalogError::Json(path, e) => write!(f, "{}: invalid JSON: {}", path.display(), e),
+            CatalogError::Toml(path, e) => write!(f, "{}: invalid TOML: {}", path.display(), e),
+            CatalogError::InvalidIdl(name) => write!(f, "{}: malformed `errors` in IDL", name),
+        }
+    }
+}
+
+impl std::error::Error for CatalogError {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ErrorEntry {
+    pub code: u32,
+    pub name: String,
+    pub msg:  Option<String>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct ProgramErrors {
+    pub name:   String,
+    pub errors: BTreeMap<u32, ErrorEntry>,
+}
+
+// Where a code was found
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Source<'a> {
+    Anchor,
+    Program(&'a str), // program name
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Lookup<'a> {
+    pub source: Source<'a>,
+    pub code:   u32,
+    pub name:   &'a str,
+    pub msg:    Option<&'a str>,
+}
+
+impl fmt::Display for Lookup<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.source {
+            Source::Anchor => write!(f, "anchor::{} ({})", self.name, self.code)?,
+            Source::Program(program) => write!(f, "{}::{} ({})", program, self.name, self.code)?,
+        }
+
+        match self.msg {
+            Some(msg) => write!(f, ": {}", msg),
+            None => Ok(()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Catalog {
+    programs: BTreeMap<String, ProgramErrors>, // by program id (base58)
+}
+
+impl Catalog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn programs(&self) -> impl Iterator<Item = (&str, &ProgramErrors)> {
+        self.programs.iter().map(|(id, program)| (id.as_str(), program))
+    }
+
+    // Adds the error table of a (legacy, anchor 0.29) IDL. An IDL without
+    // `errors` still registers the program, with an empty table.
+    pub fn add_idl(&mut self, program_id: &str, idl: &Value) -> Result<(), CatalogError> {
+        let name = idl["name"].as_str().unwrap_or(program_id).to_string();
+        let mut errors = BTreeMap::new();
+
+        for error in idl["errors"].as_array().map(Vec::as_slice).unwrap_or_default() {
+            let entry = ErrorEntry {
+                code: error["code"]
+                    .as_u64()
+                    .and_then(|code| u32::try_from(code).ok())
+                    .ok_or_else(|| CatalogError::InvalidIdl(name.clone()))?,
+                name: error["name"]
+                    .as_str()
+                    .ok_or_else(|| CatalogError::InvalidIdl(name.clone()))?
+                    .to_string(),
+                msg:  error["msg"].as_str().map(str::to_string),
+            };
+            errors.insert(entry.code, entry);
+        }
+
+        self.programs.insert(program_id.to_string(), ProgramErrors { name, errors });
+        Ok(())
+    }
+
+    // Adds the programs of an Anchor workspace: ids from the
+    // `[programs.localnet]` section of `Anchor.toml`, error tables from
+    // `target/idl/<program>.json`. Programs that were not built yet are
+    // skipped. Returns the number of programs added.
+    pub fn add_workspace(&mut self, workspace: &Path) -> Result<usize, CatalogError> {
+        let anchor_toml = workspace.join("Anchor.toml");
+        let content = std::fs::read_to_string(&anchor_toml).map_err(|e| CatalogError::Io(anchor_toml.clone(), e))?;
+        let config: toml::Table = content.parse().map_err(|e| CatalogError::Toml(anchor_toml.clone(), e))?;
+
+        let mut added = 0;
+        let programs = config
+            .get("programs")
+            .and_then(|programs| programs.get("localnet"))
+            .and_then(toml::Value::as_table);
+
+        for (name, program_id) in programs.into_iter().flatten() {
+            let Some(program_id) = program_id.as_str() else {
+                continue;
+            };
+
+            let idl_path = workspace.join("target").join("idl").join(format!("{}.json", name));
+            let idl = match std::fs::read_to_string(&idl_path) {
+                Ok(idl) => idl,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(CatalogError::Io(idl_path, e)),
+            };
+            let idl: Value = serde_json::from_str(&idl).map_err(|e| CatalogError::Json(idl_path, e))?;
+
+            self.add_idl(program_id, &idl)?;
+            added += 1;
+        }
+
+        Ok(added)
+    }
+
+    // Codes below ERROR_CODE_OFFSET are Anchor's, but only for the programs
+    // of the catalog, which are known to be Anchor programs: any other
+    // program may use the same codes for its own errors
+    pub fn lookup(&self, program_id: &str, code: u32) -> Option<Lookup<'_>> {
+        let program = self.programs.get(program_id)?;
+
+        let (source, entry) = if code < ERROR_CODE_OFFSET {
+            (Source::Anchor, anchor_errors().iter().find(|entry| entry.code == code)?)
+        } else {
+            (Source::Program(&program.name), program.errors.get(&code)?)
+        };
+
+        Some(Lookup { source, code, name: &entry.name, msg: entry.msg.as_deref() })
+    }
+
+    // Decodes a `TransactionError` in its JSON form, as found in
+    // `meta.err` of `getTransaction` or the `err` of `simulateTransaction`:
+    //     {"InstructionError": [1, {"Custom": 6000}]}
+    // `program_ids` are the programs of the transaction's top-level
+    // instructions, in order, and `logs` its log messages (`meta.logMessages`),
+    // if any. The error is reported against the program the logs show
+    // failing first, which may be one invoked through CPI; without logs it
+    // can only be attributed to the top-level instruction's program.
+    pub fn decode_transaction_error(&self, error: &Value, program_ids: &[&str], logs: &[&str]) -> Option<DecodedError> {
+        let [index, inner] = error.get("InstructionError")?.as_array()?.as_slice() else {
+            return None;
+        };
+        let index = u8::try_from(index.as_u64()?).ok()?;
+
+        let kind = match inner {
+            Value::String(name) => ErrorKind::Builtin(name.clone()),
+            Value::Object(object) => match object.get("Custom").and_then(Value::as_u64) {
+                Some(code) => ErrorKind::Custom(u32::try_from(code).ok()?),
+                None => ErrorKind::Builtin(inner.to_string()),
+            },
+            _ => return None,
+        };
+
+        Some(self.decoded(index, kind, program_ids, logs))
+    }
+
+    // Same for the message of a failed `sendTransaction`, as printed by the
+    // TypeScript tests:
+    //     Error processing Instruction 0: custom program error: 0x1770
+    pub fn decode_error_message(&self, message: &str, program_ids: &[&str], logs: &[&str]) -> Option<DecodedError> {
+        let rest = &message[message.find("Error processing Instruction ")? + "Error processing Instruction ".len()..];
+        let (index, rest) = rest.split_once(": ")?;
+        let index = index.trim().parse().ok()?;
+
+        let kind = match rest.trim().strip_prefix("custom program error: 0x") {
+            Some(hex) => {
+                let hex = hex.split(|c: char| !c.is_ascii_hexdigit()).next()?;
+                ErrorKind::Custom(u32::from_str_radix(hex, 16).ok()?)
+            }
+            None => ErrorKind::Builtin(rest.lines().next()?.trim().to_string()),
+        };
+
+        Some(self.decoded(index, kind, program_ids, logs))
+    }
+
+    fn decoded(&self, instruction: u8, kind: ErrorKind, program_ids: &[&str], logs: &[&str]) -> DecodedError {
+        let (program_id, origin) = match failing_program(logs) {
+            Some((program_id, depth)) => (Some(program_id.to_string()), Origin::Logs { depth }),
+            None => (program_ids.get(instruction as usize).map(|id| id.to_string()), Origin::TopLevel),
+        };
+        let description = match kind {
+            // Without a program id, nothing can be found
+            ErrorKind::Custom(code) => self
+                .lookup(program_id.as_deref().unwrap_or_default(), code)
+                .map(|lookup| lookup.to_string()),
+            ErrorKind::Builtin(_) => None,
+        };
+
+        DecodedError { instruction, program_id, origin, kind, description }
+    }
+}
+
+// The program that failed first in a transaction's logs, and its invoke
+// depth (1 for a top-level instruction). A failing CPI fails its callers
+// too, so every program of the stack logs `failed`, innermost first:
+//     Program Alice invoke [1]
+//     Program Bob invoke [2]
+//     Program Bob failed: custom program error: 0x1770
+//     Program Alice failed: custom program error: 0x1770
+pub fn failing_program<'a>(logs: &[&'a str]) -> Option<(&'a str, usize)> {
+    let mut depth: usize = 0;
+
+    for line in logs {
+        let mut words = line.split_whitespace();
+        if words.next() != Some("Program") {
+            continue;
+        }
+        let (Some(program_id), Some(event)) = (words.next(), words.next()) else {
+            continue;
+        };
+        // `Program log: ...`, `Program data: ...`
+        if program_id.ends_with(':') {
+            continue;
+        }
+
+        match event {
+            "invoke" => {
+                depth = words
+                    .next()
+                    .and_then(|depth| depth.strip_prefix('[')?.strip_suffix(']')?.parse().ok())
+                    .unwrap_or(depth + 1);
+            }
+            "success" => depth = depth.saturating_sub(1),
+            "failed:" => return Some((program_id, depth.max(1))),
+            _ => {}
+        }
+    }
+
+    None
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ErrorKind {
+    Custom(u32),     // ProgramError::Custom, what `#[error_code]` errors become
+    Builtin(String), // any other InstructionError, e.g. "InvalidAccountData"
+}
+
+// How the program of a decoded error was found
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Origin {
+    TopLevel,              // no logs: the instruction's program, or one it invoked
+    Logs { depth: usize }, // the program failing first in the logs, at this invoke depth
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DecodedError {
+    pub instruction: u8,
+    pub program_id:  Option<String>,
+    pub origin:      Origin,
+    pub kind:        ErrorKind,
+    pub description: Option<String>, // None when the code is not in the catalog
+}
+
+impl fmt::Display for DecodedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "instruction {}", self.instruction)?;
+        match (&self.program_id, self.origin) {
+            (Some(program_id), Origin::TopLevel) => write!(f, " ({}, or a program it invoked)", program_id)?,
+            (Some(program_id), Origin::Logs { depth }) => write!(f, " ({} at invoke depth {})", program_id, depth)?,
+            (None, _) => {}
+        }
+
+        match (&self.kind, &self.description) {
+            (_, Some(description)) => write!(f, ": {}", description),
+            (ErrorKind::Custom(code), None) => write!(f, ": unknown custom error {} (0x{:x})", code, code),
+            (ErrorKind::Builtin(name), None) => write!(f, ": {}", name),
+        }
+    }
+}
@@ -0,0 +1,116 @@
+// Usage:
+//   error-catalog [-w WORKSPACE]... list
+//   error-catalog [-w WORKSPACE]... code PROGRAM_ID CODE
+//   error-catalog [-w WORKSPACE]... tx ERROR [PROGRAM_ID]... [--logs LOG_FILE]
+//
+// WORKSPACE is an Anchor workspace (a directory with an Anchor.toml) whose
+// programs were built, so that target/idl holds their IDLs. Without `-w`,
+// every workspace directly under the current directory is loaded, e.g. all
+// of them when run from `playground/`.
+//
+// `list` prints every known error, one per line.
+// `code` looks a single code up; CODE is decimal or 0x-prefixed hex.
+// `tx` decodes a transaction error, either its JSON form
+//   '{"InstructionError":[0,{"Custom":6000}]}'
+// or the message of a failed transaction
+//   'Error processing Instruction 0: custom program error: 0x1770'
+// given the program ids of the transaction's instructions, in order. With
+// the transaction's logs, one message per line, the error is attributed to
+// the program that raised it even through CPI.
+use error_catalog::{anchor_errors, Catalog};
+use std::path::PathBuf;
+
+fn usage() -> ! {
+    eprintln!("usage: error-catalog [-w WORKSPACE]... (list | code PROGRAM_ID CODE | tx ERROR [PROGRAM_ID]... [--logs LOG_FILE])");
+    std::process::exit(2);
+}
+
+fn parse_code(code: &str) -> Option<u32> {
+    match code.strip_prefix("0x") {
+        Some(hex) => u32::from_str_radix(hex, 16).ok(),
+        None => code.parse().ok(),
+    }
+}
+
+fn main() {
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+
+    let mut workspaces = Vec::new();
+    while args.first().map(String::as_str) == Some("-w") {
+        if args.len() < 2 {
+            usage();
+        }
+        workspaces.push(PathBuf::from(args.remove(1)));
+        args.remove(0);
+    }
+
+    if workspaces.is_empty() {
+        let entries = std::fs::read_dir(".").unwrap_or_else(|e| {
+            eprintln!("cannot read the current directory: {}", e);
+            std::process::exit(1);
+        });
+        workspaces = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.join("Anchor.toml").is_file())
+            .collect();
+        workspaces.sort();
+    }
+
+    let mut catalog = Catalog::new();
+    for workspace in &workspaces {
+        if let Err(e) = catalog.add_workspace(workspace) {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    }
+
+    match args.iter().map(String::as_str).collect::<Vec<_>>().as_slice() {
+        ["list"] => {
+            for error in anchor_errors() {
+                println!("anchor\t{}\t{}\t{}", error.code, error.name, error.msg.as_deref().unwrap_or_default());
+            }
+            for (program_id, program) in catalog.programs() {
+                for error in program.errors.values() {
+                    let msg = error.msg.as_deref().unwrap_or_default();
+                    println!("{} ({})\t{}\t{}\t{}", program.name, program_id, error.code, error.name, msg);
+                }
+            }
+        }
+        ["code", program_id, code] => {
+            let code = parse_code(code).unwrap_or_else(|| usage());
+            match catalog.lookup(program_id, code) {
+                Some(lookup) => println!("{}", lookup),
+                None => {
+                    eprintln!("unknown error {} for {}", code, program_id);
+                    std::process::exit(1);
+                }
+            }
+        }
+        ["tx", error, rest @ ..] => {
+            let (program_ids, logs) = match rest {
+                [program_ids @ .., "--logs", path] => {
+                    let logs = std::fs::read_to_string(path).unwrap_or_else(|e| {
+                        eprintln!("cannot read {}: {}", path, e);
+                        std::process::exit(1);
+                    });
+                    (program_ids, logs)
+                }
+                program_ids => (program_ids, String::new()),
+            };
+            let logs: Vec<&str> = logs.lines().collect();
+
+            let decoded = match serde_json::from_str(error) {
+                Ok(json) => catalog.decode_transaction_error(&json, program_ids, &logs),
+                Err(_) => catalog.decode_error_message(error, program_ids, &logs),
+            };
+            match decoded {
+                Some(decoded) => println!("{}", decoded),
+                None => {
+                    eprintln!("not an instruction error: {}", error);
+                    std::process::exit(1);
+                }
+            }
+        }
+        _ => usage(),
+    }
+}
@@ -0,0 +1,151 @@
+// Decoding of transaction errors against a hand-written IDL
+use error_catalog::{failing_program, Catalog, ErrorKind, Origin};
+use serde_json::json;
+
+const ALICE: &str = "AL1CE11111111111111111111111111111111111111";
+const BOB: &str = "B0B1111111111111111111111111111111111111111";
+
+fn catalog() -> Catalog {
+    let mut catalog = Catalog::new();
+    catalog
+        .add_idl(ALICE, &json!({ "name": "alice", "errors": [
+            { "code": 6000, "name": "NotEnoughFunds", "msg": "Not enough funds" },
+        ]}))
+        .unwrap();
+    catalog
+        .add_idl(BOB, &json!({ "name": "bob", "errors": [
+            { "code": 6000, "name": "Paused", "msg": "This instruction is paused" },
+        ]}))
+        .unwrap();
+    catalog
+}
+
+// Alice's instruction 1 invoking Bob, which fails with its code 6000
+const CPI_LOGS: &[&str] = &[
+    "Program ComputeBudget111111111111111111111111111111 invoke [1]",
+    "Program ComputeBudget111111111111111111111111111111 success",
+    "Program AL1CE11111111111111111111111111111111111111 invoke [1]",
+    "Program log: Instruction: Withdraw",
+    "Program B0B1111111111111111111111111111111111111111 invoke [2]",
+    "Program log: AnchorError occurred. Error Code: Paused. Error Number: 6000. Error Message: This instruction is paused.",
+    "Program B0B1111111111111111111111111111111111111111 consumed 4000 of 190000 compute units",
+    "Program B0B1111111111111111111111111111111111111111 failed: custom program error: 0x1770",
+    "Program AL1CE11111111111111111111111111111111111111 consumed 10000 of 200000 compute units",
+    "Program AL1CE11111111111111111111111111111111111111 failed: custom program error: 0x1770",
+];
+
+#[test]
+fn decodes_a_custom_error() {
+    let error = json!({ "InstructionError": [0, { "Custom": 6000 }] });
+    let decoded = catalog().decode_transaction_error(&error, &[ALICE], &[]).unwrap();
+
+    assert_eq!(decoded.instruction, 0);
+    assert_eq!(decoded.program_id.as_deref(), Some(ALICE));
+    assert_eq!(decoded.kind, ErrorKind::Custom(6000));
+    assert_eq!(decoded.description.as_deref(), Some("alice::NotEnoughFunds (6000): Not enough funds"));
+}
+
+#[test]
+fn decodes_an_anchor_error() {
+    let error = json!({ "InstructionError": [0, { "Custom": 2006 }] });
+    let decoded = catalog().decode_transaction_error(&error, &[ALICE], &[]).unwrap();
+
+    assert_eq!(decoded.description.as_deref(), Some("anchor::ConstraintSeeds (2006): A seeds constraint was violated"));
+
+    // only for the programs of the catalog: another one may not be an Anchor
+    // program, and use the code for its own error
+    const TOKEN: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+    let decoded = catalog().decode_transaction_error(&error, &[TOKEN], &[]).unwrap();
+    assert_eq!(decoded.description, None);
+    assert!(decoded.to_string().ends_with(": unknown custom error 2006 (0x7d6)"));
+
+    let decoded = catalog().decode_transaction_error(&error, &[], &[]).unwrap();
+    assert_eq!(decoded.program_id, None);
+    assert_eq!(decoded.description, None);
+}
+
+#[test]
+fn decodes_a_builtin_error() {
+    let error = json!({ "InstructionError": [1, "InvalidAccountData"] });
+    let decoded = catalog().decode_transaction_error(&error, &[BOB, ALICE], &[]).unwrap();
+
+    assert_eq!(decoded.instruction, 1);
+    assert_eq!(decoded.program_id.as_deref(), Some(ALICE));
+    assert_eq!(decoded.kind, ErrorKind::Builtin("InvalidAccountData".to_string()));
+    assert_eq!(decoded.description, None);
+    assert_eq!(decoded.to_string(), format!("instruction 1 ({}, or a program it invoked): InvalidAccountData", ALICE));
+}
+
+#[test]
+fn decodes_error_messages() {
+    let catalog = catalog();
+
+    let decoded = catalog
+        .decode_error_message("failed to send transaction: Error processing Instruction 0: custom program error: 0x1770", &[BOB], &[])
+        .unwrap();
+    assert_eq!(decoded.kind, ErrorKind::Custom(6000));
+    assert_eq!(decoded.description.as_deref(), Some("bob::Paused (6000): This instruction is paused"));
+
+    let decoded = catalog
+        .decode_error_message("Error processing Instruction 2: invalid account data for instruction", &[], &[])
+        .unwrap();
+    assert_eq!(decoded.instruction, 2);
+    assert_eq!(decoded.kind, ErrorKind::Builtin("invalid account data for instruction".to_string()));
+
+    assert!(catalog.decode_error_message("Blockhash not found", &[ALICE], &[]).is_none());
+}
+
+#[test]
+fn unknown_custom_errors_keep_their_code() {
+    let error = json!({ "InstructionError": [0, { "Custom": 6001 }] });
+    let decoded = catalog().decode_transaction_error(&error, &[ALICE], &[]).unwrap();
+
+    assert_eq!(decoded.description, None);
+    assert!(decoded.to_string().ends_with(": unknown custom error 6001 (0x1771)"));
+}
+
+#[test]
+fn without_logs_the_error_is_ambiguous() {
+    let error = json!({ "InstructionError": [1, { "Custom": 6000 }] });
+    let decoded = catalog().decode_transaction_error(&error, &[BOB, ALICE], &[]).unwrap();
+
+    // Alice's code, though Bob may have raised it through CPI
+    assert_eq!(decoded.origin, Origin::TopLevel);
+    assert_eq!(decoded.program_id.as_deref(), Some(ALICE));
+    assert!(decoded.to_string().contains("or a program it invoked"));
+}
+
+#[test]
+fn logs_attribute_the_error_to_the_invoked_program() {
+    let error = json!({ "InstructionError": [1, { "Custom": 6000 }] });
+    let decoded = catalog()
+        .decode_transaction_error(&error, &["ComputeBudget111111111111111111111111111111", ALICE], CPI_LOGS)
+        .unwrap();
+
+    assert_eq!(decoded.origin, Origin::Logs { depth: 2 });
+    assert_eq!(decoded.program_id.as_deref(), Some(BOB));
+    assert_eq!(decoded.description.as_deref(), Some("bob::Paused (6000): This instruction is paused"));
+    assert_eq!(
+        decoded.to_string(),
+        format!("instruction 1 ({} at invoke depth 2): bob::Paused (6000): This instruction is paused", BOB)
+    );
+}
+
+#[test]
+fn failing_program_of_the_logs() {
+    assert_eq!(failing_program(CPI_LOGS), Some((BOB, 2)));
+
+    // the top-level program failing after its CPI returned
+    let logs = [
+        "Program AL1CE11111111111111111111111111111111111111 invoke [1]",
+        "Program B0B1111111111111111111111111111111111111111 invoke [2]",
+        "Program B0B1111111111111111111111111111111111111111 success",
+        "Program AL1CE11111111111111111111111111111111111111 failed: custom program error: 0x1770",
+    ];
+    assert_eq!(failing_program(&logs), Some((ALICE, 1)));
+
+    // no failure, or only `Program log:` lines
+    assert_eq!(failing_program(&logs[..3]), None);
+    assert_eq!(failing_program(&["Program log: failed: nothing"]), None);
+    assert_eq!(failing_program(&[]), None);
+}
@@ -3,17 +3,57 @@
 extern crate proc_macro;
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{parse_macro_input, ItemStruct};
+use syn::{Fields, GenericParam, ItemStruct};
+
+#[cfg(test)]
+mod tests;
 
 #[proc_macro_attribute]
 pub fn destroy_attribute(_metadata: TokenStream, _input: TokenStream) -> TokenStream {
-    let input = parse_macro_input!(_input as ItemStruct);
-    let struct_name = &input.ident; // Get the name of the struct
+    destroy(_input.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
 
-    TokenStream::from(quote! {
-        // This returns an empty struct with the same name
-        #[derive(Debug)]
-        struct #struct_name {
+// This returns the struct without its fields: same name, shape (named, tuple
+// or unit), generics, visibility and attributes
+fn destroy(input: proc_macro2::TokenStream) -> syn::Result<proc_macro2::TokenStream> {
+    let input: ItemStruct = syn::parse2(input)?;
+    let ItemStruct { attrs, vis, struct_token, ident, generics, fields, .. } = &input;
+    let where_clause = &generics.where_clause;
+
+    // Type and lifetime parameters must still be used by a field
+    let mut used = Vec::new();
+    for param in &generics.params {
+        match param {
+            GenericParam::Type(param) => {
+                let ident = &param.ident;
+                used.push(quote! { #ident });
+            }
+            GenericParam::Lifetime(param) => {
+                let lifetime = &param.lifetime;
+                used.push(quote! { &#lifetime () });
+            }
+            GenericParam::Const(_) => {}
         }
+    }
+    let marker = if used.is_empty() {
+        None
+    } else {
+        Some(quote! { ::core::marker::PhantomData<(#(#used,)*)> })
+    };
+
+    let body = match (fields, &marker) {
+        (Fields::Named(_), Some(marker)) => quote! { #where_clause { _marker: #marker } },
+        (Fields::Named(_), None) => quote! { #where_clause {} },
+        (Fields::Unnamed(_), Some(marker)) => quote! { (#marker) #where_clause; },
+        (Fields::Unnamed(_), None) => quote! { () #where_clause; },
+        (Fields::Unit, _) => quote! { #where_clause; },
+    };
+
+    Ok(quote! {
+        #[derive(Debug)]
+        #(#attrs)*
+        #vis #struct_token #ident #generics #body
     })
 }
@@ -7,7 +7,9 @@ struct MyStruct {
 }
 
 fn main() {
-    let demo = MyStruct { baz: 3, qux: 4 };
+    // `baz` and `qux` are gone: `MyStruct { baz: 3, qux: 4 }` no longer
+    // compiles, see tests/ui/destroyed_fields.rs
+    let demo = MyStruct {};
 
     println!("struct is {:?}", demo);
 }
@@ -0,0 +1,28 @@
+// Tests of the expansion itself, which needs the crate's private functions:
+// a proc-macro crate exports nothing else than its macros.
+// The helpers come from macro-test-harness, shared by both macro crates.
+//
+//   snapshots()   each tests/expand/*.rs, expanded, against its .expanded.rs
+//   shapes.rs     `#[destroy_attribute]` on every shape of struct
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::Item;
+
+mod shapes;
+
+// Expands `#[destroy_attribute]` on `item`, if it has it
+pub fn expand(mut item: Item) -> syn::Result<TokenStream> {
+    if let Item::Struct(item) = &mut item {
+        if let Some(i) = item.attrs.iter().position(|attr| attr.path.is_ident("destroy_attribute")) {
+            item.attrs.remove(i);
+            return crate::destroy(quote! { #item });
+        }
+    }
+    Ok(quote! { #item })
+}
+
+// Each tests/expand/*.rs, expanded, against its .expanded.rs
+#[test]
+fn snapshots() {
+    macro_test_harness::assert_snapshots(&std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/expand"), expand);
+}
@@ -0,0 +1,52 @@
+// `#[destroy_attribute]` on every shape of struct: named, tuple and unit
+// fields, with or without generics, attributes and visibility. Only the
+// fields go, and the struct keeps its shape.
+use macro_test_harness::{shapes, string};
+use syn::{Fields, File, Item};
+
+use super::expand;
+
+#[test]
+fn destroy_attribute() {
+    for (_, item) in shapes("#[destroy_attribute]") {
+        let source = string(&item);
+        let expanded = expand(Item::Struct(item.clone())).unwrap_or_else(|error| panic!("{} failed: {}", source, error));
+        let file: File = syn::parse2(expanded).expect("the expansion does not parse");
+        let [Item::Struct(after)] = file.items.as_slice() else {
+            panic!("{} did not expand to a single struct", source);
+        };
+
+        assert_eq!(after.ident, item.ident, "name of {}", source);
+        assert_eq!(string(&after.vis), string(&item.vis), "visibility of {}", source);
+        assert_eq!(string(&after.generics), string(&item.generics), "generics of {}", source);
+        assert_eq!(
+            string(&after.generics.where_clause),
+            string(&item.generics.where_clause),
+            "where clause of {}",
+            source
+        );
+
+        let after_attrs: Vec<String> = after.attrs.iter().map(string).collect();
+        for attr in item.attrs.iter().filter(|attr| !attr.path.is_ident("destroy_attribute")) {
+            assert!(after_attrs.contains(&string(attr)), "{} lost {}", source, string(attr));
+        }
+
+        // Same shape, with at most the marker using the generic parameters
+        let same_shape = matches!(
+            (&item.fields, &after.fields),
+            (Fields::Named(_), Fields::Named(_)) | (Fields::Unnamed(_), Fields::Unnamed(_)) | (Fields::Unit, Fields::Unit)
+        );
+        assert!(same_shape, "{} changed shape", source);
+        assert!(
+            after.fields.len() <= 1 && after.fields.iter().all(|field| string(&field.ty).contains("PhantomData")),
+            "fields of {} left",
+            source
+        );
+    }
+}
+
+// Not a struct at all
+#[test]
+fn enums() {
+    assert!(crate::destroy(quote::quote! { enum Shaped { A, B(u8) } }).is_err());
+}
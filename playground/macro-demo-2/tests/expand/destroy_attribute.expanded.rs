@@ -0,0 +1,14 @@
+#[derive(Debug)]
+struct MyStruct {}
+#[derive(Debug)]
+#[derive(Clone)]
+pub struct Generic<'a, T: Clone, const N: usize>
+where
+    T: Default,
+{
+    _marker: ::core::marker::PhantomData<(&'a (), T)>,
+}
+#[derive(Debug)]
+pub(crate) struct Pair();
+#[derive(Debug)]
+struct Unit;
@@ -0,0 +1,20 @@
+#[destroy_attribute]
+struct MyStruct {
+    baz: i32,
+    qux: i32,
+}
+
+#[destroy_attribute]
+#[derive(Clone)]
+pub struct Generic<'a, T: Clone, const N: usize>
+where
+    T: Default,
+{
+    items: [&'a T; N],
+}
+
+#[destroy_attribute]
+pub(crate) struct Pair(u8, u8);
+
+#[destroy_attribute]
+struct Unit;
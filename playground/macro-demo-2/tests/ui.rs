@@ -0,0 +1,9 @@
+// Compile errors of the macros, checked against tests/ui/*.stderr, and
+// expansions that must compile and run, in tests/ui/pass.
+// After changing a message, regenerate them with `TRYBUILD=overwrite cargo test`.
+#[test]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+    t.pass("tests/ui/pass/*.rs");
+}
@@ -0,0 +1,8 @@
+use macro_demo::destroy_attribute;
+
+#[destroy_attribute]
+enum MyEnum {
+    A,
+}
+
+fn main() {}
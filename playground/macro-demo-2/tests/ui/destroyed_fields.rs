@@ -0,0 +1,11 @@
+use macro_demo::destroy_attribute;
+
+#[destroy_attribute]
+struct MyStruct {
+    baz: i32,
+    qux: i32,
+}
+
+fn main() {
+    let _ = MyStruct { baz: 3, qux: 4 };
+}
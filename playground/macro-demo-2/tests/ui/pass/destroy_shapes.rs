@@ -0,0 +1,34 @@
+// `#[destroy_attribute]` keeps the generics, visibility and attributes
+use macro_demo::destroy_attribute;
+
+#[destroy_attribute]
+struct Named {
+    baz: i32,
+}
+
+#[destroy_attribute]
+#[derive(Clone, Default)]
+pub struct Generic<'a, T: Clone> {
+    value: &'a T,
+}
+
+#[destroy_attribute]
+pub(crate) struct Tuple<T>(T, u8)
+where
+    T: Copy;
+
+#[destroy_attribute]
+struct Array<const N: usize>([u8; N]);
+
+#[destroy_attribute]
+struct Unit;
+
+fn main() {
+    println!("{:?}", Named {});
+
+    let generic = Generic::<'static, String>::default();
+    println!("{:?}", generic.clone());
+
+    println!("{:?}", Tuple::<u8>(Default::default()));
+    println!("{:?} {:?}", Array::<4>(), Unit);
+}
@@ -0,0 +1,240 @@
+// `#[derive(Accessors)]`, see its doc in lib.rs
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::parse::{Parse, ParseStream};
+use syn::{Attribute, Data, DeriveInput, Field, Fields, Ident, Token, Type};
+
+use crate::space::fixed_size;
+
+// `#[accessors(account = bob_data_account, authority = authority)]` on the
+// struct, both optional
+struct StructArgs {
+    account:   Option<Ident>,
+    authority: Option<Ident>,
+}
+
+impl Parse for StructArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut args = StructArgs { account: None, authority: None };
+
+        while !input.is_empty() {
+            let key: Ident = input.parse()?;
+            input.parse::<Token![=]>()?;
+            let value: Ident = input.parse()?;
+
+            match key.to_string().as_str() {
+                "account" => args.account = Some(value),
+                "authority" => args.authority = Some(value),
+                _ => {
+                    return Err(syn::Error::new(
+                        key.span(),
+                        format!("unknown key `{}`, expected `account` or `authority`", key),
+                    ))
+                }
+            }
+
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+
+        Ok(args)
+    }
+}
+
+fn struct_args(attrs: &[Attribute]) -> syn::Result<StructArgs> {
+    match attrs.iter().find(|attr| attr.path.is_ident("accessors")) {
+        Some(attr) => attr.parse_args(),
+        None => Ok(StructArgs { account: None, authority: None }),
+    }
+}
+
+// `#[accessors(instruction)]` on a field, asking for the accounts setting it
+fn has_instruction(field: &Field) -> syn::Result<bool> {
+    let Some(attr) = field.attrs.iter().find(|attr| attr.path.is_ident("accessors")) else {
+        return Ok(false);
+    };
+
+    let key: Ident = attr.parse_args()?;
+    if key != "instruction" {
+        return Err(syn::Error::new(key.span(), format!("unknown key `{}`, expected `instruction`", key)));
+    }
+    Ok(true)
+}
+
+// Getters return the fixed-size types by value and the others by reference
+fn is_copied(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) if path.qself.is_none() => {
+            fixed_size(&path.path.segments.last().unwrap().ident.to_string()).is_some()
+        }
+        _ => false,
+    }
+}
+
+// `BobData` becomes `bob_data`
+fn snake_case(name: &Ident) -> Ident {
+    let mut snake = String::new();
+    for (i, c) in name.to_string().chars().enumerate() {
+        if c.is_uppercase() {
+            if i > 0 {
+                snake.push('_');
+            }
+            snake.extend(c.to_lowercase());
+        } else {
+            snake.push(c);
+        }
+    }
+    Ident::new(&snake, name.span())
+}
+
+// `call_policy` becomes `CallPolicy`
+fn camel_case(name: &Ident) -> String {
+    name.to_string()
+        .split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+pub fn derive_accessors(input: TokenStream) -> syn::Result<TokenStream> {
+    let input: DeriveInput = syn::parse2(input)?;
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => fields.named.iter().collect::<Vec<_>>(),
+            _ => {
+                return Err(syn::Error::new_spanned(&data.fields, "`Accessors` can only be derived for structs with named fields"));
+            }
+        },
+        _ => return Err(syn::Error::new_spanned(&input.ident, "`Accessors` can only be derived for structs")),
+    };
+
+    let args = struct_args(&input.attrs)?;
+    let name = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+
+    let mut methods = Vec::new();
+    let mut instructions = Vec::new();
+    for field in &fields {
+        let field_name = field.ident.as_ref().unwrap();
+        let ty = &field.ty;
+        let setter = format_ident!("set_{}", field_name);
+
+        let getter = if is_copied(ty) {
+            quote! { pub fn #field_name(&self) -> #ty { self.#field_name } }
+        } else {
+            quote! { pub fn #field_name(&self) -> &#ty { &self.#field_name } }
+        };
+        methods.push(quote! {
+            #getter
+
+            pub fn #setter(&mut self, value: #ty) {
+                self.#field_name = value;
+            }
+        });
+
+        if has_instruction(field)? {
+            instructions.push(instruction(&input, &args, field_name, ty)?);
+        }
+    }
+
+    let builder = builder(&input, &fields);
+
+    Ok(quote! {
+        impl #impl_generics #name #type_generics #where_clause {
+            #(#methods)*
+        }
+
+        #builder
+
+        #(#instructions)*
+    })
+}
+
+// `BobDataBuilder`, every field left unset takes `Default::default()`
+fn builder(input: &DeriveInput, fields: &[&Field]) -> TokenStream {
+    let name = &input.ident;
+    let vis = &input.vis;
+    let builder = format_ident!("{}Builder", name);
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+
+    let names: Vec<&Ident> = fields.iter().map(|field| field.ident.as_ref().unwrap()).collect();
+    let types: Vec<&Type> = fields.iter().map(|field| &field.ty).collect();
+
+    let mut predicates: Vec<TokenStream> = where_clause
+        .map(|clause| clause.predicates.iter().map(|predicate| quote! { #predicate }).collect())
+        .unwrap_or_default();
+    for ty in &types {
+        predicates.push(quote! { #ty: ::core::default::Default });
+    }
+
+    quote! {
+        #vis struct #builder #impl_generics #where_clause {
+            #(#names: ::core::option::Option<#types>,)*
+        }
+
+        impl #impl_generics #name #type_generics #where_clause {
+            pub fn builder() -> #builder #type_generics {
+                #builder { #(#names: ::core::option::Option::None,)* }
+            }
+        }
+
+        impl #impl_generics #builder #type_generics #where_clause {
+            #(
+                pub fn #names(mut self, value: #types) -> Self {
+                    self.#names = ::core::option::Option::Some(value);
+                    self
+                }
+            )*
+        }
+
+        impl #impl_generics #builder #type_generics where #(#predicates,)* {
+            pub fn build(self) -> #name #type_generics {
+                #name { #(#names: self.#names.unwrap_or_default(),)* }
+            }
+        }
+    }
+}
+
+// Only the `#[derive(Accounts)]` struct for setting `field`, e.g.
+// `SetBobDataCallPolicy`, and its `apply`: no handler is generated, the
+// `#[program]` module declares it.
+fn instruction(input: &DeriveInput, args: &StructArgs, field: &Ident, ty: &Type) -> syn::Result<TokenStream> {
+    let name = &input.ident;
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(&input.generics, "`#[accessors(instruction)]` does not support generic accounts"));
+    }
+    let Some(authority) = &args.authority else {
+        return Err(syn::Error::new(
+            field.span(),
+            "`#[accessors(instruction)]` needs the authority field, as in `#[accessors(authority = authority)]` on the struct",
+        ));
+    };
+
+    let accounts = format_ident!("Set{}{}", name, camel_case(field));
+    let account = args.account.clone().unwrap_or_else(|| snake_case(name));
+    let setter = format_ident!("set_{}", field);
+
+    Ok(quote! {
+        #[derive(Accounts)]
+        pub struct #accounts<'info> {
+            #[account(mut, has_one = #authority)]
+            pub #account: Account<'info, #name>,
+
+            pub #authority: Signer<'info>,
+        }
+
+        impl<'info> #accounts<'info> {
+            pub fn apply(&mut self, value: #ty) -> Result<()> {
+                self.#account.#setter(value);
+                Ok(())
+            }
+        }
+    })
+}
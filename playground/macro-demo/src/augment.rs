@@ -0,0 +1,193 @@
+// `#[augment(...)]`, see its doc in lib.rs
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, ToTokens};
+use syn::ext::IdentExt;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{parenthesized, Expr, Field, Fields, FieldsNamed, Ident, ImplItemMethod, Item, ItemStruct, LitStr, Token, Type, Visibility};
+
+// `field(name = "foo", ty = i32, default = 10)`
+struct NewField {
+    name:    Ident,
+    ty:      Type,
+    default: Option<Expr>,
+}
+
+impl NewField {
+    // `span` is the one of `field`, for the errors about missing keys
+    fn parse(input: ParseStream, span: Span) -> syn::Result<Self> {
+        let mut name = None;
+        let mut ty = None;
+        let mut default = None;
+
+        while !input.is_empty() {
+            // Keywords too, `type` is an easy mistake for `ty`
+            let key = Ident::parse_any(input)?;
+            input.parse::<Token![=]>()?;
+
+            match key.to_string().as_str() {
+                "name" => {
+                    let lit: LitStr = input.parse()?;
+                    let ident = syn::parse_str::<Ident>(&lit.value())
+                        .map_err(|_| syn::Error::new(lit.span(), format!("`{}` is not a valid field name", lit.value())))?;
+                    name = Some(Ident::new(&ident.to_string(), lit.span()));
+                }
+                "ty" => ty = Some(input.parse()?),
+                "default" => default = Some(input.parse()?),
+                _ => {
+                    return Err(syn::Error::new(
+                        key.span(),
+                        format!("unknown key `{}`, expected `name`, `ty` or `default`", key),
+                    ))
+                }
+            }
+
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+
+        Ok(NewField {
+            name: name.ok_or_else(|| syn::Error::new(span, "missing `name = \"...\"`"))?,
+            ty: ty.ok_or_else(|| syn::Error::new(span, "missing `ty = Type`"))?,
+            default,
+        })
+    }
+}
+
+// `field(...)` and `method(fn double_foo(&self) -> i32 { self.foo * 2 })`,
+// separated by commas
+struct Args {
+    fields:  Vec<NewField>,
+    methods: Vec<ImplItemMethod>,
+}
+
+impl Parse for Args {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut args = Args { fields: Vec::new(), methods: Vec::new() };
+
+        while !input.is_empty() {
+            let kind: Ident = input.parse()?;
+            let content;
+            parenthesized!(content in input);
+
+            match kind.to_string().as_str() {
+                "field" => args.fields.push(NewField::parse(&content, kind.span())?),
+                "method" => args.methods.push(content.parse()?),
+                _ => return Err(syn::Error::new(kind.span(), "expected `field(...)` or `method(...)`")),
+            }
+
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+
+        Ok(args)
+    }
+}
+
+pub fn augment(args: TokenStream, input: TokenStream) -> syn::Result<TokenStream> {
+    let args: Args = syn::parse2(args)?;
+    let mut item = match syn::parse2::<Item>(input)? {
+        Item::Struct(item) => item,
+        other => return Err(syn::Error::new_spanned(other, "`#[augment]` only applies to structs")),
+    };
+
+    let Args { fields: new_fields, methods } = args;
+
+    // A unit struct becomes a struct with named fields
+    if let Fields::Unit = item.fields {
+        if !new_fields.is_empty() {
+            item.fields = Fields::Named(FieldsNamed { brace_token: Default::default(), named: Punctuated::new() });
+            item.semi_token = None;
+        }
+    }
+
+    let original: Vec<Field> = item.fields.iter().cloned().collect();
+    match &mut item.fields {
+        Fields::Named(fields) => {
+            for new_field in &new_fields {
+                if let Some(existing) = fields.named.iter().find(|f| f.ident.as_ref() == Some(&new_field.name)) {
+                    let mut error = syn::Error::new(new_field.name.span(), format!("field `{}` already exists", new_field.name));
+                    error.combine(syn::Error::new_spanned(existing, "declared here"));
+                    return Err(error);
+                }
+
+                fields.named.push(Field {
+                    attrs:       Vec::new(),
+                    vis:         Visibility::Inherited,
+                    ident:       Some(new_field.name.clone()),
+                    colon_token: Some(Default::default()),
+                    ty:          new_field.ty.clone(),
+                });
+            }
+        }
+        Fields::Unnamed(fields) if !new_fields.is_empty() => {
+            return Err(syn::Error::new_spanned(fields, "`#[augment]` can only add fields to a struct with named fields"));
+        }
+        _ => {}
+    }
+
+    let default_impl = if new_fields.iter().any(|field| field.default.is_some()) {
+        Some(default_impl(&item, &original, &new_fields))
+    } else {
+        None
+    };
+
+    let name = &item.ident;
+    let (impl_generics, type_generics, where_clause) = item.generics.split_for_impl();
+    let methods_impl = if methods.is_empty() {
+        None
+    } else {
+        Some(quote! {
+            impl #impl_generics #name #type_generics #where_clause {
+                #(#methods)*
+            }
+        })
+    };
+
+    Ok(quote! {
+        #item
+        #default_impl
+        #methods_impl
+    })
+}
+
+// `Default` with the given defaults for the new fields, and
+// `Default::default()` for the others
+fn default_impl(item: &ItemStruct, original: &[Field], new_fields: &[NewField]) -> TokenStream {
+    let name = &item.ident;
+    let (impl_generics, type_generics, where_clause) = item.generics.split_for_impl();
+
+    let mut predicates: Vec<TokenStream> = where_clause
+        .map(|clause| clause.predicates.iter().map(ToTokens::to_token_stream).collect())
+        .unwrap_or_default();
+    let mut values = Vec::new();
+
+    for field in original {
+        let ty = &field.ty;
+        predicates.push(quote! { #ty: ::core::default::Default });
+        let name = &field.ident;
+        values.push(quote! { #name: ::core::default::Default::default() });
+    }
+    for field in new_fields {
+        let name = &field.name;
+        let value = match &field.default {
+            Some(default) => quote! { #default },
+            None => {
+                let ty = &field.ty;
+                predicates.push(quote! { #ty: ::core::default::Default });
+                quote! { ::core::default::Default::default() }
+            }
+        };
+        values.push(quote! { #name: #value });
+    }
+
+    quote! {
+        impl #impl_generics ::core::default::Default for #name #type_generics where #(#predicates,)* {
+            fn default() -> Self {
+                Self { #(#values,)* }
+            }
+        }
+    }
+}
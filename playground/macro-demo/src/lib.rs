@@ -3,7 +3,6 @@
 extern crate proc_macro;
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{parse_macro_input, ItemStruct};
 
 // Declaring a procedural attribute-like macro using the `proc_macro_attribute` directive
 // This makes the macro usable as an attribute
@@ -11,40 +10,276 @@ use syn::{parse_macro_input, ItemStruct};
 #[proc_macro_attribute]
 // The function `foo_bar_attribute` takes two arguments:
 // _metadata: The arguments provided to the macro (if any)
-// _input: The TokenStream the macro is applied to
-pub fn foo_bar_attribute(_metadata: TokenStream, _input: TokenStream) -> TokenStream {
-    // Parse the input TokenStream into an AST node representing a struct
-    let input = parse_macro_input!(_input as ItemStruct);
-    let struct_name = &input.ident; // Get the name of the struct
-
-    // Constructing the output TokenStream using the quote! macro
-    // The quote! macro allows for writing Rust code as if it were a string,
-    // but with the ability to interpolate values
-    TokenStream::from(quote! {
-        // Derive Debug trait for #struct_name to enable formatted output with `println()`
-        #[derive(Debug)]
-        // Defining a new struct #struct_name with two fields: foo and bar
-        struct #struct_name {
-            foo: i32,
-            bar: i32,
-        }
+// input: The TokenStream the macro is applied to
+pub fn foo_bar_attribute(_metadata: TokenStream, input: TokenStream) -> TokenStream {
+    foo_bar(input.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
 
-        // Implementing the Default trait for #struct_name
-        // This provides a default() method to create a new instance of #struct_name
-        impl Default for #struct_name {
-            // The default method returns a new instance of #struct_name
-            // with foo set to 10 and bar set to 20
-            fn default() -> Self {
-                #struct_name { foo: 10, bar: 20}
-            }
+fn foo_bar(input: proc_macro2::TokenStream) -> syn::Result<proc_macro2::TokenStream> {
+    // foo and bar are named fields, a tuple struct cannot have them
+    match syn::parse2::<syn::Item>(input.clone())? {
+        syn::Item::Struct(item) if !matches!(item.fields, syn::Fields::Unnamed(_)) => {}
+        syn::Item::Struct(item) => {
+            return Err(syn::Error::new_spanned(item.fields, "`#[foo_bar_attribute]` does not apply to tuple structs"));
         }
+        other => return Err(syn::Error::new_spanned(other, "`#[foo_bar_attribute]` only applies to structs")),
+    }
 
-        impl #struct_name {
-            // Defining a method double_foo for #struct_name
-            // This method returns double the value of foo
-            fn double_foo(&self) -> i32 {
-                self.foo * 2
-            }
-        }
-    })
+    // `#[augment]` keeps the fields of the struct and adds ours:
+    // - foo and bar, set to 10 and 20 by the generated `Default`
+    // - a double_foo method, which returns double the value of foo
+    let args = quote! {
+        field(name = "foo", ty = i32, default = 10),
+        field(name = "bar", ty = i32, default = 20),
+        method(fn double_foo(&self) -> i32 {
+            self.foo * 2
+        }),
+    };
+
+    // Derive Debug to enable formatted output with `println()`
+    let input = quote! {
+        #[derive(Debug)]
+        #input
+    };
+
+    augment::augment(args, input)
+}
+
+mod accessors;
+mod augment;
+mod pda;
+mod space;
+mod trace;
+mod validate;
+
+#[cfg(test)]
+mod tests;
+
+// `#[validate(...)]` declares constraints on the arguments of an Anchor
+// instruction handler and inserts the matching checks at the start of its body:
+//
+//     #[validate(
+//         a(range = 10..=100),
+//         b(min = 1 @ MyErrors::BTooSmall, max = 5),
+//         amount(non_zero),
+//         name(max_len = 32),
+//         items(len = 1..=8),
+//         kind(one_of = [Kind::Small, Kind::Large]),
+//     )]
+//     pub fn handler(ctx: Context<Accounts>, a: u64, ...) -> Result<()> { ... }
+//
+// A failed check logs which argument was wrong and returns one of the errors
+// added by `#[validation_errors]`, from the `Errors` enum unless another one
+// is given with `errors = MyErrors`, or the error following its `@`. Range,
+// `min`/`max` (inclusive) and length bounds are also exported as
+// `#[constant]`s (`HANDLER_A_MIN`, `HANDLER_A_MAX`, `HANDLER_NAME_MAX_LEN`,
+// `HANDLER_ITEMS_LEN_MIN`, ...) so clients can check arguments before sending.
+#[proc_macro_attribute]
+pub fn validate(metadata: TokenStream, input: TokenStream) -> TokenStream {
+    validate::validate(metadata.into(), input.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+// `#[validation_errors]` goes above `#[error_code]` and adds the errors
+// returned by `#[validate]` at the end of the enum, so the codes of the
+// program's own errors do not change: ValueOutOfRange, ValueIsZero,
+// StringTooLong, LengthOutOfRange and ValueNotAllowed follow its last one.
+//
+// The legacy IDL parser reads the source and would miss these variants (and
+// misnumber the others), so programs using it must build their IDL with the
+// `idl-build` feature.
+#[proc_macro_attribute]
+pub fn validation_errors(_metadata: TokenStream, input: TokenStream) -> TokenStream {
+    validate::validation_errors(input.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+// `#[derive(Space)]` computes the largest Borsh-serialized size of a struct
+// or enum, for the `space` of an Anchor account:
+//
+//     #[account]
+//     #[derive(Space)]
+//     pub struct Player {
+//         points:    u32,
+//         authority: Pubkey,
+//         #[max_len(32)]
+//         name:      String,
+//         #[max_len(10, 16)] // 10 tags of up to 16 bytes
+//         tags:      Vec<String>,
+//     }
+//
+//     #[account(init, payer = signer, space = 8 + Player::INIT_SPACE)]
+//
+// Unlike `size_of::<Player>()`, it counts no alignment padding, and gives
+// `String`, `Vec`, `Option` and enum fields their serialized size: a 4-byte
+// length plus `#[max_len]` items, a 1-byte tag plus the value, a 1-byte
+// variant index plus the largest variant. Fields of other types must derive
+// `Space` themselves. `INIT_SPACE` leaves out the 8-byte discriminator.
+#[proc_macro_derive(Space, attributes(max_len))]
+pub fn derive_space(input: TokenStream) -> TokenStream {
+    space::derive_space(input.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+// `#[augment(...)]` adds fields and methods to a struct, keeping its own
+// fields, generics and attributes:
+//
+//     #[augment(
+//         field(name = "foo", ty = i32, default = 10),
+//         field(name = "count", ty = u64),
+//         method(fn double_foo(&self) -> i32 { self.foo * 2 }),
+//     )]
+//     struct MyStruct<T> {
+//         baz: T,
+//     }
+//
+// The fields are appended in order. When one of them has a `default`, the
+// struct also gets a `Default` implementation, where the fields without one
+// take `Default::default()`. A unit struct becomes a struct with named
+// fields; a tuple struct can only get methods.
+#[proc_macro_attribute]
+pub fn augment(metadata: TokenStream, input: TokenStream) -> TokenStream {
+    augment::augment(metadata.into(), input.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+// `#[derive(Accessors)]` gives each field of a struct a getter and a setter,
+// and the struct a builder, meant for tests:
+//
+//     #[account]
+//     #[derive(Accessors)]
+//     #[accessors(authority = authority, account = bob_data_account)]
+//     pub struct BobData {
+//         result:      u64,
+//         authority:   Pubkey,
+//         #[accessors(instruction)]
+//         call_policy: CallPolicy,
+//     }
+//
+//     data.result()                  // u64
+//     data.call_policy()             // &CallPolicy
+//     data.set_result(3);
+//     BobData::builder().result(3).build()
+//
+// Getters return the fixed-size types (integers, `bool`, `Pubkey`...) by
+// value and the others by reference. Fields left out of the builder take
+// `Default::default()`.
+//
+// A field marked `#[accessors(instruction)]` also gets a `#[derive(Accounts)]`
+// struct, `SetBobDataCallPolicy`, with the account, which must be signed by
+// the `authority` it stores (`has_one`), and that signer, plus an `apply`
+// method setting the field. Only these two are generated: the instruction
+// itself is still written in the `#[program]` module, as a single line:
+//
+//     pub fn set_call_policy(ctx: Context<SetBobDataCallPolicy>, call_policy: CallPolicy) -> Result<()> {
+//         ctx.accounts.apply(call_policy)
+//     }
+//
+// The account is named after the struct (`bob_data`) unless `account` says
+// otherwise. The generated accounts expect `anchor_lang::prelude::*` in
+// scope, and the program needs the `idl-build` feature to have them in its
+// IDL, as Anchor cannot see them in the source.
+#[proc_macro_derive(Accessors, attributes(accessors))]
+pub fn derive_accessors(input: TokenStream) -> TokenStream {
+    accessors::derive_accessors(input.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+// `#[trace]` logs a `#[program]` handler's entry, with its arguments, and its
+// exit, with the error when it fails, each with the compute units left:
+//
+//     #[trace]
+//     pub fn set(ctx: Context<Set>, new_x: u64) -> Result<()> { ... }
+//
+//     Program log: -> set(new_x: 42), 199650 CU left
+//     Program log: <- set failed: Paused (6000), 195311 CU left
+//
+// It only does so when the program is built with its `trace` feature, which
+// the program must declare (`trace = []`): without it, the handler is left
+// untouched, so release builds pay nothing. Arguments are logged with
+// `Debug`, except the `Context`; `#[trace(skip(a, b))]` leaves out others,
+// e.g. the large ones.
+#[proc_macro_attribute]
+pub fn trace(metadata: TokenStream, input: TokenStream) -> TokenStream {
+    trace::trace(metadata.into(), input.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+// `#[pda(seeds = [...])]` declares the seeds of the accounts of a type, once,
+// and generates what derives their addresses from them, on-chain and off:
+//
+//     #[account]
+//     #[pda(seeds = ["player", authority: Pubkey])]
+//     pub struct Player { ... }
+//
+//     Player::SEED                                 // b"player"
+//     Player::find_address(&authority)             // (address, canonical bump)
+//     Player::create_address(&authority, bump)?    // address, with a known bump
+//     Player::signer_seeds(&authority, bump)       // for `invoke_signed`:
+//
+//     let seeds = Player::signer_seeds(&authority, bump);
+//     CpiContext::new_with_signer(program, accounts, &[&seeds.as_slices()])
+//
+// A seed is a string, or a named argument of type `Pubkey` (its bytes), an
+// integer (little-endian), `String` (UTF-8) or `[u8; N]`. With several
+// strings, the constants are `SEED_0`, `SEED_1`... The addresses are those of
+// the program's `declare_id!`; the generated code expects
+// `anchor_lang::prelude::*` in scope.
+//
+// A seed is at most 32 bytes: longer strings and arrays do not compile. A
+// `String` argument can only be checked at run time, so with one
+// `find_address` returns a `Result`, failing with `ConstraintSeeds` when it
+// is too long, as `create_address` does.
+//
+// The `seeds` constraints of the accounts structs take the same seeds from
+// it, with `#[pda_accounts]`.
+#[proc_macro_attribute]
+pub fn pda(metadata: TokenStream, input: TokenStream) -> TokenStream {
+    pda::pda(metadata.into(), input.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+// `#[pda_accounts]`, above `#[derive(Accounts)]`, expands the `seeds`
+// constraints written as a call to `<type>::seeds` into the seeds the
+// type's `#[pda]` declares, with the same conversions as `signer_seeds`:
+//
+//     #[pda_accounts]
+//     #[derive(Accounts)]
+//     pub struct Initialize<'info> {
+//         #[account(init, seeds = [Player::seeds(signer.key())], bump, ...)]
+//         player: Account<'info, Player>,
+//         ...
+//     }
+//
+// gives Anchor `seeds = [Player::SEED, signer.key().as_ref()]`. The call
+// takes an expression per argument seed, in order, and stays in brackets
+// so that Anchor's IDL parser still reads a list.
+//
+// The type must be declared with `#[pda]` in the same crate, and in scope
+// under the path the call uses: `#[pda]` declares a `__pda_seeds_<type>!`
+// macro next to it, which `#[pda_accounts]` invokes to learn the seeds.
+#[proc_macro_attribute]
+pub fn pda_accounts(metadata: TokenStream, input: TokenStream) -> TokenStream {
+    pda::pda_accounts(metadata.into(), input.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+// The second half of `#[pda_accounts]`, invoked by the `__pda_seeds_<type>!`
+// macros with the seeds of their type
+#[doc(hidden)]
+#[proc_macro]
+pub fn __pda_accounts_seeds(input: TokenStream) -> TokenStream {
+    pda::pda_accounts_seeds(input.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
 }
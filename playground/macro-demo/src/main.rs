@@ -4,7 +4,7 @@
 	use macro_demo::*;
 
 	// Apply the `foo_bar_attribute` procedural attribute-like macro we created in `src/lib.rs` to `struct MyStruct`
-	// The procedural macro will generate a new struct definition with specified fields and methods
+	// The procedural macro keeps the `baz` field and adds the `foo` and `bar` fields and a `double_foo` method
 	#[foo_bar_attribute]
 	struct MyStruct {
 		baz: i32,
@@ -25,5 +25,19 @@
 	
 		// Print the result of calling `double_foo` to the console
 		println!("double foo: {}", double_foo);
+
+		// `#[augment]` does the same with the fields and methods of your choice, on any struct
+		let wrapper = Wrapper::<&str>::default();
+		println!("wrapper is {:?}, label: {}", wrapper, wrapper.label());
+	}
+
+	// The generic parameter and the existing field are kept
+	#[augment(
+		field(name = "count", ty = u64, default = 3),
+		method(fn label(&self) -> String { format!("{:?} x {}", self.value, self.count) }),
+	)]
+	#[derive(Debug)]
+	struct Wrapper<T: std::fmt::Debug> {
+		value: T,
 	}
 	
\ No newline at end of file
@@ -0,0 +1,441 @@
+// `#[pda(seeds = [...])]`, see its doc in lib.rs
+use proc_macro2::{Delimiter, Group, TokenStream, TokenTree};
+use quote::{format_ident, quote, ToTokens};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{bracketed, parenthesized, Expr, ExprLit, Ident, Item, ItemStruct, Lit, LitByteStr, LitStr, Path, Token, Type};
+
+// Longest seed `Pubkey::find_program_address` takes, `MAX_SEED_LEN`
+const MAX_SEED_LEN: usize = 32;
+
+// One seed: a constant, or an argument of the generated functions
+enum Seed {
+    Literal(LitByteStr),
+    Argument { name: Ident, ty: Box<Type> },
+}
+
+impl Parse for Seed {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let lit = if input.peek(LitStr) {
+            let lit: LitStr = input.parse()?;
+            Some(LitByteStr::new(lit.value().as_bytes(), lit.span()))
+        } else if input.peek(LitByteStr) {
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        if let Some(lit) = lit {
+            let len = lit.value().len();
+            if len > MAX_SEED_LEN {
+                return Err(syn::Error::new(lit.span(), format!("a seed is at most {} bytes, this one is {}", MAX_SEED_LEN, len)));
+            }
+            return Ok(Seed::Literal(lit));
+        }
+
+        let name: Ident = input.parse()?;
+        input.parse::<Token![:]>()?;
+        Ok(Seed::Argument { name, ty: input.parse()? })
+    }
+}
+
+// `seeds = ["player", authority: Pubkey]`, and its tokens
+struct Args {
+    seeds: Vec<Seed>,
+    raw:   TokenStream,
+}
+
+impl Parse for Args {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let raw = input.fork().parse::<TokenStream>()?;
+        let key: Ident = input.parse()?;
+        if key != "seeds" {
+            return Err(syn::Error::new(key.span(), format!("unknown key `{}`, expected `seeds = [...]`", key)));
+        }
+        input.parse::<Token![=]>()?;
+
+        let content;
+        let brackets = bracketed!(content in input);
+        let seeds = Punctuated::<Seed, Token![,]>::parse_terminated(&content)?;
+        if seeds.is_empty() {
+            return Err(syn::Error::new(brackets.span, "expected at least one seed"));
+        }
+        // The bump is the 16th
+        if seeds.len() > 15 {
+            return Err(syn::Error::new(brackets.span, "a PDA has at most 15 seeds besides the bump"));
+        }
+
+        Ok(Args { seeds: seeds.into_iter().collect(), raw })
+    }
+}
+
+// How an argument `value` is taken, stored in the signer seeds, turned
+// into bytes, and into a seed of a `seeds` constraint, by type. `unbounded`
+// arguments (`String`) may be longer than a seed, which is only known when
+// deriving the address.
+struct Conversion {
+    param:     TokenStream,
+    owned:     TokenStream,
+    bytes:     TokenStream,
+    seed:      TokenStream,
+    unbounded: bool,
+}
+
+fn conversion(value: &TokenStream, ty: &Type) -> syn::Result<Conversion> {
+    if let Type::Array(array) = ty {
+        if matches!(&*array.elem, Type::Path(path) if path.path.is_ident("u8")) {
+            let len = &array.len;
+            if let Expr::Lit(ExprLit { lit: Lit::Int(int), .. }) = len {
+                let bytes = int.base10_parse::<usize>()?;
+                if bytes > MAX_SEED_LEN {
+                    return Err(syn::Error::new_spanned(len, format!("a seed is at most {} bytes, this one is {}", MAX_SEED_LEN, bytes)));
+                }
+            }
+            return Ok(Conversion {
+                param:     quote! { &#ty },
+                owned:     quote! { [u8; #len] },
+                bytes:     quote! { *#value },
+                seed:      quote! { #value.as_ref() },
+                unbounded: false,
+            });
+        }
+    }
+
+    let Type::Path(path) = ty else {
+        return Err(unsupported(ty));
+    };
+    let Some(ident) = path.path.get_ident() else {
+        return Err(unsupported(ty));
+    };
+
+    match ident.to_string().as_str() {
+        "Pubkey" => Ok(Conversion {
+            param:     quote! { &Pubkey },
+            owned:     quote! { [u8; 32] },
+            bytes:     quote! { #value.to_bytes() },
+            seed:      quote! { #value.as_ref() },
+            unbounded: false,
+        }),
+        "u8" | "u16" | "u32" | "u64" | "u128" | "i8" | "i16" | "i32" | "i64" | "i128" => Ok(Conversion {
+            param:     quote! { #ty },
+            owned:     quote! { [u8; ::core::mem::size_of::<#ty>()] },
+            bytes:     quote! { #value.to_le_bytes() },
+            seed:      quote! { #value.to_le_bytes().as_ref() },
+            unbounded: false,
+        }),
+        "String" => Ok(Conversion {
+            param:     quote! { &str },
+            owned:     quote! { ::std::vec::Vec<u8> },
+            bytes:     quote! { #value.as_bytes().to_vec() },
+            seed:      quote! { #value.as_bytes() },
+            unbounded: true,
+        }),
+        _ => Err(unsupported(ty)),
+    }
+}
+
+fn unsupported(ty: &Type) -> syn::Error {
+    syn::Error::new_spanned(ty, "expected `Pubkey`, an integer, `String` or `[u8; N]`")
+}
+
+pub fn pda(args: TokenStream, input: TokenStream) -> syn::Result<TokenStream> {
+    let args: Args = syn::parse2(args)?;
+    let item = match syn::parse2::<Item>(input)? {
+        Item::Struct(item) => item,
+        other => return Err(syn::Error::new_spanned(other, "`#[pda]` only applies to structs")),
+    };
+    if !item.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(&item.generics, "`#[pda]` does not support generic structs"));
+    }
+
+    let name = &item.ident;
+    let vis = &item.vis;
+    let seeds_struct = format_ident!("{}Seeds", name);
+
+    let constant_names = constant_names(&args.seeds);
+    let mut constants = Vec::new();
+    let mut params = Vec::new();
+    let mut names = Vec::new();
+    let mut fields = Vec::new();
+    let mut values = Vec::new();
+    let mut slices = Vec::new();
+    let mut checks = Vec::new();
+    let mut fallible = false;
+    for seed in &args.seeds {
+        match seed {
+            Seed::Literal(lit) => {
+                let constant = &constant_names[constants.len()];
+                slices.push(quote! { #name::#constant });
+                constants.push(quote! { pub const #constant: &'static [u8] = #lit; });
+            }
+            Seed::Argument { name: arg, ty } => {
+                if names.contains(&arg) {
+                    return Err(syn::Error::new(arg.span(), format!("seed `{}` is declared twice", arg)));
+                }
+                let Conversion { param, owned, bytes, unbounded, .. } = conversion(&arg.to_token_stream(), ty)?;
+                fallible |= unbounded;
+                // `[u8; N]` with a constant `N` is only known to the compiler
+                if let Type::Array(array) = &**ty {
+                    if !matches!(array.len, Expr::Lit(_)) {
+                        let len = &array.len;
+                        let message = format!("seed `{}` is longer than {} bytes", arg, MAX_SEED_LEN);
+                        checks.push(quote! { const _: () = assert!((#len) <= #MAX_SEED_LEN, #message); });
+                    }
+                }
+                params.push(quote! { #arg: #param });
+                names.push(arg);
+                fields.push(quote! { #arg: #owned });
+                values.push(quote! { #arg: #bytes });
+                slices.push(quote! { &self.#arg[..] });
+            }
+        }
+    }
+    // Without, then with the bump
+    let without_bump = slices.len();
+    let count = without_bump + 1;
+
+    // A `String` seed may be too long, and then there is no address
+    let find_address = if fallible {
+        quote! {
+            pub fn find_address(#(#params),*) -> Result<(Pubkey, u8)> {
+                let seeds = Self::signer_seeds(#(#names,)* 0);
+                let slices = seeds.as_slices();
+                Pubkey::try_find_program_address(&slices[..#without_bump], &crate::ID)
+                    .ok_or_else(|| error!(::anchor_lang::error::ErrorCode::ConstraintSeeds))
+            }
+        }
+    } else {
+        quote! {
+            pub fn find_address(#(#params),*) -> (Pubkey, u8) {
+                let seeds = Self::signer_seeds(#(#names,)* 0);
+                let slices = seeds.as_slices();
+                Pubkey::find_program_address(&slices[..#without_bump], &crate::ID)
+            }
+        }
+    };
+
+    // What `#[pda_accounts]` expands `seeds = [<name>::seeds(...)]` with
+    let seeds_macro = seeds_macro(name);
+    let raw_args = args.raw;
+
+    Ok(quote! {
+        #item
+
+        #(#checks)*
+
+        #[doc(hidden)]
+        macro_rules! #seeds_macro {
+            (($($path:tt)*) $($item:tt)*) => {
+                ::macro_demo::__pda_accounts_seeds! { ($($path)*) [#raw_args] $($item)* }
+            };
+        }
+        // For the accounts structs above it, and in other modules
+        #[doc(hidden)]
+        #[allow(unused_imports)]
+        pub(crate) use #seeds_macro;
+
+        impl #name {
+            #(#constants)*
+
+            #find_address
+
+            pub fn create_address(#(#params,)* bump: u8) -> Result<Pubkey> {
+                let seeds = Self::signer_seeds(#(#names,)* bump);
+                Pubkey::create_program_address(&seeds.as_slices(), &crate::ID)
+                    .map_err(|_| error!(::anchor_lang::error::ErrorCode::ConstraintSeeds))
+            }
+
+            pub fn signer_seeds(#(#params,)* bump: u8) -> #seeds_struct {
+                #seeds_struct { #(#values,)* bump: [bump] }
+            }
+        }
+
+        #vis struct #seeds_struct {
+            #(#fields,)*
+            bump: [u8; 1],
+        }
+
+        impl #seeds_struct {
+            pub fn as_slices(&self) -> [&[u8]; #count] {
+                [#(#slices,)* &self.bump[..]]
+            }
+        }
+    })
+}
+
+// `SEED` when there is a single constant seed, `SEED_0`, `SEED_1`... otherwise
+fn constant_names(seeds: &[Seed]) -> Vec<Ident> {
+    let count = seeds.iter().filter(|seed| matches!(seed, Seed::Literal(_))).count();
+    (0..count)
+        .map(|i| if count == 1 { format_ident!("SEED") } else { format_ident!("SEED_{}", i) })
+        .collect()
+}
+
+// `__pda_seeds_<name>`, declared next to the type by `#[pda]`
+fn seeds_macro(name: &Ident) -> Ident {
+    format_ident!("__pda_seeds_{}", name)
+}
+
+// `seeds = [Player::seeds(...)]` in an `#[account(...)]`: the type's path
+// and the arguments, if `group` is the list of such a constraint
+fn seeds_call(group: &Group) -> Option<(Path, Punctuated<Expr, Token![,]>)> {
+    if group.delimiter() != Delimiter::Bracket {
+        return None;
+    }
+    let Ok(Expr::Call(call)) = syn::parse2::<Expr>(group.stream()) else {
+        return None;
+    };
+    let Expr::Path(func) = *call.func else {
+        return None;
+    };
+
+    let mut segments: Vec<_> = func.path.segments.into_iter().collect();
+    if segments.len() < 2 || segments.pop().unwrap().ident != "seeds" {
+        return None;
+    }
+    let path = Path { leading_colon: func.path.leading_colon, segments: segments.into_iter().collect() };
+    Some((path, call.args))
+}
+
+// Calls `f` on the list of each `seeds = [...]` at the top level of an
+// `#[account(...)]`, replacing it with what `f` returns, if anything
+fn rewrite_seeds(tokens: TokenStream, f: &mut impl FnMut(&Group) -> syn::Result<Option<TokenStream>>) -> syn::Result<TokenStream> {
+    let trees: Vec<TokenTree> = tokens.into_iter().collect();
+    let mut rewritten = Vec::with_capacity(trees.len());
+    for (i, tree) in trees.iter().enumerate() {
+        let is_seeds = i >= 2
+            && matches!(&trees[i - 2], TokenTree::Ident(ident) if ident == "seeds")
+            && matches!(&trees[i - 1], TokenTree::Punct(punct) if punct.as_char() == '=');
+        match tree {
+            TokenTree::Group(group) if is_seeds => match f(group)? {
+                Some(seeds) => {
+                    let mut replaced = Group::new(Delimiter::Bracket, seeds);
+                    replaced.set_span(group.span());
+                    rewritten.push(TokenTree::Group(replaced));
+                }
+                None => rewritten.push(tree.clone()),
+            },
+            _ => rewritten.push(tree.clone()),
+        }
+    }
+    Ok(rewritten.into_iter().collect())
+}
+
+// Rewrites the `seeds = [...]` of every `#[account(...)]` of `item`
+fn rewrite_accounts(item: &mut ItemStruct, f: &mut impl FnMut(&Group) -> syn::Result<Option<TokenStream>>) -> syn::Result<()> {
+    for field in item.fields.iter_mut() {
+        for attr in field.attrs.iter_mut().filter(|attr| attr.path.is_ident("account")) {
+            let Some(TokenTree::Group(args)) = attr.tokens.clone().into_iter().next() else {
+                continue;
+            };
+            let mut replaced = Group::new(args.delimiter(), rewrite_seeds(args.stream(), f)?);
+            replaced.set_span(args.span());
+            attr.tokens = TokenTree::Group(replaced).into();
+        }
+    }
+    Ok(())
+}
+
+// Hands `item` to the seeds macro of the first type whose seeds it still
+// calls for, which expands them and comes back here, until there are none
+fn next_seeds(mut item: ItemStruct) -> syn::Result<TokenStream> {
+    let mut first = None;
+    rewrite_accounts(&mut item, &mut |group| {
+        if first.is_none() {
+            first = seeds_call(group).map(|(path, _)| path);
+        }
+        Ok(None)
+    })?;
+
+    let Some(path) = first else {
+        return Ok(quote! { #item });
+    };
+    let mut macro_path = path.clone();
+    let last = macro_path.segments.last_mut().unwrap();
+    last.ident = seeds_macro(&last.ident);
+    Ok(quote! { #macro_path! { (#path) #item } })
+}
+
+pub fn pda_accounts(args: TokenStream, input: TokenStream) -> syn::Result<TokenStream> {
+    if !args.is_empty() {
+        return Err(syn::Error::new_spanned(args, "`#[pda_accounts]` takes no arguments"));
+    }
+    let item = match syn::parse2::<Item>(input)? {
+        Item::Struct(item) => item,
+        other => return Err(syn::Error::new_spanned(other, "`#[pda_accounts]` only applies to accounts structs")),
+    };
+    next_seeds(item)
+}
+
+// `value`, in parentheses if a method called on it would bind tighter
+fn receiver(value: &Expr) -> TokenStream {
+    match value {
+        Expr::Path(_) | Expr::MethodCall(_) | Expr::Call(_) | Expr::Field(_) | Expr::Index(_) | Expr::Paren(_) | Expr::Lit(_) => {
+            quote! { #value }
+        }
+        _ => quote! { (#value) },
+    }
+}
+
+// `__pda_accounts_seeds! { (Player) [seeds = [...]] <accounts struct> }`,
+// from the `#[pda]` of `Player`
+struct SeedsInput {
+    path: Path,
+    args: Args,
+    item: ItemStruct,
+}
+
+impl Parse for SeedsInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let path;
+        parenthesized!(path in input);
+        let args;
+        bracketed!(args in input);
+        Ok(SeedsInput { path: path.parse()?, args: args.parse()?, item: input.parse()? })
+    }
+}
+
+pub fn pda_accounts_seeds(input: TokenStream) -> syn::Result<TokenStream> {
+    let SeedsInput { path, args, mut item } = syn::parse2(input)?;
+    let constant_names = constant_names(&args.seeds);
+    let expected = args.seeds.iter().filter(|seed| matches!(seed, Seed::Argument { .. })).count();
+
+    rewrite_accounts(&mut item, &mut |group| {
+        let Some((called, values)) = seeds_call(group) else {
+            return Ok(None);
+        };
+        if called.to_token_stream().to_string() != path.to_token_stream().to_string() {
+            return Ok(None);
+        }
+        if values.len() != expected {
+            return Err(syn::Error::new(
+                group.span(),
+                format!(
+                    "`{}::seeds` takes {} argument{}, as its `#[pda]` declares, not {}",
+                    path.to_token_stream(),
+                    expected,
+                    if expected == 1 { "" } else { "s" },
+                    values.len()
+                ),
+            ));
+        }
+
+        let mut constants = constant_names.iter();
+        let mut values = values.iter();
+        let seeds = args
+            .seeds
+            .iter()
+            .map(|seed| match seed {
+                Seed::Literal(_) => {
+                    let constant = constants.next().unwrap();
+                    Ok(quote! { #path::#constant })
+                }
+                Seed::Argument { ty, .. } => {
+                    Ok(conversion(&receiver(values.next().unwrap()), ty)?.seed)
+                }
+            })
+            .collect::<syn::Result<Vec<_>>>()?;
+        Ok(Some(quote! { #(#seeds),* }))
+    })?;
+
+    next_seeds(item)
+}
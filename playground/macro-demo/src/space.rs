@@ -0,0 +1,158 @@
+// `#[derive(Space)]`, see its doc in lib.rs
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned, ToTokens};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{Attribute, Data, DeriveInput, Expr, Fields, GenericArgument, PathArguments, Token, Type};
+
+// Borsh size of the fixed-size types, by name
+pub(crate) fn fixed_size(name: &str) -> Option<usize> {
+    match name {
+        "u8" | "i8" | "bool" => Some(1),
+        "u16" | "i16" => Some(2),
+        "u32" | "i32" | "f32" => Some(4),
+        "u64" | "i64" | "f64" => Some(8),
+        "u128" | "i128" => Some(16),
+        "Pubkey" => Some(32),
+        _ => None,
+    }
+}
+
+// The lengths of `#[max_len(a, b, ...)]`, outermost first: `#[max_len(10, 32)]`
+// on a `Vec<String>` is 10 strings of up to 32 bytes
+fn max_lens(attrs: &[Attribute]) -> syn::Result<Vec<Expr>> {
+    let Some(attr) = attrs.iter().find(|attr| attr.path.is_ident("max_len")) else {
+        return Ok(Vec::new());
+    };
+
+    let lens = attr.parse_args_with(Punctuated::<Expr, Token![,]>::parse_terminated)?;
+    if lens.is_empty() {
+        return Err(syn::Error::new_spanned(attr, "expected at least one length, as in `#[max_len(32)]`"));
+    }
+    Ok(lens.into_iter().collect())
+}
+
+// The single generic argument of `Vec<T>`, `Option<T>` or `Box<T>`
+fn inner_type<'a>(ty: &'a Type, arguments: &'a PathArguments) -> syn::Result<&'a Type> {
+    if let PathArguments::AngleBracketed(arguments) = arguments {
+        if let [GenericArgument::Type(inner)] = arguments.args.iter().collect::<Vec<_>>().as_slice() {
+            return Ok(inner);
+        }
+    }
+    Err(syn::Error::new_spanned(ty, "expected a single type argument"))
+}
+
+// Max Borsh size of `ty`, consuming one `max_len` per `String` or `Vec` level
+fn type_space(ty: &Type, lens: &mut std::slice::Iter<Expr>) -> syn::Result<TokenStream> {
+    match ty {
+        Type::Path(path) if path.qself.is_none() => {
+            let segment = path.path.segments.last().unwrap();
+            let name = segment.ident.to_string();
+
+            if let Some(size) = fixed_size(&name) {
+                return Ok(quote! { #size });
+            }
+
+            match name.as_str() {
+                "String" | "Vec" => {
+                    let Some(len) = lens.next() else {
+                        return Err(syn::Error::new_spanned(
+                            ty,
+                            format!("`{}` needs a maximum length, as in `#[max_len(32)]`", name),
+                        ));
+                    };
+                    let item = if name == "String" {
+                        quote! { 1 }
+                    } else {
+                        type_space(inner_type(ty, &segment.arguments)?, lens)?
+                    };
+                    // 4 bytes of length, then the items
+                    Ok(quote! { (4 + (#len) as usize * (#item)) })
+                }
+                "Option" => {
+                    let inner = type_space(inner_type(ty, &segment.arguments)?, lens)?;
+                    Ok(quote! { (1 + #inner) })
+                }
+                "Box" => type_space(inner_type(ty, &segment.arguments)?, lens),
+                "usize" | "isize" => Err(syn::Error::new_spanned(ty, "Borsh does not serialize `usize` and `isize`")),
+                // Any other type must derive `Space` too
+                _ => Ok(quote_spanned! { ty.span()=> <#ty>::INIT_SPACE }),
+            }
+        }
+        Type::Array(array) => {
+            let item = type_space(&array.elem, lens)?;
+            let len = &array.len;
+            Ok(quote! { ((#len) as usize * (#item)) })
+        }
+        Type::Tuple(tuple) => {
+            let items = tuple.elems.iter().map(|elem| type_space(elem, lens)).collect::<syn::Result<Vec<_>>>()?;
+            Ok(quote! { (0 #(+ #items)*) })
+        }
+        Type::Group(group) => type_space(&group.elem, lens),
+        Type::Paren(paren) => type_space(&paren.elem, lens),
+        _ => Err(syn::Error::new_spanned(ty, "`Space` does not support this type")),
+    }
+}
+
+fn field_space(ty: &Type, attrs: &[Attribute]) -> syn::Result<TokenStream> {
+    let lens = max_lens(attrs)?;
+    let mut iter = lens.iter();
+    let space = type_space(ty, &mut iter)?;
+
+    if let Some(extra) = iter.next() {
+        return Err(syn::Error::new_spanned(
+            extra,
+            format!("`{}` has fewer `String` or `Vec` levels than lengths", ty.to_token_stream()),
+        ));
+    }
+    Ok(space)
+}
+
+fn fields_space(fields: &Fields) -> syn::Result<TokenStream> {
+    let spaces = fields
+        .iter()
+        .map(|field| field_space(&field.ty, &field.attrs))
+        .collect::<syn::Result<Vec<_>>>()?;
+    Ok(quote! { (0 #(+ #spaces)*) })
+}
+
+pub fn derive_space(input: TokenStream) -> syn::Result<TokenStream> {
+    let input: DeriveInput = syn::parse2(input)?;
+
+    let space = match &input.data {
+        Data::Struct(data) => fields_space(&data.fields)?,
+        // 1 byte of variant index, then the largest variant
+        Data::Enum(data) => {
+            let variants = data
+                .variants
+                .iter()
+                .map(|variant| fields_space(&variant.fields))
+                .collect::<syn::Result<Vec<_>>>()?;
+            quote! {
+                (1 + {
+                    let mut max = 0;
+                    #(
+                        if #variants > max {
+                            max = #variants;
+                        }
+                    )*
+                    max
+                })
+            }
+        }
+        Data::Union(data) => {
+            return Err(syn::Error::new_spanned(data.union_token, "`Space` cannot be derived for unions"));
+        }
+    };
+
+    let name = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics #name #type_generics #where_clause {
+            // Largest Borsh-serialized size, without the account discriminator
+            pub const INIT_SPACE: usize = #space;
+        }
+    })
+}
+
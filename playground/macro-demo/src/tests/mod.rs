@@ -0,0 +1,101 @@
+// Tests of the expansions themselves, which need the crate's private
+// functions: a proc-macro crate exports nothing else than its macros, so
+// tests/ can only see what the expansions compile to.
+// The helpers come from macro-test-harness, shared by both macro crates.
+//
+//   snapshots()   each tests/expand/*.rs, expanded, against its .expanded.rs
+//   shapes.rs     every macro on every shape of struct
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Attribute, Item};
+
+mod shapes;
+
+// The macros of the crate, by name, as (attribute arguments, item) -> expansion
+fn attribute(name: &str) -> Option<fn(TokenStream, TokenStream) -> syn::Result<TokenStream>> {
+    match name {
+        "foo_bar_attribute" => Some(|_, input| crate::foo_bar(input)),
+        "augment" => Some(crate::augment::augment),
+        "validate" => Some(crate::validate::validate),
+        "validation_errors" => Some(|_, input| crate::validate::validation_errors(input)),
+        "trace" => Some(crate::trace::trace),
+        "pda" => Some(crate::pda::pda),
+        "pda_accounts" => Some(crate::pda::pda_accounts),
+        _ => None,
+    }
+}
+
+// The function-like macros of the crate, by name
+fn function(name: &str) -> Option<fn(TokenStream) -> syn::Result<TokenStream>> {
+    match name {
+        "__pda_accounts_seeds" => Some(crate::pda::pda_accounts_seeds),
+        _ => None,
+    }
+}
+
+fn derive(name: &str) -> Option<fn(TokenStream) -> syn::Result<TokenStream>> {
+    match name {
+        "Space" => Some(crate::space::derive_space),
+        "Accessors" => Some(crate::accessors::derive_accessors),
+        _ => None,
+    }
+}
+
+fn derives(attrs: &[Attribute]) -> Vec<String> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("derive"))
+        .flat_map(|attr| {
+            attr.parse_args_with(syn::punctuated::Punctuated::<syn::Path, syn::Token![,]>::parse_terminated)
+                .unwrap()
+                .into_iter()
+                .map(|path| path.segments.last().unwrap().ident.to_string())
+        })
+        .collect()
+}
+
+fn item_attrs(item: &mut Item) -> Option<&mut Vec<Attribute>> {
+    match item {
+        Item::Struct(item) => Some(&mut item.attrs),
+        Item::Enum(item) => Some(&mut item.attrs),
+        Item::Fn(item) => Some(&mut item.attrs),
+        _ => None,
+    }
+}
+
+// Expands the first macro of the crate found on `item`, as the compiler
+// would: an attribute replaces the item, a derive is appended to it, and so
+// does a function-like macro invoked as an item. Items without any are
+// returned as they are.
+pub fn expand(mut item: Item) -> syn::Result<TokenStream> {
+    if let Item::Macro(invocation) = &item {
+        let name = invocation.mac.path.segments.last().unwrap().ident.to_string();
+        if let Some(expand) = function(&name) {
+            return expand(invocation.mac.tokens.clone());
+        }
+    }
+
+    let Some(attrs) = item_attrs(&mut item) else {
+        return Ok(quote! { #item });
+    };
+
+    if let Some(i) = attrs.iter().position(|attr| attr.path.get_ident().is_some_and(|name| attribute(&name.to_string()).is_some())) {
+        let attr = attrs.remove(i);
+        let args = if attr.tokens.is_empty() { TokenStream::new() } else { attr.parse_args()? };
+        let expand = attribute(&attr.path.get_ident().unwrap().to_string()).unwrap();
+        return expand(args, quote! { #item });
+    }
+
+    let derives = derives(attrs);
+    let mut expanded = quote! { #item };
+    for derive in derives.iter().filter_map(|name| derive(name)) {
+        expanded.extend(derive(quote! { #item })?);
+    }
+    Ok(expanded)
+}
+
+// Each tests/expand/*.rs, expanded, against its .expanded.rs
+#[test]
+fn snapshots() {
+    macro_test_harness::assert_snapshots(&std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/expand"), expand);
+}
@@ -0,0 +1,154 @@
+// Every macro on every shape of struct: named, tuple and unit fields, with
+// or without generics, attributes and visibility. The expansion must keep
+// what it does not change, or fail with an error instead of a panic.
+use macro_test_harness::{shapes, string, Shape};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Fields, File, Item, ItemStruct};
+
+use super::expand;
+
+fn expanded(item: &ItemStruct) -> syn::Result<File> {
+    expand(Item::Struct(item.clone())).map(|tokens| syn::parse2(tokens).expect("the expansion does not parse"))
+}
+
+fn find_struct<'a>(file: &'a File, name: &str) -> &'a ItemStruct {
+    file.items
+        .iter()
+        .find_map(|item| match item {
+            Item::Struct(item) if item.ident == name => Some(item),
+            _ => None,
+        })
+        .unwrap_or_else(|| panic!("no struct `{}` in the expansion", name))
+}
+
+// The original attributes, without the macro's own
+fn original_attrs(item: &ItemStruct, macro_name: &str) -> Vec<String> {
+    item.attrs
+        .iter()
+        .filter(|attr| !attr.path.is_ident(macro_name) && !string(attr).contains(macro_name))
+        .map(string)
+        .collect()
+}
+
+// Name, visibility, generics and attributes are the same
+fn assert_kept(before: &ItemStruct, after: &ItemStruct, macro_name: &str) {
+    let source = string(before);
+    assert_eq!(string(&before.vis), string(&after.vis), "visibility of {}", source);
+    assert_eq!(string(&before.generics), string(&after.generics), "generics of {}", source);
+    assert_eq!(
+        string(&before.generics.where_clause),
+        string(&after.generics.where_clause),
+        "where clause of {}",
+        source
+    );
+    let after_attrs: Vec<String> = after.attrs.iter().map(string).collect();
+    for attr in original_attrs(before, macro_name) {
+        assert!(after_attrs.contains(&attr), "{} lost {}", source, attr);
+    }
+}
+
+// The impl blocks of the expansion are for the struct, with its generics
+fn assert_impls(before: &ItemStruct, file: &File) {
+    let (_, type_generics, _) = before.generics.split_for_impl();
+    let self_ty = string(quote! { Shaped #type_generics });
+    for item in &file.items {
+        if let Item::Impl(item) = item {
+            let ty = string(&item.self_ty);
+            if ty.starts_with("Shaped ") || ty == "Shaped" {
+                assert_eq!(ty, self_ty, "impl of {}", string(before));
+                assert_eq!(string(&item.generics.params), string(&before.generics.params), "impl of {}", string(before));
+            }
+        }
+    }
+}
+
+fn field_names(fields: &Fields) -> Vec<String> {
+    fields.iter().map(|field| string(&field.ident)).collect()
+}
+
+#[test]
+fn foo_bar_attribute() {
+    for (shape, item) in shapes("#[foo_bar_attribute]") {
+        match (shape, expanded(&item)) {
+            (Shape::Tuple, Err(error)) => assert!(error.to_string().contains("tuple structs"), "{}", error),
+            (Shape::Tuple, Ok(_)) => panic!("{} should not expand", string(&item)),
+            (_, Err(error)) => panic!("{} failed: {}", string(&item), error),
+            (_, Ok(file)) => {
+                let after = find_struct(&file, "Shaped");
+                assert_kept(&item, after, "foo_bar_attribute");
+                assert_impls(&item, &file);
+
+                let mut expected = field_names(&item.fields);
+                expected.extend(["foo".to_string(), "bar".to_string()]);
+                assert_eq!(field_names(&after.fields), expected, "fields of {}", string(&item));
+            }
+        }
+    }
+}
+
+#[test]
+fn augment() {
+    for (shape, item) in shapes("#[augment(field(name = \"count\", ty = u64, default = 1), method(fn one(&self) -> u8 { 1 }))]") {
+        match (shape, expanded(&item)) {
+            (Shape::Tuple, Err(error)) => assert!(error.to_string().contains("named fields"), "{}", error),
+            (Shape::Tuple, Ok(_)) => panic!("{} should not expand", string(&item)),
+            (_, Err(error)) => panic!("{} failed: {}", string(&item), error),
+            (_, Ok(file)) => {
+                let after = find_struct(&file, "Shaped");
+                assert_kept(&item, after, "augment");
+                assert_impls(&item, &file);
+                assert_eq!(field_names(&after.fields).last().map(String::as_str), Some("count"));
+            }
+        }
+    }
+}
+
+#[test]
+fn derive_accessors() {
+    for (shape, item) in shapes("#[derive(Accessors)]") {
+        match (shape, expanded(&item)) {
+            (Shape::Named, Ok(file)) => {
+                assert_kept(&item, find_struct(&file, "Shaped"), "Accessors");
+                assert_impls(&item, &file);
+                find_struct(&file, "ShapedBuilder");
+            }
+            (Shape::Named, Err(error)) => panic!("{} failed: {}", string(&item), error),
+            (_, Err(error)) => assert!(error.to_string().contains("named fields"), "{}", error),
+            (_, Ok(_)) => panic!("{} should not expand", string(&item)),
+        }
+    }
+}
+
+#[test]
+fn derive_space() {
+    for (_, item) in shapes("#[derive(Space)]") {
+        match expanded(&item) {
+            Ok(file) => {
+                assert_kept(&item, find_struct(&file, "Shaped"), "Space");
+                assert_impls(&item, &file);
+            }
+            // `Vec<T>` without `#[max_len]`, a reference
+            Err(error) => {
+                let message = error.to_string();
+                assert!(
+                    message.contains("maximum length") || message.contains("does not support"),
+                    "{} failed: {}",
+                    string(&item),
+                    message
+                );
+            }
+        }
+    }
+}
+
+// Not a struct at all
+#[test]
+fn enums() {
+    let item: TokenStream = quote! { enum Shaped { A, B(u8) } };
+    for macro_name in ["foo_bar_attribute", "augment"] {
+        let name = syn::Ident::new(macro_name, proc_macro2::Span::call_site());
+        let item: Item = syn::parse2(quote! { #[#name] #item }).unwrap();
+        assert!(expand(item).is_err(), "#[{}] on an enum", macro_name);
+    }
+}
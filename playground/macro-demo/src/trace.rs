@@ -0,0 +1,113 @@
+// `#[trace]`, see its doc in lib.rs
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{parenthesized, FnArg, Ident, ItemFn, Pat, ReturnType, Token, Type};
+
+// Must be declared by the program using `#[trace]`: `trace = []`
+const FEATURE: &str = "trace";
+
+// `skip(a, b)`, the arguments left out of the entry log
+struct Args {
+    skip: Vec<Ident>,
+}
+
+impl Parse for Args {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.is_empty() {
+            return Ok(Args { skip: Vec::new() });
+        }
+
+        let key: Ident = input.parse()?;
+        if key != "skip" {
+            return Err(syn::Error::new(key.span(), format!("unknown key `{}`, expected `skip(...)`", key)));
+        }
+        let content;
+        parenthesized!(content in input);
+        let skip = Punctuated::<Ident, Token![,]>::parse_terminated(&content)?;
+
+        Ok(Args { skip: skip.into_iter().collect() })
+    }
+}
+
+// `Context<...>` has nothing worth logging, and no `Debug`
+fn is_context(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path.path.segments.last().is_some_and(|segment| segment.ident == "Context"),
+        _ => false,
+    }
+}
+
+pub fn trace(args: TokenStream, input: TokenStream) -> syn::Result<TokenStream> {
+    let args: Args = syn::parse2(args)?;
+    let item: ItemFn = syn::parse2(input)?;
+
+    let ReturnType::Type(_, output) = &item.sig.output else {
+        return Err(syn::Error::new_spanned(&item.sig, "`#[trace]` only applies to handlers returning a `Result`"));
+    };
+
+    // The arguments logged on entry, by name
+    let mut logged = Vec::new();
+    for arg in &item.sig.inputs {
+        let FnArg::Typed(arg) = arg else {
+            return Err(syn::Error::new_spanned(arg, "`#[trace]` does not apply to methods"));
+        };
+        let Pat::Ident(pat) = &*arg.pat else {
+            continue;
+        };
+        if !is_context(&arg.ty) && !args.skip.contains(&pat.ident) {
+            logged.push(pat.ident.clone());
+        }
+    }
+    for skipped in &args.skip {
+        if !item.sig.inputs.iter().any(|arg| matches!(arg, FnArg::Typed(arg) if matches!(&*arg.pat, Pat::Ident(pat) if pat.ident == *skipped))) {
+            return Err(syn::Error::new(skipped.span(), format!("no argument named `{}`", skipped)));
+        }
+    }
+
+    let name = item.sig.ident.to_string();
+    let entry = format!(
+        "-> {}({}), {{}} CU left",
+        name,
+        logged.iter().map(|arg| format!("{}: {{:?}}", arg)).collect::<Vec<_>>().join(", ")
+    );
+    let ok = format!("<- {} ok, {{}} CU left", name);
+    let failed = format!("<- {} failed: {{}} ({{}}), {{}} CU left", name);
+
+    let ItemFn { attrs, vis, sig, block } = &item;
+    let stmts = &block.stmts;
+
+    // Without the feature, the handler is left as it is
+    Ok(quote! {
+        #[cfg(not(feature = #FEATURE))]
+        #item
+
+        #[cfg(feature = #FEATURE)]
+        #(#attrs)*
+        #vis #sig {
+            let remaining = ::anchor_lang::solana_program::compute_units::sol_remaining_compute_units;
+            ::anchor_lang::prelude::msg!(#entry, #(#logged,)* remaining());
+
+            #[allow(clippy::redundant_closure_call)]
+            let result = (move || -> #output { #(#stmts)* })();
+
+            match &result {
+                ::core::result::Result::Ok(_) => ::anchor_lang::prelude::msg!(#ok, remaining()),
+                ::core::result::Result::Err(::anchor_lang::error::Error::AnchorError(error)) => {
+                    ::anchor_lang::prelude::msg!(#failed, error.error_name, error.error_code_number, remaining())
+                }
+                ::core::result::Result::Err(::anchor_lang::error::Error::ProgramError(error)) => {
+                    ::anchor_lang::prelude::msg!(
+                        #failed,
+                        error.program_error,
+                        u64::from(error.program_error.clone()),
+                        remaining()
+                    )
+                }
+            }
+
+            result
+        }
+    })
+}
@@ -0,0 +1,308 @@
+// `#[validate(...)]` and `#[validation_errors]`, see their doc in lib.rs
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{bracketed, parenthesized, Expr, ExprRange, FnArg, Ident, ItemEnum, ItemFn, Pat, Path, RangeLimits, Token, Type};
+
+// Variants added by `#[validation_errors]`, after those of the program so
+// its own codes do not move
+const VALIDATION_ERRORS: &[(&str, &str)] = &[
+    ("ValueOutOfRange", "Value is out of the allowed range"),
+    ("ValueIsZero", "Value must not be zero"),
+    ("StringTooLong", "String is too long"),
+    ("LengthOutOfRange", "Length is out of the allowed range"),
+    ("ValueNotAllowed", "Value is not one of the allowed values"),
+];
+
+enum Constraint {
+    Range(ExprRange), // range = 10..=100
+    Min(Expr),        // min = 10
+    Max(Expr),        // max = 100
+    NonZero,          // non_zero
+    MaxLen(Expr),     // max_len = 32
+    Len(ExprRange),   // len = 1..=8
+    OneOf(Vec<Pat>),  // one_of = [Kind::A, Kind::B]
+}
+
+impl Parse for Constraint {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name: Ident = input.parse()?;
+
+        match name.to_string().as_str() {
+            "non_zero" => Ok(Constraint::NonZero),
+            "range" | "len" => {
+                input.parse::<Token![=]>()?;
+                let range = match input.parse::<Expr>()? {
+                    Expr::Range(range) if range.from.is_some() || range.to.is_some() => range,
+                    other => return Err(syn::Error::new_spanned(other, "expected a range such as `1..=10`")),
+                };
+
+                if name == "range" {
+                    Ok(Constraint::Range(range))
+                } else {
+                    Ok(Constraint::Len(range))
+                }
+            }
+            "min" | "max" => {
+                input.parse::<Token![=]>()?;
+                let bound = input.parse()?;
+
+                if name == "min" {
+                    Ok(Constraint::Min(bound))
+                } else {
+                    Ok(Constraint::Max(bound))
+                }
+            }
+            "max_len" => {
+                input.parse::<Token![=]>()?;
+                Ok(Constraint::MaxLen(input.parse()?))
+            }
+            "one_of" => {
+                input.parse::<Token![=]>()?;
+                let content;
+                bracketed!(content in input);
+                let values = Punctuated::<Pat, Token![,]>::parse_terminated(&content)?;
+                Ok(Constraint::OneOf(values.into_iter().collect()))
+            }
+            _ => Err(syn::Error::new(
+                name.span(),
+                "unknown constraint, expected one of `range`, `min`, `max`, `non_zero`, `max_len`, `len` or `one_of`",
+            )),
+        }
+    }
+}
+
+// A constraint, and the error replacing its default one:
+// `min = 10 @ Day04Error::AisTooSmall`
+struct CheckedConstraint {
+    constraint: Constraint,
+    error:      Option<Path>,
+}
+
+impl Parse for CheckedConstraint {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let constraint = input.parse()?;
+        let error = if input.peek(Token![@]) {
+            input.parse::<Token![@]>()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+
+        Ok(CheckedConstraint { constraint, error })
+    }
+}
+
+// `a(range = 10..=100, non_zero)`
+struct ArgConstraints {
+    arg:         Ident,
+    constraints: Punctuated<CheckedConstraint, Token![,]>,
+}
+
+// `errors = Day04Error`, the error enum to use, `Errors` by default
+enum Item {
+    Errors(Path),
+    Arg(ArgConstraints),
+}
+
+impl Parse for Item {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(Ident) && input.peek2(Token![=]) {
+            let key: Ident = input.parse()?;
+            if key != "errors" {
+                return Err(syn::Error::new(key.span(), "expected `errors = ErrorEnum`"));
+            }
+            input.parse::<Token![=]>()?;
+            return Ok(Item::Errors(input.parse()?));
+        }
+
+        let arg = input.parse()?;
+        let content;
+        parenthesized!(content in input);
+        let constraints = Punctuated::parse_terminated(&content)?;
+        Ok(Item::Arg(ArgConstraints { arg, constraints }))
+    }
+}
+
+struct Args {
+    items: Punctuated<Item, Token![,]>,
+}
+
+impl Parse for Args {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Ok(Args { items: Punctuated::parse_terminated(input)? })
+    }
+}
+
+fn arg_type<'a>(function: &'a ItemFn, name: &Ident) -> Option<&'a Type> {
+    function.sig.inputs.iter().find_map(|input| match input {
+        FnArg::Typed(typed) => match &*typed.pat {
+            Pat::Ident(ident) if ident.ident == *name => Some(&*typed.ty),
+            _ => None,
+        },
+        FnArg::Receiver(_) => None,
+    })
+}
+
+// `value` is within `range`, and the constants exporting its bounds
+fn range_check(value: &TokenStream, range: &ExprRange, ty: &TokenStream, prefix: &str) -> (TokenStream, TokenStream) {
+    let mut constants = Vec::new();
+
+    if let Some(from) = &range.from {
+        let name = format_ident!("{}_MIN", prefix);
+        constants.push(quote! {
+            #[::anchor_lang::prelude::constant]
+            pub const #name: #ty = (#from) as #ty;
+        });
+    }
+
+    if let Some(to) = &range.to {
+        let name = format_ident!("{}_MAX", prefix);
+        // Exported as an inclusive bound, whatever the form of the range
+        let max = match range.limits {
+            RangeLimits::Closed(_) => quote! { #to },
+            RangeLimits::HalfOpen(_) => quote! { (#to) - 1 },
+        };
+        constants.push(quote! {
+            #[::anchor_lang::prelude::constant]
+            pub const #name: #ty = (#max) as #ty;
+        });
+    }
+
+    (quote! { (#range).contains(&#value) }, quote! { #(#constants)* })
+}
+
+pub fn validate(args: TokenStream, input: TokenStream) -> syn::Result<TokenStream> {
+    let args: Args = syn::parse2(args)?;
+    let mut function: ItemFn = syn::parse2(input)?;
+
+    let function_name = function.sig.ident.to_string();
+    let mut errors: Path = syn::parse_quote!(Errors);
+    let mut checks = Vec::new();
+    let mut constants = Vec::new();
+
+    for item in args.items {
+        let arg_constraints = match item {
+            Item::Errors(path) => {
+                errors = path;
+                continue;
+            }
+            Item::Arg(arg_constraints) => arg_constraints,
+        };
+
+        let arg = &arg_constraints.arg;
+        let ty = arg_type(&function, arg)
+            .ok_or_else(|| syn::Error::new(arg.span(), format!("`{}` is not an argument of `{}`", arg, function_name)))?;
+        let prefix = format!("{}_{}", function_name, arg).to_uppercase();
+        let arg_name = arg.to_string();
+
+        for CheckedConstraint { constraint, error: custom_error } in arg_constraints.constraints {
+            let (condition, log, error, constant) = match constraint {
+                Constraint::Range(range) => {
+                    let bounds = quote!(#range).to_string().replace(' ', "");
+                    let (condition, constant) = range_check(&quote!(#arg), &range, &quote!(#ty), &prefix);
+                    let log = quote! {
+                        ::anchor_lang::prelude::msg!("{}: {} = {} is outside of {}", #function_name, #arg_name, #arg, #bounds);
+                    };
+                    (condition, log, "ValueOutOfRange", constant)
+                }
+                Constraint::Min(min) => {
+                    let name = format_ident!("{}_MIN", prefix);
+                    let log = quote! {
+                        ::anchor_lang::prelude::msg!("{}: {} = {} is less than {}", #function_name, #arg_name, #arg, #min);
+                    };
+                    let constant = quote! {
+                        #[::anchor_lang::prelude::constant]
+                        pub const #name: #ty = (#min) as #ty;
+                    };
+                    (quote! { #arg >= (#min) as #ty }, log, "ValueOutOfRange", constant)
+                }
+                Constraint::Max(max) => {
+                    let name = format_ident!("{}_MAX", prefix);
+                    let log = quote! {
+                        ::anchor_lang::prelude::msg!("{}: {} = {} is more than {}", #function_name, #arg_name, #arg, #max);
+                    };
+                    let constant = quote! {
+                        #[::anchor_lang::prelude::constant]
+                        pub const #name: #ty = (#max) as #ty;
+                    };
+                    (quote! { #arg <= (#max) as #ty }, log, "ValueOutOfRange", constant)
+                }
+                Constraint::NonZero => {
+                    let log = quote! {
+                        ::anchor_lang::prelude::msg!("{}: {} must not be zero", #function_name, #arg_name);
+                    };
+                    (quote! { #arg != 0 }, log, "ValueIsZero", quote! {})
+                }
+                Constraint::MaxLen(max_len) => {
+                    let name = format_ident!("{}_MAX_LEN", prefix);
+                    let log = quote! {
+                        ::anchor_lang::prelude::msg!("{}: {} is {} bytes long, more than {}", #function_name, #arg_name, #arg.len(), #max_len);
+                    };
+                    let constant = quote! {
+                        #[::anchor_lang::prelude::constant]
+                        pub const #name: u32 = (#max_len) as u32;
+                    };
+                    (quote! { #arg.len() <= (#max_len) as usize }, log, "StringTooLong", constant)
+                }
+                Constraint::Len(range) => {
+                    let bounds = quote!(#range).to_string().replace(' ', "");
+                    let (condition, constant) = range_check(&quote!(#arg.len()), &range, &quote!(u32), &format!("{}_LEN", prefix));
+                    let log = quote! {
+                        ::anchor_lang::prelude::msg!("{}: {} has {} items, outside of {}", #function_name, #arg_name, #arg.len(), #bounds);
+                    };
+                    (condition, log, "LengthOutOfRange", constant)
+                }
+                Constraint::OneOf(values) => {
+                    let log = quote! {
+                        ::anchor_lang::prelude::msg!("{}: {} is not one of the allowed values", #function_name, #arg_name);
+                    };
+                    (quote! { matches!(#arg, #(#values)|*) }, log, "ValueNotAllowed", quote! {})
+                }
+            };
+
+            let error = match custom_error {
+                Some(path) => quote! { #path },
+                None => {
+                    let variant = Ident::new(error, Span::call_site());
+                    quote! { #errors::#variant }
+                }
+            };
+            checks.push(quote! {
+                if !(#condition) {
+                    #log
+                    return Err(::anchor_lang::prelude::error!(#error).into());
+                }
+            });
+            constants.push(constant);
+        }
+    }
+
+    let body = &function.block;
+    function.block = syn::parse_quote!({
+        #(#checks)*
+        #body
+    });
+
+    Ok(quote! {
+        #(#constants)*
+        #function
+    })
+}
+
+pub fn validation_errors(input: TokenStream) -> syn::Result<TokenStream> {
+    let mut error_enum: ItemEnum = syn::parse2(input)?;
+
+    let variants = VALIDATION_ERRORS.iter().map(|(name, msg)| {
+        let name = Ident::new(name, Span::call_site());
+        let variant: syn::Variant = syn::parse_quote! {
+            #[msg(#msg)]
+            #name
+        };
+        variant
+    });
+    error_enum.variants.extend(variants);
+
+    Ok(quote! { #error_enum })
+}
@@ -0,0 +1,72 @@
+use macro_demo::Accessors;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct Pubkey([u8; 32]);
+
+#[derive(Clone, Debug, Default, PartialEq)]
+enum Policy {
+    #[default]
+    Open,
+    Closed,
+}
+
+#[derive(Accessors, Debug, PartialEq)]
+struct Data {
+    result:    u64,
+    authority: Pubkey,
+    policy:    Policy,
+    name:      String,
+}
+
+#[derive(Accessors)]
+struct Wrapper<T: Clone> {
+    value: T,
+    count: u8,
+}
+
+#[test]
+fn getters() {
+    let data = Data { result: 3, authority: Pubkey([1; 32]), policy: Policy::Closed, name: "bob".to_string() };
+
+    // Fixed-size types by value, the others by reference
+    let result: u64 = data.result();
+    let authority: Pubkey = data.authority();
+    let policy: &Policy = data.policy();
+    let name: &String = data.name();
+
+    assert_eq!(result, 3);
+    assert_eq!(authority, Pubkey([1; 32]));
+    assert_eq!(policy, &Policy::Closed);
+    assert_eq!(name, "bob");
+}
+
+#[test]
+fn setters() {
+    let mut data = Data::builder().build();
+    data.set_result(7);
+    data.set_policy(Policy::Closed);
+    data.set_name("alice".to_string());
+
+    assert_eq!(data.result(), 7);
+    assert_eq!(data.policy(), &Policy::Closed);
+    assert_eq!(data.name(), "alice");
+}
+
+#[test]
+fn builder_defaults_unset_fields() {
+    let data = Data::builder().result(5).name("bob".to_string()).build();
+
+    assert_eq!(
+        data,
+        Data { result: 5, authority: Pubkey::default(), policy: Policy::Open, name: "bob".to_string() }
+    );
+}
+
+#[test]
+fn generic_struct() {
+    let mut wrapper = Wrapper::<Vec<u8>>::builder().value(vec![1, 2]).build();
+    wrapper.set_count(2);
+
+    assert_eq!(wrapper.value(), &vec![1, 2]);
+    assert_eq!(wrapper.count(), 2);
+}
@@ -0,0 +1,82 @@
+#[derive(Accessors)]
+#[accessors(authority = authority, account = data_account)]
+pub struct Data {
+    result: u64,
+    authority: Pubkey,
+    #[accessors(instruction)]
+    call_policy: CallPolicy,
+}
+impl Data {
+    pub fn result(&self) -> u64 {
+        self.result
+    }
+    pub fn set_result(&mut self, value: u64) {
+        self.result = value;
+    }
+    pub fn authority(&self) -> Pubkey {
+        self.authority
+    }
+    pub fn set_authority(&mut self, value: Pubkey) {
+        self.authority = value;
+    }
+    pub fn call_policy(&self) -> &CallPolicy {
+        &self.call_policy
+    }
+    pub fn set_call_policy(&mut self, value: CallPolicy) {
+        self.call_policy = value;
+    }
+}
+pub struct DataBuilder {
+    result: ::core::option::Option<u64>,
+    authority: ::core::option::Option<Pubkey>,
+    call_policy: ::core::option::Option<CallPolicy>,
+}
+impl Data {
+    pub fn builder() -> DataBuilder {
+        DataBuilder {
+            result: ::core::option::Option::None,
+            authority: ::core::option::Option::None,
+            call_policy: ::core::option::Option::None,
+        }
+    }
+}
+impl DataBuilder {
+    pub fn result(mut self, value: u64) -> Self {
+        self.result = ::core::option::Option::Some(value);
+        self
+    }
+    pub fn authority(mut self, value: Pubkey) -> Self {
+        self.authority = ::core::option::Option::Some(value);
+        self
+    }
+    pub fn call_policy(mut self, value: CallPolicy) -> Self {
+        self.call_policy = ::core::option::Option::Some(value);
+        self
+    }
+}
+impl DataBuilder
+where
+    u64: ::core::default::Default,
+    Pubkey: ::core::default::Default,
+    CallPolicy: ::core::default::Default,
+{
+    pub fn build(self) -> Data {
+        Data {
+            result: self.result.unwrap_or_default(),
+            authority: self.authority.unwrap_or_default(),
+            call_policy: self.call_policy.unwrap_or_default(),
+        }
+    }
+}
+#[derive(Accounts)]
+pub struct SetDataCallPolicy<'info> {
+    #[account(mut, has_one = authority)]
+    pub data_account: Account<'info, Data>,
+    pub authority: Signer<'info>,
+}
+impl<'info> SetDataCallPolicy<'info> {
+    pub fn apply(&mut self, value: CallPolicy) -> Result<()> {
+        self.data_account.set_call_policy(value);
+        Ok(())
+    }
+}
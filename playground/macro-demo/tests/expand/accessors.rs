@@ -0,0 +1,8 @@
+#[derive(Accessors)]
+#[accessors(authority = authority, account = data_account)]
+pub struct Data {
+    result:      u64,
+    authority:   Pubkey,
+    #[accessors(instruction)]
+    call_policy: CallPolicy,
+}
@@ -0,0 +1,37 @@
+#[derive(Debug)]
+pub(crate) struct Wrapper<T>
+where
+    T: Default,
+{
+    value: T,
+    count: u64,
+    label: String,
+}
+impl<T> ::core::default::Default for Wrapper<T>
+where
+    T: Default,
+    T: ::core::default::Default,
+    String: ::core::default::Default,
+{
+    fn default() -> Self {
+        Self {
+            value: ::core::default::Default::default(),
+            count: 3,
+            label: ::core::default::Default::default(),
+        }
+    }
+}
+impl<T> Wrapper<T>
+where
+    T: Default,
+{
+    fn double(&self) -> u64 {
+        self.count * 2
+    }
+}
+struct Pair(u8, u8);
+impl Pair {
+    fn first(&self) -> u8 {
+        self.0
+    }
+}
@@ -0,0 +1,12 @@
+#[augment(
+    field(name = "count", ty = u64, default = 3),
+    field(name = "label", ty = String),
+    method(fn double(&self) -> u64 { self.count * 2 }),
+)]
+#[derive(Debug)]
+pub(crate) struct Wrapper<T> where T: Default {
+    value: T,
+}
+
+#[augment(method(fn first(&self) -> u8 { self.0 }))]
+struct Pair(u8, u8);
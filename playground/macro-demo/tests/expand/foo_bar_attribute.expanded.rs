@@ -0,0 +1,61 @@
+#[derive(Debug)]
+struct MyStruct {
+    baz: i32,
+    foo: i32,
+    bar: i32,
+}
+impl ::core::default::Default for MyStruct
+where
+    i32: ::core::default::Default,
+{
+    fn default() -> Self {
+        Self {
+            baz: ::core::default::Default::default(),
+            foo: 10,
+            bar: 20,
+        }
+    }
+}
+impl MyStruct {
+    fn double_foo(&self) -> i32 {
+        self.foo * 2
+    }
+}
+#[derive(Debug)]
+pub struct Generic<T: Clone> {
+    value: T,
+    foo: i32,
+    bar: i32,
+}
+impl<T: Clone> ::core::default::Default for Generic<T>
+where
+    T: ::core::default::Default,
+{
+    fn default() -> Self {
+        Self {
+            value: ::core::default::Default::default(),
+            foo: 10,
+            bar: 20,
+        }
+    }
+}
+impl<T: Clone> Generic<T> {
+    fn double_foo(&self) -> i32 {
+        self.foo * 2
+    }
+}
+#[derive(Debug)]
+struct Unit {
+    foo: i32,
+    bar: i32,
+}
+impl ::core::default::Default for Unit {
+    fn default() -> Self {
+        Self { foo: 10, bar: 20 }
+    }
+}
+impl Unit {
+    fn double_foo(&self) -> i32 {
+        self.foo * 2
+    }
+}
@@ -0,0 +1,12 @@
+#[foo_bar_attribute]
+struct MyStruct {
+    baz: i32,
+}
+
+#[foo_bar_attribute]
+pub struct Generic<T: Clone> {
+    value: T,
+}
+
+#[foo_bar_attribute]
+struct Unit;
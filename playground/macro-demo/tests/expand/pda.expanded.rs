@@ -0,0 +1,159 @@
+pub struct Player {
+    points: u32,
+    authority: Pubkey,
+}
+#[doc(hidden)]
+macro_rules! __pda_seeds_Player {
+    (($($path:tt)*) $($item:tt)*) => {
+        ::macro_demo::__pda_accounts_seeds! { ($($path)*) [seeds = ["player", authority :
+        Pubkey]] $($item)* }
+    };
+}
+#[doc(hidden)]
+#[allow(unused_imports)]
+pub(crate) use __pda_seeds_Player;
+impl Player {
+    pub const SEED: &'static [u8] = b"player";
+    pub fn find_address(authority: &Pubkey) -> (Pubkey, u8) {
+        let seeds = Self::signer_seeds(authority, 0);
+        let slices = seeds.as_slices();
+        Pubkey::find_program_address(&slices[..2usize], &crate::ID)
+    }
+    pub fn create_address(authority: &Pubkey, bump: u8) -> Result<Pubkey> {
+        let seeds = Self::signer_seeds(authority, bump);
+        Pubkey::create_program_address(&seeds.as_slices(), &crate::ID)
+            .map_err(|_| error!(::anchor_lang::error::ErrorCode::ConstraintSeeds))
+    }
+    pub fn signer_seeds(authority: &Pubkey, bump: u8) -> PlayerSeeds {
+        PlayerSeeds {
+            authority: authority.to_bytes(),
+            bump: [bump],
+        }
+    }
+}
+pub struct PlayerSeeds {
+    authority: [u8; 32],
+    bump: [u8; 1],
+}
+impl PlayerSeeds {
+    pub fn as_slices(&self) -> [&[u8]; 3usize] {
+        [Player::SEED, &self.authority[..], &self.bump[..]]
+    }
+}
+struct Vault {
+    amount: u64,
+}
+#[doc(hidden)]
+macro_rules! __pda_seeds_Vault {
+    (($($path:tt)*) $($item:tt)*) => {
+        ::macro_demo::__pda_accounts_seeds! { ($($path)*) [seeds = [b"vault", "v1", id :
+        u64, tag : [u8; 4], name : String]] $($item)* }
+    };
+}
+#[doc(hidden)]
+#[allow(unused_imports)]
+pub(crate) use __pda_seeds_Vault;
+impl Vault {
+    pub const SEED_0: &'static [u8] = b"vault";
+    pub const SEED_1: &'static [u8] = b"v1";
+    pub fn find_address(id: u64, tag: &[u8; 4], name: &str) -> Result<(Pubkey, u8)> {
+        let seeds = Self::signer_seeds(id, tag, name, 0);
+        let slices = seeds.as_slices();
+        Pubkey::try_find_program_address(&slices[..5usize], &crate::ID)
+            .ok_or_else(|| error!(::anchor_lang::error::ErrorCode::ConstraintSeeds))
+    }
+    pub fn create_address(
+        id: u64,
+        tag: &[u8; 4],
+        name: &str,
+        bump: u8,
+    ) -> Result<Pubkey> {
+        let seeds = Self::signer_seeds(id, tag, name, bump);
+        Pubkey::create_program_address(&seeds.as_slices(), &crate::ID)
+            .map_err(|_| error!(::anchor_lang::error::ErrorCode::ConstraintSeeds))
+    }
+    pub fn signer_seeds(id: u64, tag: &[u8; 4], name: &str, bump: u8) -> VaultSeeds {
+        VaultSeeds {
+            id: id.to_le_bytes(),
+            tag: *tag,
+            name: name.as_bytes().to_vec(),
+            bump: [bump],
+        }
+    }
+}
+struct VaultSeeds {
+    id: [u8; ::core::mem::size_of::<u64>()],
+    tag: [u8; 4],
+    name: ::std::vec::Vec<u8>,
+    bump: [u8; 1],
+}
+impl VaultSeeds {
+    pub fn as_slices(&self) -> [&[u8]; 6usize] {
+        [
+            Vault::SEED_0,
+            Vault::SEED_1,
+            &self.id[..],
+            &self.tag[..],
+            &self.name[..],
+            &self.bump[..],
+        ]
+    }
+}
+pub struct Labeled {
+    count: u8,
+}
+const _: () = assert!((LABEL_LEN) <= 32usize, "seed `label` is longer than 32 bytes");
+#[doc(hidden)]
+macro_rules! __pda_seeds_Labeled {
+    (($($path:tt)*) $($item:tt)*) => {
+        ::macro_demo::__pda_accounts_seeds! { ($($path)*) [seeds = [label : [u8;
+        LABEL_LEN]]] $($item)* }
+    };
+}
+#[doc(hidden)]
+#[allow(unused_imports)]
+pub(crate) use __pda_seeds_Labeled;
+impl Labeled {
+    pub fn find_address(label: &[u8; LABEL_LEN]) -> (Pubkey, u8) {
+        let seeds = Self::signer_seeds(label, 0);
+        let slices = seeds.as_slices();
+        Pubkey::find_program_address(&slices[..1usize], &crate::ID)
+    }
+    pub fn create_address(label: &[u8; LABEL_LEN], bump: u8) -> Result<Pubkey> {
+        let seeds = Self::signer_seeds(label, bump);
+        Pubkey::create_program_address(&seeds.as_slices(), &crate::ID)
+            .map_err(|_| error!(::anchor_lang::error::ErrorCode::ConstraintSeeds))
+    }
+    pub fn signer_seeds(label: &[u8; LABEL_LEN], bump: u8) -> LabeledSeeds {
+        LabeledSeeds {
+            label: *label,
+            bump: [bump],
+        }
+    }
+}
+pub struct LabeledSeeds {
+    label: [u8; LABEL_LEN],
+    bump: [u8; 1],
+}
+impl LabeledSeeds {
+    pub fn as_slices(&self) -> [&[u8]; 2usize] {
+        [&self.label[..], &self.bump[..]]
+    }
+}
+__pda_seeds_Vault! {
+    (Vault) #[derive(Accounts)] #[instruction(id : u64, name : String)] pub struct
+    Deposit < 'info > { #[account(init, payer = signer, space = 8 + 8, seeds =
+    [Vault::seeds(id, * b"tag!", name)], bump)] vault : Account < 'info, Vault >,
+    #[account(seeds = [Player::seeds(signer.key())], bump, has_one = signer)] player :
+    Account < 'info, Player >, #[account(mut)] signer : Signer < 'info >, system_program
+    : Program < 'info, System >, }
+}
+__pda_seeds_Player! {
+    (Player) #[derive(Accounts)] #[instruction(id : u64, name : String)] pub struct
+    Deposit < 'info > { #[account(init, payer = signer, space = 8 + 8, seeds =
+    [Vault::SEED_0, Vault::SEED_1, id.to_le_bytes().as_ref(), (* b"tag!").as_ref(), (&
+    name).as_bytes()], bump)] vault : Account < 'info, Vault >, #[account(seeds =
+    [Player::seeds(signer.key())], bump, has_one = signer)] player : Account < 'info,
+    Player >, #[account(mut)] signer : Signer < 'info >, system_program : Program <
+    'info, System >, }
+}
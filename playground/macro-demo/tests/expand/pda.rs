@@ -0,0 +1,45 @@
+#[pda(seeds = ["player", authority: Pubkey])]
+pub struct Player {
+    points:    u32,
+    authority: Pubkey,
+}
+
+#[pda(seeds = [b"vault", "v1", id: u64, tag: [u8; 4], name: String])]
+struct Vault {
+    amount: u64,
+}
+
+#[pda(seeds = [label: [u8; LABEL_LEN]])]
+pub struct Labeled {
+    count: u8,
+}
+
+#[pda_accounts]
+#[derive(Accounts)]
+#[instruction(id: u64, name: String)]
+pub struct Deposit<'info> {
+    #[account(init, payer = signer, space = 8 + 8, seeds = [Vault::seeds(id, *b"tag!", name)], bump)]
+    vault: Account<'info, Vault>,
+    #[account(seeds = [Player::seeds(signer.key())], bump, has_one = signer)]
+    player: Account<'info, Player>,
+    #[account(mut)]
+    signer: Signer<'info>,
+    system_program: Program<'info, System>,
+}
+
+// What `__pda_seeds_Vault!` hands back, with the seeds `#[pda]` declares
+macro_demo::__pda_accounts_seeds! {
+    (Vault)
+    [seeds = [b"vault", "v1", id: u64, tag: [u8; 4], name: String]]
+    #[derive(Accounts)]
+    #[instruction(id: u64, name: String)]
+    pub struct Deposit<'info> {
+        #[account(init, payer = signer, space = 8 + 8, seeds = [Vault::seeds(id, *b"tag!", &name)], bump)]
+        vault: Account<'info, Vault>,
+        #[account(seeds = [Player::seeds(signer.key())], bump, has_one = signer)]
+        player: Account<'info, Player>,
+        #[account(mut)]
+        signer: Signer<'info>,
+        system_program: Program<'info, System>,
+    }
+}
@@ -0,0 +1,36 @@
+#[derive(Space)]
+pub struct Player {
+    points: u32,
+    authority: Pubkey,
+    #[max_len(32)]
+    name: String,
+    #[max_len(10, 16)]
+    tags: Vec<String>,
+    best: Option<u64>,
+}
+impl Player {
+    pub const INIT_SPACE: usize = (0 + 4usize + 32usize + (4 + (32) as usize * (1))
+        + (4 + (10) as usize * ((4 + (16) as usize * (1)))) + (1 + 8usize));
+}
+#[derive(Space)]
+enum Kind {
+    Empty,
+    Points(u64),
+    Named { id: u16, #[max_len(8)] name: String },
+}
+impl Kind {
+    pub const INIT_SPACE: usize = (1
+        + {
+            let mut max = 0;
+            if (0) > max {
+                max = (0);
+            }
+            if (0 + 8usize) > max {
+                max = (0 + 8usize);
+            }
+            if (0 + 2usize + (4 + (8) as usize * (1))) > max {
+                max = (0 + 2usize + (4 + (8) as usize * (1)));
+            }
+            max
+        });
+}
@@ -0,0 +1,17 @@
+#[derive(Space)]
+pub struct Player {
+    points:    u32,
+    authority: Pubkey,
+    #[max_len(32)]
+    name:      String,
+    #[max_len(10, 16)]
+    tags:      Vec<String>,
+    best:      Option<u64>,
+}
+
+#[derive(Space)]
+enum Kind {
+    Empty,
+    Points(u64),
+    Named { id: u16, #[max_len(8)] name: String },
+}
@@ -0,0 +1,33 @@
+#[cfg(not(feature = "trace"))]
+pub fn register(ctx: Context<Register>, name: String, fee: u64) -> Result<()> {
+    require!(fee > 0, Errors::ValueIsZero);
+    Ok(())
+}
+#[cfg(feature = "trace")]
+pub fn register(ctx: Context<Register>, name: String, fee: u64) -> Result<()> {
+    let remaining = ::anchor_lang::solana_program::compute_units::sol_remaining_compute_units;
+    ::anchor_lang::prelude::msg!("-> register(fee: {:?}), {} CU left", fee, remaining());
+    #[allow(clippy::redundant_closure_call)]
+    let result = (move || -> Result<()> {
+        require!(fee > 0, Errors::ValueIsZero);
+        Ok(())
+    })();
+    match &result {
+        ::core::result::Result::Ok(_) => {
+            ::anchor_lang::prelude::msg!("<- register ok, {} CU left", remaining())
+        }
+        ::core::result::Result::Err(::anchor_lang::error::Error::AnchorError(error)) => {
+            ::anchor_lang::prelude::msg!(
+                "<- register failed: {} ({}), {} CU left", error.error_name, error
+                .error_code_number, remaining()
+            )
+        }
+        ::core::result::Result::Err(::anchor_lang::error::Error::ProgramError(error)) => {
+            ::anchor_lang::prelude::msg!(
+                "<- register failed: {} ({}), {} CU left", error.program_error,
+                u64::from(error.program_error.clone()), remaining()
+            )
+        }
+    }
+    result
+}
@@ -0,0 +1,5 @@
+#[trace(skip(name))]
+pub fn register(ctx: Context<Register>, name: String, fee: u64) -> Result<()> {
+    require!(fee > 0, Errors::ValueIsZero);
+    Ok(())
+}
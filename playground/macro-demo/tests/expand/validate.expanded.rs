@@ -0,0 +1,64 @@
+#[::anchor_lang::prelude::constant]
+pub const HANDLER_A_MIN: u64 = (10) as u64;
+#[::anchor_lang::prelude::constant]
+pub const HANDLER_A_MAX: u64 = (100) as u64;
+#[::anchor_lang::prelude::constant]
+pub const HANDLER_B_MIN: u8 = (1) as u8;
+#[::anchor_lang::prelude::constant]
+pub const HANDLER_B_MAX: u8 = (5) as u8;
+#[::anchor_lang::prelude::constant]
+pub const HANDLER_NAME_MAX_LEN: u32 = (32) as u32;
+pub fn handler(
+    ctx: Context<Handler>,
+    a: u64,
+    b: u8,
+    name: String,
+    tier: Tier,
+) -> Result<()> {
+    if !((10..=100).contains(&a)) {
+        ::anchor_lang::prelude::msg!(
+            "{}: {} = {} is outside of {}", "handler", "a", a, "10..=100"
+        );
+        return Err(::anchor_lang::prelude::error!(Errors::ValueOutOfRange).into());
+    }
+    if !(b >= (1) as u8) {
+        ::anchor_lang::prelude::msg!(
+            "{}: {} = {} is less than {}", "handler", "b", b, 1
+        );
+        return Err(::anchor_lang::prelude::error!(Errors::Other).into());
+    }
+    if !(b <= (5) as u8) {
+        ::anchor_lang::prelude::msg!(
+            "{}: {} = {} is more than {}", "handler", "b", b, 5
+        );
+        return Err(::anchor_lang::prelude::error!(Errors::ValueOutOfRange).into());
+    }
+    if !(name.len() <= (32) as usize) {
+        ::anchor_lang::prelude::msg!(
+            "{}: {} is {} bytes long, more than {}", "handler", "name", name.len(), 32
+        );
+        return Err(::anchor_lang::prelude::error!(Errors::StringTooLong).into());
+    }
+    if !(matches!(tier, Tier::Silver | Tier::Gold)) {
+        ::anchor_lang::prelude::msg!(
+            "{}: {} is not one of the allowed values", "handler", "tier"
+        );
+        return Err(::anchor_lang::prelude::error!(Errors::ValueNotAllowed).into());
+    }
+    { Ok(()) }
+}
+#[error_code]
+pub enum Errors {
+    #[msg("Something else")]
+    Other,
+    #[msg("Value is out of the allowed range")]
+    ValueOutOfRange,
+    #[msg("Value must not be zero")]
+    ValueIsZero,
+    #[msg("String is too long")]
+    StringTooLong,
+    #[msg("Length is out of the allowed range")]
+    LengthOutOfRange,
+    #[msg("Value is not one of the allowed values")]
+    ValueNotAllowed,
+}
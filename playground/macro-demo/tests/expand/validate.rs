@@ -0,0 +1,16 @@
+#[validate(
+    a(range = 10..=100),
+    b(min = 1 @ Errors::Other, max = 5),
+    name(max_len = 32),
+    tier(one_of = [Tier::Silver, Tier::Gold]),
+)]
+pub fn handler(ctx: Context<Handler>, a: u64, b: u8, name: String, tier: Tier) -> Result<()> {
+    Ok(())
+}
+
+#[validation_errors]
+#[error_code]
+pub enum Errors {
+    #[msg("Something else")]
+    Other,
+}
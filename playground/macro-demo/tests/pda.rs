@@ -0,0 +1,61 @@
+// The `seeds` constraints `#[pda_accounts]` expands and the addresses
+// `#[pda]` derives agree, for every kind of seed: an account at
+// `find_address` passes Anchor's check, any other fails it.
+// Anchor's derives test features of the program using them, not ours.
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use macro_demo::{pda, pda_accounts};
+use std::collections::BTreeSet;
+
+declare_id!("9QH4YgSpf13KgknT7N4UfNsKyNjXT9YrkU4BgTaW55pL");
+
+#[account]
+#[pda(seeds = ["vault", authority: Pubkey, id: u64, tag: [u8; 4], name: String])]
+pub struct Vault {
+    bump: u8,
+}
+
+#[pda_accounts]
+#[derive(Accounts)]
+#[instruction(id: u64, tag: [u8; 4], name: String)]
+pub struct Check<'info> {
+    #[account(seeds = [Vault::seeds(authority.key(), id, tag, name)], bump = vault.bump)]
+    vault: Account<'info, Vault>,
+    authority: Signer<'info>,
+}
+
+// `Check` on the vault at `address`, with the instruction's arguments
+fn check(address: Pubkey, authority: Pubkey, bump: u8, id: u64, tag: [u8; 4], name: &str) -> Result<()> {
+    let mut vault_data = Vault::discriminator().to_vec();
+    Vault { bump }.serialize(&mut vault_data).unwrap();
+    let (mut vault_lamports, mut authority_lamports) = (1, 1);
+    let mut authority_data = [];
+    let system = anchor_lang::system_program::ID;
+    let accounts = [
+        AccountInfo::new(&address, false, false, &mut vault_lamports, &mut vault_data, &ID, false, 0),
+        AccountInfo::new(&authority, true, false, &mut authority_lamports, &mut authority_data, &system, false, 0),
+    ];
+
+    let mut data = Vec::new();
+    (id, tag, name.to_string()).serialize(&mut data).unwrap();
+    Check::try_accounts(&ID, &mut &accounts[..], &data, &mut CheckBumps::default(), &mut BTreeSet::new())
+        .map(|_| ())
+}
+
+#[test]
+fn constraint_seeds_match_the_declared_seeds() {
+    let authority = Pubkey::new_unique();
+    let (id, tag, name) = (u64::MAX, *b"tag!", "alice");
+    let (address, bump) = Vault::find_address(&authority, id, &tag, name).unwrap();
+
+    assert!(check(address, authority, bump, id, tag, name).is_ok());
+    assert_eq!(Vault::create_address(&authority, id, &tag, name, bump).unwrap(), address);
+
+    let other = Pubkey::new_unique();
+    let (other_address, other_bump) = Vault::find_address(&other, id, &tag, name).unwrap();
+    assert_eq!(
+        check(other_address, authority, other_bump, id, tag, name).unwrap_err(),
+        error!(ErrorCode::ConstraintSeeds)
+    );
+}
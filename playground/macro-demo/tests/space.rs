@@ -0,0 +1,113 @@
+use borsh::BorshSerialize;
+use macro_demo::Space;
+
+// Stands in for `anchor_lang::prelude::Pubkey`, `Space` goes by the name
+#[derive(BorshSerialize, Clone, Copy, Default)]
+struct Pubkey([u8; 32]);
+
+#[derive(BorshSerialize, Space)]
+struct Player {
+    points:    u32,
+    authority: Pubkey,
+}
+
+#[derive(BorshSerialize, Space)]
+enum Kind {
+    Empty,
+    Points(u64),
+    Named { id: u16, #[max_len(8)] name: String },
+}
+
+#[derive(BorshSerialize, Space)]
+struct Profile {
+    #[max_len(32)]
+    name:    String,
+    #[max_len(10, 16)]
+    tags:    Vec<String>,
+    #[max_len(4)]
+    scores:  Vec<u64>,
+    best:    Option<u128>,
+    kind:    Kind,
+    players: [Player; 2],
+    pair:    (bool, i16),
+    flag:    Box<bool>,
+}
+
+// Rust pads `flag` to the alignment of `amount`, Borsh does not
+#[derive(BorshSerialize, Space)]
+struct Padded {
+    flag:   bool,
+    amount: u64,
+}
+
+#[derive(BorshSerialize, Space)]
+struct Unit;
+
+#[derive(BorshSerialize, Space)]
+struct Tuple(u8, #[max_len(3)] Vec<i32>);
+
+// Checked at compile time
+const _: () = assert!(Player::INIT_SPACE == 36);
+const _: () = assert!(Padded::INIT_SPACE == 9 && std::mem::size_of::<Padded>() == 16);
+const _: () = assert!(Kind::INIT_SPACE == 1 + 2 + 4 + 8);
+const _: () = assert!(Unit::INIT_SPACE == 0);
+const _: () = assert!(Tuple::INIT_SPACE == 1 + 4 + 3 * 4);
+
+fn serialized_len<T: BorshSerialize>(value: &T) -> usize {
+    value.try_to_vec().unwrap().len()
+}
+
+fn player() -> Player {
+    Player { points: u32::MAX, authority: Pubkey([1; 32]) }
+}
+
+#[test]
+fn fixed_size_struct_matches_borsh() {
+    assert_eq!(Player::INIT_SPACE, serialized_len(&player()));
+}
+
+#[test]
+fn padding_is_not_counted() {
+    assert_eq!(Padded::INIT_SPACE, serialized_len(&Padded { flag: true, amount: 1 }));
+}
+
+#[test]
+fn largest_variant_matches_borsh() {
+    let largest = Kind::Named { id: 1, name: "x".repeat(8) };
+    assert_eq!(Kind::INIT_SPACE, serialized_len(&largest));
+    assert!(serialized_len(&Kind::Empty) < Kind::INIT_SPACE);
+    assert!(serialized_len(&Kind::Points(7)) < Kind::INIT_SPACE);
+}
+
+#[test]
+fn dynamic_fields_filled_to_their_max_len_match_borsh() {
+    let full = Profile {
+        name:    "n".repeat(32),
+        tags:    vec!["t".repeat(16); 10],
+        scores:  vec![u64::MAX; 4],
+        best:    Some(1),
+        kind:    Kind::Named { id: 1, name: "x".repeat(8) },
+        players: [player(), player()],
+        pair:    (true, -1),
+        flag:    Box::new(true),
+    };
+    assert_eq!(Profile::INIT_SPACE, serialized_len(&full));
+
+    let empty = Profile {
+        name:    String::new(),
+        tags:    Vec::new(),
+        scores:  Vec::new(),
+        best:    None,
+        kind:    Kind::Empty,
+        players: [player(), player()],
+        pair:    (false, 0),
+        flag:    Box::new(false),
+    };
+    assert!(serialized_len(&empty) < Profile::INIT_SPACE);
+}
+
+#[test]
+fn tuple_and_unit_structs_match_borsh() {
+    assert_eq!(Tuple::INIT_SPACE, serialized_len(&Tuple(1, vec![1, 2, 3])));
+    assert_eq!(Unit::INIT_SPACE, serialized_len(&Unit));
+}
@@ -0,0 +1,9 @@
+use macro_demo::Accessors;
+
+#[derive(Accessors)]
+struct Data {
+    #[accessors(instruction)]
+    result: u64,
+}
+
+fn main() {}
@@ -0,0 +1,6 @@
+use macro_demo::Accessors;
+
+#[derive(Accessors)]
+struct Data(u64, bool);
+
+fn main() {}
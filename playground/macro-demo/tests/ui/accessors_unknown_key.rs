@@ -0,0 +1,9 @@
+use macro_demo::Accessors;
+
+#[derive(Accessors)]
+#[accessors(owner = authority)]
+struct Data {
+    authority: u64,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use macro_demo::augment;
+
+// The default is checked against the type by the compiler, at its own span
+#[augment(field(name = "foo", ty = i32, default = "ten"))]
+struct MyStruct {
+    baz: i32,
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use macro_demo::augment;
+
+#[augment(field(name = "foo", ty = i32))]
+enum MyEnum {
+    A,
+}
+
+fn main() {}
@@ -0,0 +1,6 @@
+use macro_demo::augment;
+
+#[augment(field(name = "foo", ty = i32))]
+struct MyStruct(i32);
+
+fn main() {}
@@ -0,0 +1,8 @@
+use macro_demo::augment;
+
+#[augment(field(name = "foo", type = i32))]
+struct MyStruct {
+    baz: i32,
+}
+
+fn main() {}
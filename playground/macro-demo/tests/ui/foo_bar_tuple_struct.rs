@@ -0,0 +1,6 @@
+use macro_demo::foo_bar_attribute;
+
+#[foo_bar_attribute]
+struct Pair(i32, i32);
+
+fn main() {}
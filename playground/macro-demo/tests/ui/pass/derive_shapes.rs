@@ -0,0 +1,41 @@
+// The derives on generic, unit and tuple structs, with attributes and
+// visibility
+use macro_demo::{Accessors, Space};
+
+#[derive(Accessors, Clone)]
+pub struct Generic<T: Clone> {
+    value: T,
+    count: u8,
+}
+
+#[derive(Accessors)]
+pub(crate) struct Borrowed<'a> {
+    name: &'a str,
+}
+
+#[derive(Space, Clone)]
+/// Documented
+pub struct Sizes {
+    #[max_len(4)]
+    items: Vec<u64>,
+    pair:  (u8, u16),
+}
+
+#[derive(Space)]
+struct Unit;
+
+#[derive(Space)]
+struct Tuple(u8, [u32; 2]);
+
+fn main() {
+    let mut generic = Generic::<String>::builder().count(2).build();
+    generic.set_value("x".to_string());
+    assert_eq!((generic.value().as_str(), generic.count()), ("x", 2));
+
+    let borrowed = Borrowed::builder().name("bob").build();
+    assert_eq!(*borrowed.name(), "bob");
+
+    assert_eq!(Sizes::INIT_SPACE, 4 + 4 * 8 + 3);
+    assert_eq!(Unit::INIT_SPACE, 0);
+    assert_eq!(Tuple::INIT_SPACE, 9);
+}
@@ -0,0 +1,43 @@
+// `#[foo_bar_attribute]` keeps the generics, visibility and attributes
+use macro_demo::foo_bar_attribute;
+
+#[foo_bar_attribute]
+struct Named {
+    baz: i32,
+}
+
+#[foo_bar_attribute]
+struct Unit;
+
+#[foo_bar_attribute]
+#[derive(Clone)]
+pub struct Generic<T: Clone> {
+    value: T,
+}
+
+#[foo_bar_attribute]
+pub(crate) struct Borrowed<'a, T>
+where
+    T: Default,
+{
+    value: Option<&'a T>,
+}
+
+#[foo_bar_attribute]
+struct Array<const N: usize> {
+    bytes: [u8; N],
+}
+
+fn main() {
+    assert_eq!(Named::default().double_foo(), 20);
+    assert_eq!(Unit::default().bar, 20);
+
+    let generic = Generic::<String>::default().clone();
+    assert_eq!((generic.value.as_str(), generic.foo), ("", 10));
+
+    let borrowed = Borrowed::<u8>::default();
+    assert!(borrowed.value.is_none());
+    assert_eq!(borrowed.double_foo(), 20);
+
+    assert_eq!(Array::<4> { bytes: [1; 4], foo: 1, bar: 2 }.double_foo(), 2);
+}
@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+use macro_demo::{pda, pda_accounts};
+
+declare_id!("9QH4YgSpf13KgknT7N4UfNsKyNjXT9YrkU4BgTaW55pL");
+
+#[account]
+#[pda(seeds = ["player", authority: Pubkey])]
+pub struct Player {
+    points: u32,
+}
+
+#[pda_accounts]
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(init, payer = signer, space = 8 + 4, seeds = [Player::seeds(signer.key(), 1u8)], bump)]
+    player: Account<'info, Player>,
+    #[account(mut)]
+    signer: Signer<'info>,
+    system_program: Program<'info, System>,
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use macro_demo::pda;
+
+#[pda(seeds = ["a player seed that is longer than 32 bytes", authority: Pubkey])]
+struct Player {
+    points: u32,
+}
+
+#[pda(seeds = ["vault", id: [u8; 33]])]
+struct Vault {
+    amount: u64,
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use macro_demo::pda;
+
+#[pda(seeds = ["player", score: f64])]
+struct Player {
+    score: f64,
+}
+
+fn main() {}
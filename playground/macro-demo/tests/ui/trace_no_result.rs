@@ -0,0 +1,8 @@
+use macro_demo::trace;
+
+#[trace]
+pub fn add(a: u64, b: u64) {
+    let _ = a + b;
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use macro_demo::trace;
+
+#[trace(skip(c))]
+pub fn add(a: u64, b: u64) -> Result<u64, ()> {
+    Ok(a + b)
+}
+
+fn main() {}
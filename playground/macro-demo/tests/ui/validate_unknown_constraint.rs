@@ -0,0 +1,8 @@
+use macro_demo::validate;
+
+#[validate(a(min = 1, above = 10))]
+pub fn handler(a: u64) -> Result<(), ()> {
+    Ok(())
+}
+
+fn main() {}
@@ -0,0 +1,125 @@
+// Test helpers shared by the macro crates (macro-demo, macro-demo-2), as a
+// dev-dependency of each. A proc-macro crate exports nothing else than its
+// macros, so its tests run inside it, in src/tests, with its own `expand`:
+// one item in, its expansion out, as the compiler would do it.
+//
+//   assert_snapshots  each tests/expand/*.rs, expanded, against its .expanded.rs
+//   shapes            a struct of every shape, to put a macro on
+use proc_macro2::TokenStream;
+use quote::ToTokens;
+use std::fs;
+use std::path::Path;
+use syn::{Item, ItemStruct};
+
+// `tokens` as formatted source, to compare and to read
+pub fn pretty(tokens: TokenStream) -> String {
+    let file: syn::File = syn::parse2(tokens).unwrap();
+    prettyplease::unparse(&file)
+}
+
+// `tokens` on one line, for assertions and their messages
+pub fn string(tokens: impl ToTokens) -> String {
+    tokens.to_token_stream().to_string()
+}
+
+// Expansion snapshots: each <dir>/<name>.rs is expanded one item at a time
+// by `expand` and compared against <dir>/<name>.expanded.rs. After changing
+// a macro, review the differences and accept them with
+// `SNAPSHOTS=overwrite cargo test`.
+pub fn assert_snapshots(dir: &Path, expand: impl Fn(Item) -> syn::Result<TokenStream>) {
+    let overwrite = std::env::var("SNAPSHOTS").as_deref() == Ok("overwrite");
+    let mut failures = Vec::new();
+
+    let mut inputs: Vec<_> = fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "rs") && !path.to_string_lossy().ends_with(".expanded.rs"))
+        .collect();
+    inputs.sort();
+    assert!(!inputs.is_empty(), "no snapshot inputs in {}", dir.display());
+
+    for input in inputs {
+        let file = syn::parse_file(&fs::read_to_string(&input).unwrap()).unwrap();
+        let mut tokens = TokenStream::new();
+        for item in file.items {
+            match expand(item) {
+                Ok(expanded) => tokens.extend(expanded),
+                Err(error) => panic!("{}: {}", input.display(), error),
+            }
+        }
+
+        let actual = pretty(tokens);
+        let snapshot = input.with_extension("expanded.rs");
+        match fs::read_to_string(&snapshot) {
+            Ok(expected) if expected == actual => {}
+            _ if overwrite => fs::write(&snapshot, &actual).unwrap(),
+            Ok(expected) => failures.push(format!(
+                "{} changed\n--- expected\n{}\n--- actual\n{}",
+                snapshot.display(),
+                expected,
+                actual
+            )),
+            Err(_) => failures.push(format!("{} is missing", snapshot.display())),
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "{}\n\nIf the changes are intended: SNAPSHOTS=overwrite cargo test",
+        failures.join("\n\n")
+    );
+}
+
+// Generics, and a field type using all of them
+pub const GENERICS: &[(&str, &str)] = &[
+    ("", "u64"),
+    ("<T>", "Vec<T>"),
+    ("<'a, T: Clone>", "&'a T"),
+    ("<const N: usize>", "[u8; N]"),
+    ("<T> where T: Copy", "Option<T>"),
+];
+pub const ATTRIBUTES: &[&str] = &["", "#[derive(Clone)]", "/// Documented\n#[allow(dead_code)]"];
+pub const VISIBILITIES: &[&str] = &["", "pub", "pub(crate)"];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Shape {
+    Named,
+    Tuple,
+    Unit,
+}
+
+// `{attributes} {visibility} struct Shaped{generics} {fields}`, where-clause
+// included
+fn source(shape: Shape, (generics, ty): (&str, &str), attributes: &str, visibility: &str) -> String {
+    let (params, where_clause) = match generics.split_once(" where ") {
+        Some((params, clause)) => (params, format!("where {}", clause)),
+        None => (generics, String::new()),
+    };
+    let fields = match shape {
+        Shape::Named => format!("{} {{ a: u8, b: {} }}", where_clause, ty),
+        Shape::Tuple => format!("(u8, {}) {};", ty, where_clause),
+        Shape::Unit if generics.is_empty() => ";".to_string(),
+        // A generic unit struct does not compile, the parameters must be used
+        Shape::Unit => return String::new(),
+    };
+    format!("{} {} struct Shaped{} {}", attributes, visibility, params, fields)
+}
+
+// `Shaped`, named, tuple and unit, with or without generics, attributes and
+// visibility, each with the macro attribute or derive `with` added
+pub fn shapes(with: &str) -> Vec<(Shape, ItemStruct)> {
+    let mut all = Vec::new();
+    for shape in [Shape::Named, Shape::Tuple, Shape::Unit] {
+        for generics in GENERICS {
+            for attributes in ATTRIBUTES {
+                for visibility in VISIBILITIES {
+                    let source = source(shape, *generics, attributes, visibility);
+                    if !source.is_empty() {
+                        all.push((shape, syn::parse_str(&format!("{}\n{}", with, source)).unwrap()));
+                    }
+                }
+            }
+        }
+    }
+    all
+}
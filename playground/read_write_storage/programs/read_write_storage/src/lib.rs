@@ -1,53 +1,89 @@
 use anchor_lang::prelude::*;
+use macro_demo::Accessors;
+use macro_demo::trace;
 use std::mem::size_of;
 
+pub mod pause;
+
+use pause::*;
+
 declare_id!("6AHTMuew9aNk4RWEaYNJ18mBEeVrrDaiwwpcGgLqui3o");
 
 #[program]
 pub mod read_write_storage {
     use super::*;
 
+    #[trace]
     pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
         Ok(())
     }
 
-    // pub fn set(ctx: Context<Set>, new_x: u64) -> Result<()> {
-    //     ctx.accounts.my_storage.x = new_x;
-    //     Ok(())
-    // }
-
+    #[trace]
     pub fn set(ctx: Context<Set>, new_x: u64) -> Result<()> {
-        let my_storage = &mut ctx.accounts.my_storage;
-        my_storage.x = new_x;
-    
+        ctx.accounts.pause_state.require_not_paused(Scope::Set)?;
+
+        ctx.accounts.my_storage.set_x(new_x);
         Ok(())
     }
 
+    #[trace]
     pub fn inc_x(ctx: Context<IncX>) -> Result<()> {
+        ctx.accounts.pause_state.require_not_paused(Scope::IncX)?;
+
         let my_storage = &mut ctx.accounts.my_storage;
-        my_storage.x = my_storage.x + 1;
-        msg!("The value of x+1 is {}", my_storage.x);
+        let x = my_storage.x() + 1;
+        my_storage.set_x(x);
+        msg!("The value of x+1 is {}", my_storage.x());
         Ok(())
     }
 
+    #[trace]
     pub fn print_x(ctx: Context<PrintX>) -> Result<()> {
-        let x = ctx.accounts.my_storage.x;
+        let x = ctx.accounts.my_storage.x();
         msg!("The value of x is {}", x);
         Ok(())
     }
 
+    // PAUSE
+    // -----
+
+    #[trace]
+    pub fn initialize_pause_state(ctx: Context<InitializePauseState>) -> Result<()> {
+        ctx.accounts.initialize(ctx.bumps.pause_state)
+    }
+
+    #[trace]
+    pub fn pause(ctx: Context<SetPause>, scope: Scope) -> Result<()> {
+        ctx.accounts.pause(scope)
+    }
+
+    #[trace]
+    pub fn unpause(ctx: Context<SetPause>, scope: Scope) -> Result<()> {
+        ctx.accounts.unpause(scope)
+    }
+
+    #[trace]
+    pub fn set_pauser(ctx: Context<SetPause>, new_pauser: Pubkey) -> Result<()> {
+        ctx.accounts.set_pauser(new_pauser)
+    }
 }
 
 #[derive(Accounts)]
 pub struct Set<'info> {
     #[account(mut, seeds = [], bump)]
     pub my_storage: Account<'info, MyStorage>,
+
+    #[account(seeds = [PauseState::SEED], bump = pause_state.bump)]
+    pub pause_state: Account<'info, PauseState>,
 }
 
 #[derive(Accounts)]
 pub struct IncX<'info> {
     #[account(mut, seeds = [], bump)]
     pub my_storage: Account<'info, MyStorage>,
+
+    #[account(seeds = [PauseState::SEED], bump = pause_state.bump)]
+    pub pause_state: Account<'info, PauseState>,
 }
 
 #[derive(Accounts)]
@@ -71,6 +107,15 @@ pub struct Initialize<'info> {
 }
 
 #[account]
+#[derive(Accessors)]
 pub struct MyStorage {
     x: u64,
 }
+
+#[error_code]
+pub enum Errors {
+    #[msg("This instruction is paused")]
+    Paused,
+    #[msg("Signer is not the program's upgrade authority")]
+    NotUpgradeAuthority,
+}
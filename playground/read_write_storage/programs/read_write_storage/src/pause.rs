@@ -0,0 +1,12 @@
+// Scopes of the circuit breaker, see the circuit-breaker crate. A paused
+// instruction fails with `Errors::Paused`.
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Scope {
+    All,  // every scope below
+    Set,  // set
+    IncX, // inc_x
+}
+
+circuit_breaker::pause_state!(program = ReadWriteStorage, scope = Scope { All, Set, IncX });
@@ -1,26 +1,93 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { ReadWriteStorage } from "../target/types/read_write_storage";
+import { expect } from "chai";
 
 describe("read_write_storage", () => {
   anchor.setProvider(anchor.AnchorProvider.env());
 
   const program = anchor.workspace.ReadWriteStorage as Program<ReadWriteStorage>;
 
-  it("Is initialized!", async () => {
-    const seeds = []
-    const [myStorage, _bump] = anchor.web3.PublicKey.findProgramAddressSync(seeds, program.programId);
+  const [myStorage] = anchor.web3.PublicKey.findProgramAddressSync([], program.programId);
+  const [pauseState] = anchor.web3.PublicKey.findProgramAddressSync([Buffer.from("pause_state")], program.programId);
+  // `anchor test` deploys with the upgradeable loader (`[test] upgradeable`
+  // in Anchor.toml), which writes this account with the wallet as upgrade
+  // authority, the only one allowed to initialize the pause state
+  const [programData] = anchor.web3.PublicKey.findProgramAddressSync(
+    [program.programId.toBuffer()],
+    new anchor.web3.PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+  );
+
+  const expectError = async (promise: Promise<unknown>, code: string) => {
+    try {
+      await promise;
+      expect.fail(`should have failed with ${code}`);
+    } catch (e) {
+      expect(e.toString()).to.contain(code);
+    }
+  };
+
+  const pauser = program.provider.publicKey;
+  const pause = (scope: any) => program.methods.pause(scope).accounts({ pauseState, pauser }).rpc();
+  const unpause = (scope: any) => program.methods.unpause(scope).accounts({ pauseState, pauser }).rpc();
+  const set = (x: number) => program.methods.set(new anchor.BN(x)).accounts({ myStorage, pauseState }).rpc();
+  const incX = () => program.methods.incX().accounts({ myStorage, pauseState }).rpc();
 
+  it("Is initialized!", async () => {
     console.log("the storage account address is", myStorage.toBase58());
 
     await program.methods.initialize().accounts({ myStorage: myStorage }).rpc();
     //await program.methods.initialize().accounts({ myStorage: myStorage }).rpc();
-    await program.methods.set(
-      new anchor.BN(777)
-    ).accounts({ myStorage: myStorage }).rpc();
+    await program.methods
+      .initializePauseState()
+      .accounts({ pauseState, program: program.programId, programData })
+      .rpc();
 
-    await program.methods.incX().accounts({myStorage: myStorage}).rpc();
+    await set(777);
+    await incX();
     await program.methods.printX().accounts({myStorage: myStorage}).rpc();
+  });
+
+  it("Pauses a single instruction", async () => {
+    await pause({ set: {} });
+    await expectError(set(1), "Paused");
+    await incX();
+
+    await unpause({ set: {} });
+    await set(1);
+  });
+
+  it("Pauses everything", async () => {
+    await pause({ all: {} });
+    await expectError(set(2), "Paused");
+    await expectError(incX(), "Paused");
+
+    // Reading is still allowed
+    await program.methods.printX().accounts({ myStorage }).rpc();
+
+    await unpause({ all: {} });
+    await incX();
+  });
+
+  it("Only lets the pauser pause", async () => {
+    const stranger = anchor.web3.Keypair.generate();
+
+    await expectError(
+      program.methods.pause({ all: {} }).accounts({ pauseState, pauser: stranger.publicKey }).signers([stranger]).rpc(),
+      "ConstraintHasOne"
+    );
+  });
+
+  it("Emits events", async () => {
+    const events = [];
+    const listener = program.addEventListener("PausedEvent", (event) => events.push(event));
+
+    await pause({ incX: {} });
+    await unpause({ incX: {} });
+    await new Promise((resolve) => setTimeout(resolve, 1000));
+    await program.removeEventListener(listener);
 
+    expect(events).to.have.lengthOf(1);
+    expect(events[0].scope).to.deep.equal({ incX: {} });
   });
 });
@@ -0,0 +1,204 @@
+// Integer-only proleptic Gregorian calendar, in UTC.
+//
+// Only uses `core`, no allocation and no floating point, so it is cheap on
+// SBF and does not pull a date library into the program. Day <-> date
+// conversions follow Howard Hinnant's `days_from_civil` / `civil_from_days`
+// algorithms (http://howardhinnant.github.io/date_algorithms.html).
+//
+// Supported dates are the same as chrono's `NaiveDate` (years -262143 to
+// 262142), which the tests of this crate use as an oracle.
+use core::fmt;
+
+pub const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+pub const MIN_YEAR: i32 = -262_143;
+pub const MAX_YEAR: i32 = 262_142;
+
+// First and last second of the supported range:
+// -262143-01-01T00:00:00 and +262142-12-31T23:59:59
+pub const MIN_TIMESTAMP: i64 = -8_334_601_228_800;
+pub const MAX_TIMESTAMP: i64 = 8_210_266_876_799;
+
+// Days between 0000-03-01 and 1970-01-01
+const DAYS_TO_UNIX_EPOCH: i64 = 719_468;
+const DAYS_PER_ERA: i64 = 146_097; // 400 years
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CalendarError {
+    TimestampOutOfRange, // before MIN_TIMESTAMP or after MAX_TIMESTAMP
+    InvalidDate,         // year out of range, month not in 1..=12 or day not in the month
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Weekday {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+impl Weekday {
+    // 0 = Monday ... 6 = Sunday
+    pub fn from_monday(n: u8) -> Weekday {
+        match n % 7 {
+            0 => Weekday::Monday,
+            1 => Weekday::Tuesday,
+            2 => Weekday::Wednesday,
+            3 => Weekday::Thursday,
+            4 => Weekday::Friday,
+            5 => Weekday::Saturday,
+            _ => Weekday::Sunday,
+        }
+    }
+
+    pub fn number_from_monday(self) -> u8 {
+        self as u8 + 1
+    }
+}
+
+// Same short names as chrono, so logs are unchanged
+impl fmt::Display for Weekday {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Weekday::Monday => "Mon",
+            Weekday::Tuesday => "Tue",
+            Weekday::Wednesday => "Wed",
+            Weekday::Thursday => "Thu",
+            Weekday::Friday => "Fri",
+            Weekday::Saturday => "Sat",
+            Weekday::Sunday => "Sun",
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Date {
+    pub year:  i32,
+    pub month: u8, // 1..=12
+    pub day:   u8, // 1..=31
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IsoWeek {
+    pub year: i32, // can differ from the calendar year around January 1st
+    pub week: u8,  // 1..=53
+}
+
+impl Date {
+    pub fn new(year: i32, month: u8, day: u8) -> Result<Date, CalendarError> {
+        if !(MIN_YEAR..=MAX_YEAR).contains(&year)
+            || !(1..=12).contains(&month)
+            || day == 0
+            || day > days_in_month(year, month)
+        {
+            return Err(CalendarError::InvalidDate);
+        }
+
+        Ok(Date { year, month, day })
+    }
+
+    pub fn from_timestamp(unix_timestamp: i64) -> Result<Date, CalendarError> {
+        if !(MIN_TIMESTAMP..=MAX_TIMESTAMP).contains(&unix_timestamp) {
+            return Err(CalendarError::TimestampOutOfRange);
+        }
+
+        Ok(Date::from_days(unix_timestamp.div_euclid(SECONDS_PER_DAY)))
+    }
+
+    // Days since 1970-01-01, which must be in the supported range
+    fn from_days(days: i64) -> Date {
+        let z = days + DAYS_TO_UNIX_EPOCH;
+        let era = z.div_euclid(DAYS_PER_ERA);
+        let doe = z - era * DAYS_PER_ERA; // day of era, [0, 146096]
+        let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365; // year of era, [0, 399]
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100); // day of year from March 1st, [0, 365]
+        let mp = (5 * doy + 2) / 153; // month from March, [0, 11]
+
+        let day = doy - (153 * mp + 2) / 5 + 1;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = yoe + era * 400 + (month <= 2) as i64;
+
+        Date { year: year as i32, month: month as u8, day: day as u8 }
+    }
+
+    // Days since 1970-01-01
+    pub fn days_since_epoch(&self) -> i64 {
+        let month = self.month as i64;
+        let year = self.year as i64 - (month <= 2) as i64;
+
+        let era = year.div_euclid(400);
+        let yoe = year - era * 400;
+        let doy = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + self.day as i64 - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+
+        era * DAYS_PER_ERA + doe - DAYS_TO_UNIX_EPOCH
+    }
+
+    pub fn weekday(&self) -> Weekday {
+        weekday_from_days(self.days_since_epoch())
+    }
+
+    // 1 for January 1st
+    pub fn ordinal(&self) -> u16 {
+        let january_first = Date { year: self.year, month: 1, day: 1 };
+        (self.days_since_epoch() - january_first.days_since_epoch() + 1) as u16
+    }
+
+    pub fn is_leap_year(&self) -> bool {
+        is_leap_year(self.year)
+    }
+
+    // ISO 8601 week: weeks start on Monday and week 1 is the one holding the
+    // year's first Thursday
+    pub fn iso_week(&self) -> IsoWeek {
+        let weekday = self.weekday().number_from_monday() as i32;
+        let week = (self.ordinal() as i32 - weekday + 10) / 7;
+
+        if week < 1 {
+            IsoWeek { year: self.year - 1, week: iso_weeks_in_year(self.year - 1) }
+        } else if week > iso_weeks_in_year(self.year) as i32 {
+            IsoWeek { year: self.year + 1, week: 1 }
+        } else {
+            IsoWeek { year: self.year, week: week as u8 }
+        }
+    }
+}
+
+pub fn is_leap_year(year: i32) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+pub fn days_in_month(year: i32, month: u8) -> u8 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+// 52, or 53 when the year starts on a Thursday (or on a Wednesday for a
+// leap year)
+pub fn iso_weeks_in_year(year: i32) -> u8 {
+    let january_first = weekday_from_days(Date { year, month: 1, day: 1 }.days_since_epoch());
+
+    match january_first {
+        Weekday::Thursday => 53,
+        Weekday::Wednesday if is_leap_year(year) => 53,
+        _ => 52,
+    }
+}
+
+// Weekday of a day counted from 1970-01-01, which was a Thursday.
+// Works for any i64, not only the supported range.
+pub fn weekday_from_days(days: i64) -> Weekday {
+    Weekday::from_monday((days.rem_euclid(7) as u8 + 3) % 7)
+}
+
+// Weekday of a unix timestamp, for any i64
+pub fn weekday(unix_timestamp: i64) -> Weekday {
+    weekday_from_days(unix_timestamp.div_euclid(SECONDS_PER_DAY))
+}
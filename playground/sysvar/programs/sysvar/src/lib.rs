@@ -1,11 +1,17 @@
 use anchor_lang::prelude::*;
+use std::mem::size_of;
+
+pub mod calendar;
+pub mod schedule;
+
+use calendar::{CalendarError, Date};
+use schedule::{Unlock, Vesting, Window};
 
 declare_id!("5R2J9VX6beEckkhckVe4PxwmsXbMjuiVP72dKaey6qU");
 
 #[program]
 pub mod sysvar {
     use super::*;
-    use chrono::*;
 
     pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
         let clock: Clock = Clock::get()?;
@@ -24,16 +30,193 @@ pub mod sysvar {
         let clock = Clock::get()?;
         let time_stamp = clock.unix_timestamp; // current timestamp
 
-        let date_time = chrono::NaiveDateTime::from_timestamp_opt(time_stamp, 0).unwrap();
-        let day_of_the_week = date_time.weekday();
+        let date = Date::from_timestamp(time_stamp)?;
+        let day_of_the_week = date.weekday();
     
         msg!("Week day is: {}", day_of_the_week);
 
         Ok(())
     }
 
+    // Current UTC date, as return data
+    pub fn get_date(_ctx: Context<Initialize>) -> Result<CalendarDate> {
+        let clock = Clock::get()?;
+        CalendarDate::from_timestamp(clock.unix_timestamp)
+    }
+
+    // TIME LOCK
+    // ---------
+
+    pub fn create_time_lock(ctx: Context<CreateTimeLock>, unlock: Unlock) -> Result<()> {
+        let time_lock = &mut ctx.accounts.time_lock;
+        time_lock.authority = ctx.accounts.authority.key();
+        time_lock.unlock    = unlock;
+        Ok(())
+    }
+
+    // Closes the time lock (its lamports go back to the authority) once open
+    pub fn release_time_lock(ctx: Context<ReleaseTimeLock>) -> Result<()> {
+        let clock = Clock::get()?;
+        require!(ctx.accounts.time_lock.unlock.is_unlocked(&clock), Errors::StillLocked);
+
+        msg!("Time lock released at {}", clock.unix_timestamp);
+        Ok(())
+    }
+
+    // RECURRING WINDOW
+    // ----------------
+
+    pub fn check_window(_ctx: Context<CheckWindow>, window: Window) -> Result<()> {
+        require!(window.is_valid(), Errors::InvalidWindow);
+
+        let clock = Clock::get()?;
+        require!(window.contains(&clock), Errors::OutsideWindow);
+
+        msg!("Inside window at {}", clock.unix_timestamp);
+        Ok(())
+    }
+
+    // VESTING
+    // -------
+
+    pub fn create_vesting(ctx: Context<CreateVesting>, vesting: Vesting) -> Result<()> {
+        require!(vesting.is_valid(), Errors::InvalidVesting);
+
+        let vesting_account = &mut ctx.accounts.vesting;
+        vesting_account.authority = ctx.accounts.authority.key();
+        vesting_account.vesting   = vesting;
+        vesting_account.claimed   = 0;
+        Ok(())
+    }
+
+    // Returns the amount vested since the last claim
+    pub fn claim_vesting(ctx: Context<ClaimVesting>) -> Result<u64> {
+        let clock = Clock::get()?;
+        let vesting_account = &mut ctx.accounts.vesting;
+
+        let claimable = vesting_account.vesting.vested_amount(&clock).saturating_sub(vesting_account.claimed);
+        require!(claimable > 0, Errors::NothingToClaim);
+
+        vesting_account.claimed += claimable;
+        msg!("Claimed {}, {} of {} in total", claimable, vesting_account.claimed, vesting_account.vesting.total);
+        Ok(claimable)
+    }
 }
 
 
 #[derive(Accounts)]
 pub struct Initialize {}
+
+#[derive(Accounts)]
+pub struct CheckWindow {}
+
+#[derive(Accounts)]
+pub struct CreateTimeLock<'info> {
+    #[account(init, payer = authority, space = size_of::<TimeLock>() + 8)]
+    pub time_lock: Account<'info, TimeLock>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ReleaseTimeLock<'info> {
+    #[account(mut, has_one = authority, close = authority)]
+    pub time_lock: Account<'info, TimeLock>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CreateVesting<'info> {
+    #[account(init, payer = authority, space = size_of::<VestingAccount>() + 8)]
+    pub vesting: Account<'info, VestingAccount>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimVesting<'info> {
+    #[account(mut, has_one = authority)]
+    pub vesting: Account<'info, VestingAccount>,
+
+    pub authority: Signer<'info>,
+}
+
+#[account]
+pub struct TimeLock {
+    pub authority: Pubkey,
+    pub unlock:    Unlock,
+}
+
+#[account]
+pub struct VestingAccount {
+    pub authority: Pubkey,
+    pub vesting:   Vesting,
+    pub claimed:   u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CalendarDate {
+    pub unix_timestamp: i64, // the Clock's, that the date is of
+    pub year:           i32,
+    pub month:          u8,
+    pub day:            u8,
+    pub weekday:        u8, // 1 = Monday ... 7 = Sunday
+    pub ordinal:        u16,
+    pub iso_year:       i32,
+    pub iso_week:       u8,
+    pub is_leap_year:   bool,
+}
+
+impl CalendarDate {
+    pub fn from_timestamp(unix_timestamp: i64) -> Result<Self> {
+        let date = Date::from_timestamp(unix_timestamp)?;
+        let iso_week = date.iso_week();
+
+        Ok(Self {
+            unix_timestamp,
+            year:         date.year,
+            month:        date.month,
+            day:          date.day,
+            weekday:      date.weekday().number_from_monday(),
+            ordinal:      date.ordinal(),
+            iso_year:     iso_week.year,
+            iso_week:     iso_week.week,
+            is_leap_year: date.is_leap_year(),
+        })
+    }
+}
+
+impl From<CalendarError> for Error {
+    fn from(error: CalendarError) -> Self {
+        match error {
+            CalendarError::TimestampOutOfRange => error!(Errors::TimestampOutOfRange),
+            CalendarError::InvalidDate => error!(Errors::InvalidDate),
+        }
+    }
+}
+
+#[error_code]
+pub enum Errors {
+    #[msg("Time lock is still locked")]
+    StillLocked,
+    #[msg("Window must have known days and start before its end, within a day")]
+    InvalidWindow,
+    #[msg("Outside of the allowed window")]
+    OutsideWindow,
+    #[msg("Vesting must have a positive duration and a cliff within it")]
+    InvalidVesting,
+    #[msg("Nothing vested since the last claim")]
+    NothingToClaim,
+    #[msg("Timestamp is outside of the supported calendar range")]
+    TimestampOutOfRange,
+    #[msg("Invalid calendar date")]
+    InvalidDate,
+}
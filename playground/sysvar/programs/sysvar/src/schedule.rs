@@ -0,0 +1,92 @@
+// Time-based scheduling primitives: time locks, recurring windows and
+// vesting curves.
+//
+// Nothing here reads the Clock sysvar itself, every check takes a `&Clock`.
+// Instructions pass `Clock::get()?`, while off-chain code (or a test) can
+// build any point in time:
+//
+//     let clock = Clock { unix_timestamp: 1_700_000_000, ..Clock::default() };
+//     assert!(Unlock::Timestamp(1_600_000_000).is_unlocked(&clock));
+use anchor_lang::prelude::*;
+
+use crate::calendar;
+pub use crate::calendar::SECONDS_PER_DAY;
+
+// Day bits of `Window::days`, Monday first
+pub const MONDAY: u8    = 1 << 0;
+pub const TUESDAY: u8   = 1 << 1;
+pub const WEDNESDAY: u8 = 1 << 2;
+pub const THURSDAY: u8  = 1 << 3;
+pub const FRIDAY: u8    = 1 << 4;
+pub const SATURDAY: u8  = 1 << 5;
+pub const SUNDAY: u8    = 1 << 6;
+pub const WEEKDAYS: u8  = MONDAY | TUESDAY | WEDNESDAY | THURSDAY | FRIDAY;
+pub const EVERY_DAY: u8 = WEEKDAYS | SATURDAY | SUNDAY;
+
+// When a time lock opens
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Unlock {
+    Timestamp(i64), // unix timestamp, in seconds
+    Slot(u64),
+}
+
+impl Unlock {
+    pub fn is_unlocked(&self, clock: &Clock) -> bool {
+        match *self {
+            Unlock::Timestamp(timestamp) => clock.unix_timestamp >= timestamp,
+            Unlock::Slot(slot) => clock.slot >= slot,
+        }
+    }
+}
+
+// A window repeating every week, e.g. weekdays from 09:00 to 17:00 UTC:
+//     Window { days: WEEKDAYS, start: 9 * 3600, end: 17 * 3600 }
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Window {
+    pub days:  u8,  // MONDAY | TUESDAY | ... bits
+    pub start: u32, // seconds since midnight UTC, included
+    pub end:   u32, // seconds since midnight UTC, excluded
+}
+
+impl Window {
+    pub fn is_valid(&self) -> bool {
+        self.days & !EVERY_DAY == 0 && self.start < self.end && self.end as i64 <= SECONDS_PER_DAY
+    }
+
+    pub fn contains(&self, clock: &Clock) -> bool {
+        let second_of_day = clock.unix_timestamp.rem_euclid(SECONDS_PER_DAY) as u32;
+        let day = 1 << (calendar::weekday(clock.unix_timestamp) as u8);
+
+        self.days & day != 0 && self.start <= second_of_day && second_of_day < self.end
+    }
+}
+
+// Nothing vests before `start + cliff`, then `total` vests linearly from
+// `start` to `start + duration`: at the cliff, the part accrued since `start`
+// is released at once.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Vesting {
+    pub start:    i64, // unix timestamp, in seconds
+    pub cliff:    i64, // seconds after `start`
+    pub duration: i64, // seconds after `start`
+    pub total:    u64,
+}
+
+impl Vesting {
+    pub fn is_valid(&self) -> bool {
+        self.cliff >= 0 && self.duration > 0 && self.cliff <= self.duration
+    }
+
+    pub fn vested_amount(&self, clock: &Clock) -> u64 {
+        let elapsed = clock.unix_timestamp.saturating_sub(self.start);
+
+        if elapsed < self.cliff || elapsed <= 0 {
+            0
+        } else if elapsed >= self.duration {
+            self.total
+        } else {
+            // elapsed < duration, so the result is below `total`
+            (self.total as u128 * elapsed as u128 / self.duration as u128) as u64
+        }
+    }
+}
@@ -0,0 +1,148 @@
+// Host-side checks of the calendar module, with chrono as the oracle
+use chrono::{Datelike, NaiveDate, NaiveDateTime};
+use sysvar::calendar::{self, CalendarError, Date, MAX_TIMESTAMP, MIN_TIMESTAMP, SECONDS_PER_DAY};
+
+fn assert_same_date(timestamp: i64, date: Date, expected: NaiveDate) {
+    assert_eq!(date.year, expected.year(), "year of {}", timestamp);
+    assert_eq!(date.month as u32, expected.month(), "month of {}", timestamp);
+    assert_eq!(date.day as u32, expected.day(), "day of {}", timestamp);
+    assert_eq!(date.ordinal() as u32, expected.ordinal(), "ordinal of {}", timestamp);
+    assert_eq!(date.weekday().number_from_monday() as u32, expected.weekday().number_from_monday(), "weekday of {}", timestamp);
+    assert_eq!(date.weekday().to_string(), expected.weekday().to_string(), "weekday name of {}", timestamp);
+    assert_eq!(date.is_leap_year(), expected.leap_year(), "leap year of {}", timestamp);
+
+    let iso_week = date.iso_week();
+    assert_eq!(iso_week.year, expected.iso_week().year(), "ISO year of {}", timestamp);
+    assert_eq!(iso_week.week as u32, expected.iso_week().week(), "ISO week of {}", timestamp);
+}
+
+fn check_timestamp(timestamp: i64) {
+    let expected = NaiveDateTime::from_timestamp_opt(timestamp, 0).map(|date_time| date_time.date());
+
+    match (Date::from_timestamp(timestamp), expected) {
+        (Ok(date), Some(expected)) => {
+            assert_same_date(timestamp, date, expected);
+            assert_eq!(calendar::weekday(timestamp), date.weekday());
+            assert_eq!(date.days_since_epoch(), timestamp.div_euclid(SECONDS_PER_DAY));
+        }
+        (Err(CalendarError::TimestampOutOfRange), None) => {}
+        (actual, expected) => panic!("{}: got {:?}, chrono has {:?}", timestamp, actual, expected),
+    }
+}
+
+#[test]
+fn every_day_from_year_minus_1000_to_3000() {
+    let first = NaiveDate::from_ymd_opt(-1000, 1, 1).unwrap().and_hms_opt(0, 0, 0).unwrap().timestamp();
+    let last = NaiveDate::from_ymd_opt(3000, 12, 31).unwrap().and_hms_opt(0, 0, 0).unwrap().timestamp();
+
+    let mut timestamp = first;
+    while timestamp <= last {
+        check_timestamp(timestamp);
+        timestamp += SECONDS_PER_DAY;
+    }
+}
+
+#[test]
+fn whole_range_sampled() {
+    // A stride coprime with 7 and with the 400 year cycle, so every weekday
+    // and every position in the cycle gets hit
+    let stride = 997 * SECONDS_PER_DAY + 4_321;
+
+    let mut timestamp = MIN_TIMESTAMP;
+    while timestamp <= MAX_TIMESTAMP {
+        check_timestamp(timestamp);
+        timestamp += stride;
+    }
+}
+
+#[test]
+fn seconds_within_a_day() {
+    for day in [-719_528, -1, 0, 1, 11_016, 19_723] {
+        for second in [0, 1, 43_200, SECONDS_PER_DAY - 1] {
+            check_timestamp(day * SECONDS_PER_DAY + second);
+        }
+    }
+
+    // Before 1970, the date changes at midnight and not at the next second
+    check_timestamp(-1);
+    assert_eq!(Date::from_timestamp(-1), Ok(Date { year: 1969, month: 12, day: 31 }));
+}
+
+#[test]
+fn range_boundaries() {
+    for timestamp in [
+        i64::MIN,
+        i64::MIN + 1,
+        MIN_TIMESTAMP - SECONDS_PER_DAY,
+        MIN_TIMESTAMP - 1,
+        MIN_TIMESTAMP,
+        MIN_TIMESTAMP + 1,
+        MAX_TIMESTAMP - 1,
+        MAX_TIMESTAMP,
+        MAX_TIMESTAMP + 1,
+        MAX_TIMESTAMP + SECONDS_PER_DAY,
+        i64::MAX - 1,
+        i64::MAX,
+    ] {
+        check_timestamp(timestamp);
+    }
+
+    assert_eq!(Date::from_timestamp(MIN_TIMESTAMP - 1), Err(CalendarError::TimestampOutOfRange));
+    assert_eq!(Date::from_timestamp(MAX_TIMESTAMP + 1), Err(CalendarError::TimestampOutOfRange));
+}
+
+#[test]
+fn weekday_outside_of_the_range() {
+    // No date there, but the weekday keeps cycling
+    for timestamp in [i64::MIN, MIN_TIMESTAMP - 1, MAX_TIMESTAMP + 1, i64::MAX - SECONDS_PER_DAY] {
+        let today = calendar::weekday(timestamp).number_from_monday();
+        let tomorrow = calendar::weekday(timestamp + SECONDS_PER_DAY).number_from_monday();
+
+        assert_eq!(tomorrow, today % 7 + 1, "{}", timestamp);
+    }
+}
+
+#[test]
+fn new_checks_dates() {
+    for year in -401..=401 {
+        for month in 0..=13u8 {
+            for day in 0..=32u8 {
+                let expected = NaiveDate::from_ymd_opt(year, month as u32, day as u32);
+
+                match Date::new(year, month, day) {
+                    Ok(date) => {
+                        let expected = expected.unwrap_or_else(|| panic!("{}-{}-{} is not a date", year, month, day));
+                        assert_same_date(date.days_since_epoch() * SECONDS_PER_DAY, date, expected);
+                    }
+                    Err(error) => {
+                        assert_eq!(error, CalendarError::InvalidDate);
+                        assert!(expected.is_none(), "{}-{}-{} is a date", year, month, day);
+                    }
+                }
+            }
+        }
+    }
+
+    assert!(Date::new(calendar::MIN_YEAR, 1, 1).is_ok());
+    assert!(Date::new(calendar::MAX_YEAR, 12, 31).is_ok());
+    assert_eq!(Date::new(calendar::MIN_YEAR - 1, 12, 31), Err(CalendarError::InvalidDate));
+    assert_eq!(Date::new(calendar::MAX_YEAR + 1, 1, 1), Err(CalendarError::InvalidDate));
+}
+
+#[test]
+fn leap_years() {
+    for year in -2000..=3000 {
+        assert_eq!(calendar::is_leap_year(year), NaiveDate::from_ymd_opt(year, 2, 29).is_some(), "{}", year);
+    }
+}
+
+#[test]
+fn range_constants() {
+    let first = NaiveDate::MIN.and_hms_opt(0, 0, 0).unwrap().timestamp();
+    let last = NaiveDate::MAX.and_hms_opt(23, 59, 59).unwrap().timestamp();
+
+    assert_eq!(MIN_TIMESTAMP, first);
+    assert_eq!(MAX_TIMESTAMP, last);
+    assert_eq!(calendar::MIN_YEAR, NaiveDate::MIN.year());
+    assert_eq!(calendar::MAX_YEAR, NaiveDate::MAX.year());
+}
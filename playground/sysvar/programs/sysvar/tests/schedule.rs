@@ -0,0 +1,172 @@
+// Host-side checks of the schedule module, with a hand-built Clock
+use anchor_lang::prelude::Clock;
+use sysvar::schedule::{Unlock, Vesting, Window, EVERY_DAY, FRIDAY, MONDAY, SECONDS_PER_DAY, SUNDAY, THURSDAY, WEEKDAYS};
+
+// Monday 2024-01-01 00:00:00 UTC
+const MONDAY_MIDNIGHT: i64 = 1_704_067_200;
+const HOUR: i64 = 3600;
+
+fn at(unix_timestamp: i64) -> Clock {
+    Clock { unix_timestamp, ..Clock::default() }
+}
+
+fn at_slot(slot: u64) -> Clock {
+    Clock { slot, ..Clock::default() }
+}
+
+// UNLOCK
+// ------
+
+#[test]
+fn timestamp_unlock_opens_on_its_second() {
+    let unlock = Unlock::Timestamp(MONDAY_MIDNIGHT);
+
+    assert!(!unlock.is_unlocked(&at(MONDAY_MIDNIGHT - 1)));
+    assert!(unlock.is_unlocked(&at(MONDAY_MIDNIGHT)));
+    assert!(unlock.is_unlocked(&at(i64::MAX)));
+}
+
+#[test]
+fn slot_unlock_opens_on_its_slot() {
+    let unlock = Unlock::Slot(1_000);
+
+    assert!(!unlock.is_unlocked(&at_slot(999)));
+    assert!(unlock.is_unlocked(&at_slot(1_000)));
+    // the timestamp plays no part
+    assert!(!unlock.is_unlocked(&Clock { slot: 999, unix_timestamp: i64::MAX, ..Clock::default() }));
+}
+
+#[test]
+fn unlock_extremes() {
+    assert!(Unlock::Timestamp(i64::MIN).is_unlocked(&at(i64::MIN)));
+    assert!(Unlock::Slot(0).is_unlocked(&at_slot(0)));
+    assert!(!Unlock::Slot(u64::MAX).is_unlocked(&at_slot(u64::MAX - 1)));
+}
+
+// WINDOW
+// ------
+
+#[test]
+fn window_start_is_included_and_end_excluded() {
+    let office_hours = Window { days: WEEKDAYS, start: 9 * 3600, end: 17 * 3600 };
+
+    assert!(!office_hours.contains(&at(MONDAY_MIDNIGHT + 9 * HOUR - 1)));
+    assert!(office_hours.contains(&at(MONDAY_MIDNIGHT + 9 * HOUR)));
+    assert!(office_hours.contains(&at(MONDAY_MIDNIGHT + 17 * HOUR - 1)));
+    assert!(!office_hours.contains(&at(MONDAY_MIDNIGHT + 17 * HOUR)));
+}
+
+#[test]
+fn window_wraps_around_the_week() {
+    let sunday_and_monday = Window { days: SUNDAY | MONDAY, start: 0, end: SECONDS_PER_DAY as u32 };
+
+    // Saturday 23:59:59, Sunday, Monday, then Tuesday 00:00:00
+    assert!(!sunday_and_monday.contains(&at(MONDAY_MIDNIGHT - SECONDS_PER_DAY - 1)));
+    assert!(sunday_and_monday.contains(&at(MONDAY_MIDNIGHT - SECONDS_PER_DAY)));
+    assert!(sunday_and_monday.contains(&at(MONDAY_MIDNIGHT - 1)));
+    assert!(sunday_and_monday.contains(&at(MONDAY_MIDNIGHT)));
+    assert!(sunday_and_monday.contains(&at(MONDAY_MIDNIGHT + SECONDS_PER_DAY - 1)));
+    assert!(!sunday_and_monday.contains(&at(MONDAY_MIDNIGHT + SECONDS_PER_DAY)));
+
+    // the same day of the week, any number of weeks later
+    let week = 7 * SECONDS_PER_DAY;
+    assert!(sunday_and_monday.contains(&at(MONDAY_MIDNIGHT + 520 * week)));
+    assert!(!sunday_and_monday.contains(&at(MONDAY_MIDNIGHT + 520 * week + 2 * SECONDS_PER_DAY)));
+}
+
+#[test]
+fn window_wraps_before_the_epoch() {
+    // 1970-01-01 was a Thursday, 1969-12-31 a Wednesday
+    let thursday_morning = Window { days: THURSDAY, start: 0, end: HOUR as u32 };
+
+    assert!(thursday_morning.contains(&at(0)));
+    assert!(!thursday_morning.contains(&at(-1)));
+    assert!(thursday_morning.contains(&at(-7 * SECONDS_PER_DAY)));
+    assert!(thursday_morning.contains(&at(-7 * SECONDS_PER_DAY + HOUR - 1)));
+}
+
+#[test]
+fn window_does_not_wrap_past_midnight() {
+    // 22:00 to 02:00 has to be split into two windows
+    assert!(!Window { days: FRIDAY, start: 22 * 3600, end: 2 * 3600 }.is_valid());
+    assert!(!Window { days: FRIDAY, start: 3600, end: 3600 }.is_valid());
+    assert!(!Window { days: FRIDAY, start: 0, end: SECONDS_PER_DAY as u32 + 1 }.is_valid());
+    assert!(!Window { days: EVERY_DAY + 1, start: 0, end: 3600 }.is_valid());
+
+    assert!(Window { days: FRIDAY, start: 22 * 3600, end: SECONDS_PER_DAY as u32 }.is_valid());
+    assert!(Window { days: 0, start: 0, end: 1 }.is_valid());
+}
+
+// VESTING
+// -------
+
+// 1000 over 100 days from Monday, with a 25 days cliff
+fn vesting() -> Vesting {
+    Vesting { start: MONDAY_MIDNIGHT, cliff: 25 * SECONDS_PER_DAY, duration: 100 * SECONDS_PER_DAY, total: 1_000 }
+}
+
+#[test]
+fn nothing_vests_before_the_cliff() {
+    let vesting = vesting();
+
+    assert_eq!(vesting.vested_amount(&at(i64::MIN)), 0);
+    assert_eq!(vesting.vested_amount(&at(vesting.start - 1)), 0);
+    assert_eq!(vesting.vested_amount(&at(vesting.start)), 0);
+    assert_eq!(vesting.vested_amount(&at(vesting.start + vesting.cliff - 1)), 0);
+}
+
+#[test]
+fn cliff_releases_what_accrued_since_the_start() {
+    let vesting = vesting();
+
+    assert_eq!(vesting.vested_amount(&at(vesting.start + vesting.cliff)), 250);
+}
+
+#[test]
+fn vests_linearly_after_the_cliff() {
+    let vesting = vesting();
+
+    assert_eq!(vesting.vested_amount(&at(vesting.start + 50 * SECONDS_PER_DAY)), 500);
+    assert_eq!(vesting.vested_amount(&at(vesting.start + 75 * SECONDS_PER_DAY)), 750);
+    // rounded down, one second before the end
+    assert_eq!(vesting.vested_amount(&at(vesting.start + vesting.duration - 1)), 999);
+
+    let mut previous = 0;
+    for day in 25..=100 {
+        let vested = vesting.vested_amount(&at(vesting.start + day * SECONDS_PER_DAY));
+        assert!(vested >= previous, "day {}", day);
+        previous = vested;
+    }
+}
+
+#[test]
+fn everything_vests_at_the_end() {
+    let vesting = vesting();
+
+    assert_eq!(vesting.vested_amount(&at(vesting.start + vesting.duration)), 1_000);
+    assert_eq!(vesting.vested_amount(&at(i64::MAX)), 1_000);
+}
+
+#[test]
+fn large_totals_do_not_overflow() {
+    let vesting = Vesting { total: u64::MAX, ..vesting() };
+
+    assert_eq!(vesting.vested_amount(&at(vesting.start + 50 * SECONDS_PER_DAY)), u64::MAX / 2);
+    assert_eq!(vesting.vested_amount(&at(vesting.start + vesting.duration)), u64::MAX);
+}
+
+#[test]
+fn cliff_at_the_end_releases_everything_at_once() {
+    let vesting = Vesting { cliff: 100 * SECONDS_PER_DAY, ..vesting() };
+
+    assert!(vesting.is_valid());
+    assert_eq!(vesting.vested_amount(&at(vesting.start + vesting.duration - 1)), 0);
+    assert_eq!(vesting.vested_amount(&at(vesting.start + vesting.duration)), 1_000);
+}
+
+#[test]
+fn invalid_vestings() {
+    assert!(!Vesting { cliff: -1, ..vesting() }.is_valid());
+    assert!(!Vesting { duration: 0, cliff: 0, ..vesting() }.is_valid());
+    assert!(!Vesting { cliff: 101 * SECONDS_PER_DAY, ..vesting() }.is_valid());
+}
@@ -0,0 +1,75 @@
+#!/usr/bin/env bash
+# Compares the calendar module with the chrono version it replaced: program
+# size, and compute units of `get_day_of_the_week` on a local validator.
+#
+#   scripts/compare-chrono.sh
+#   CHRONO_REV=<commit> scripts/compare-chrono.sh
+#
+# Writes target/bench/chrono.csv and fails when the calendar version is
+# bigger or uses more compute units than chrono. Needs anchor, cargo
+# build-sbf and solana-test-validator on the PATH.
+set -euo pipefail
+
+cd "$(dirname "$0")/.."
+
+PROGRAM_ID=5R2J9VX6beEckkhckVe4PxwmsXbMjuiVP72dKaey6qU
+WALLET=${ANCHOR_WALLET:-$HOME/.config/solana/id.json}
+# The last commit still using chrono, pinned: set CHRONO_REV when the
+# history was rewritten
+CHRONO_REV=${CHRONO_REV:-d1fa84e8b5790e64e2e43ac510b42f58834a9dfd}
+if ! git cat-file -e "$CHRONO_REV^{commit}" 2>/dev/null; then
+    echo "CHRONO_REV $CHRONO_REV is not a commit, set it to the last one using chrono" >&2
+    exit 1
+fi
+
+WORK=$(mktemp -d)
+trap 'git worktree remove --force "$WORK/chrono" 2>/dev/null; rm -rf "$WORK"' EXIT
+
+# Calendar version, with the IDL the test client uses for both
+anchor build
+cp target/deploy/sysvar.so "$WORK/calendar.so"
+
+git worktree add --detach "$WORK/chrono" "$CHRONO_REV"
+(cd "$WORK/chrono/playground/sysvar/programs/sysvar" && cargo build-sbf --sbf-out-dir "$WORK/chrono-deploy")
+cp "$WORK/chrono-deploy/sysvar.so" "$WORK/chrono.so"
+
+# Compute units of `get_day_of_the_week`, with the program deployed from $1
+measure() {
+    local ledger="$WORK/ledger-$(basename "$1" .so)"
+    solana-test-validator --reset --quiet --ledger "$ledger" --bpf-program "$PROGRAM_ID" "$1" &
+    local validator=$!
+
+    until solana cluster-version --url http://127.0.0.1:8899 >/dev/null 2>&1; do
+        sleep 1
+    done
+
+    ANCHOR_PROVIDER_URL=http://127.0.0.1:8899 ANCHOR_WALLET="$WALLET" CU_REPORT="$WORK/cu" \
+        yarn run ts-mocha -p ./tsconfig.json -t 1000000 -g "Get day of the week" tests/sysvar.ts >&2
+
+    kill "$validator"
+    wait "$validator" 2>/dev/null || true
+    cat "$WORK/cu"
+}
+
+chrono_bytes=$(stat -c %s "$WORK/chrono.so")
+calendar_bytes=$(stat -c %s "$WORK/calendar.so")
+chrono_cu=$(measure "$WORK/chrono.so")
+calendar_cu=$(measure "$WORK/calendar.so")
+
+mkdir -p target/bench
+{
+    echo "name,program_bytes,compute_units"
+    echo "chrono,$chrono_bytes,$chrono_cu"
+    echo "calendar,$calendar_bytes,$calendar_cu"
+} | tee target/bench/chrono.csv
+
+status=0
+if [ "$calendar_bytes" -gt "$chrono_bytes" ]; then
+    echo "calendar program is bigger than chrono: $calendar_bytes > $chrono_bytes bytes" >&2
+    status=1
+fi
+if [ "$calendar_cu" -gt "$chrono_cu" ]; then
+    echo "calendar uses more compute units than chrono: $calendar_cu > $chrono_cu" >&2
+    status=1
+fi
+exit $status
@@ -1,23 +1,193 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { Sysvar } from "../target/types/sysvar";
+import { expect } from "chai";
+import * as fs from "fs";
 
 describe("sysvar", () => {
   // Configure the client to use the local cluster.
-  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
 
   const program = anchor.workspace.Sysvar as Program<Sysvar>;
 
+  // Same values as in `schedule.rs`
+  const EVERY_DAY = 0b1111111;
+  const SECONDS_PER_DAY = 24 * 60 * 60;
+
+  const expectError = async (promise: Promise<unknown>, code: string) => {
+    try {
+      await promise;
+      expect.fail(`should have failed with ${code}`);
+    } catch (e) {
+      expect(e.toString()).to.contain(code);
+    }
+  };
+
+  // Block time of the local validator, close to the wall clock
+  const now = async () => provider.connection.getBlockTime(await provider.connection.getSlot());
+
   it("Is initialized!", async () => {
     // Add your test here.
     const tx = await program.methods.initialize().rpc();
     console.log("Your transaction signature", tx);
   });
 
-  it("Get day of the week", async () => {
-    const tx = await program.methods.getDayOfTheWeek().rpc();
+  it("Get day of the week", async () => {
+    const tx = await program.methods.getDayOfTheWeek().rpc({ commitment: "confirmed" });
     console.log("Your transaction signature", tx);
-  }); 
 
+    // Compute units used by the calendar, compared with the chrono version
+    // by scripts/compare-chrono.sh, which reads them from CU_REPORT
+    const result = await provider.connection.getTransaction(tx, {
+      commitment                    : "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    expect(result.meta.logMessages.join("\n")).to.match(/Week day is: (Mon|Tue|Wed|Thu|Fri|Sat|Sun)/);
+    expect(result.meta.computeUnitsConsumed).to.be.above(0);
+    if (process.env.CU_REPORT) {
+      fs.writeFileSync(process.env.CU_REPORT, `${result.meta.computeUnitsConsumed}\n`);
+    }
+  });
+
+  it("Gets the current date", async () => {
+    const date = await program.methods.getDate().view();
+    // The date of the Clock sysvar's timestamp the program read, which the
+    // block time or the host clock may put on another day
+    const expected = new Date(date.unixTimestamp.toNumber() * 1000);
+
+    expect(date.unixTimestamp.toNumber()).to.be.closeTo(await now(), 60);
+    expect(date.year).to.equal(expected.getUTCFullYear());
+    expect(date.month).to.equal(expected.getUTCMonth() + 1);
+    expect(date.day).to.equal(expected.getUTCDate());
+    expect(date.weekday).to.equal(((expected.getUTCDay() + 6) % 7) + 1);
+    expect(date.isoWeek).to.be.within(1, 53);
+    expect(date.isLeapYear).to.equal(new Date(Date.UTC(date.year, 1, 29)).getUTCDate() === 29);
+  });
+
+  describe("time lock", () => {
+    const createTimeLock = async (unlock: any) => {
+      const timeLock = anchor.web3.Keypair.generate();
+      await program.methods
+        .createTimeLock(unlock)
+        .accounts({
+          timeLock     : timeLock.publicKey,
+          authority    : provider.wallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([timeLock])
+        .rpc();
+      return timeLock.publicKey;
+    };
+
+    const release = (timeLock: anchor.web3.PublicKey) =>
+      program.methods
+        .releaseTimeLock()
+        .accounts({ timeLock, authority: provider.wallet.publicKey })
+        .rpc();
+
+    it("Releases a lock whose slot has passed", async () => {
+      const timeLock = await createTimeLock({ slot: { 0: new anchor.BN(1) } });
+      await release(timeLock);
+
+      expect(await provider.connection.getAccountInfo(timeLock)).to.be.null;
+    });
+
+    it("Keeps a lock whose timestamp is in the future", async () => {
+      const timeLock = await createTimeLock({ timestamp: { 0: new anchor.BN((await now()) + 3600) } });
+      await expectError(release(timeLock), "StillLocked");
+    });
+  });
+
+  describe("recurring window", () => {
+    it("Accepts a window covering the whole week", async () => {
+      await program.methods.checkWindow({ days: EVERY_DAY, start: 0, end: SECONDS_PER_DAY }).rpc();
+    });
 
+    it("Rejects a window without any day", async () => {
+      await expectError(
+        program.methods.checkWindow({ days: 0, start: 0, end: SECONDS_PER_DAY }).rpc(),
+        "OutsideWindow"
+      );
+    });
+
+    it("Rejects an invalid window", async () => {
+      await expectError(
+        program.methods.checkWindow({ days: EVERY_DAY, start: 3600, end: 60 }).rpc(),
+        "InvalidWindow"
+      );
+    });
+  });
+
+  describe("vesting", () => {
+    const createVesting = async (vesting: any) => {
+      const account = anchor.web3.Keypair.generate();
+      await program.methods
+        .createVesting(vesting)
+        .accounts({
+          vesting      : account.publicKey,
+          authority    : provider.wallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([account])
+        .rpc();
+      return account.publicKey;
+    };
+
+    const claim = (vesting: anchor.web3.PublicKey) =>
+      program.methods.claimVesting().accounts({ vesting, authority: provider.wallet.publicKey }).rpc();
+
+    const claimed = async (vesting: anchor.web3.PublicKey) =>
+      (await program.account.vestingAccount.fetch(vesting)).claimed.toNumber();
+
+    it("Releases everything after the end of the vesting", async () => {
+      const vesting = await createVesting({
+        start   : new anchor.BN((await now()) - 1000),
+        cliff   : new anchor.BN(10),
+        duration: new anchor.BN(100),
+        total   : new anchor.BN(1_000),
+      });
+
+      await claim(vesting);
+      expect(await claimed(vesting)).to.equal(1_000);
+
+      await expectError(claim(vesting), "NothingToClaim");
+    });
+
+    it("Releases a linear share after the cliff", async () => {
+      const vesting = await createVesting({
+        start   : new anchor.BN((await now()) - 5_000),
+        cliff   : new anchor.BN(1_000),
+        duration: new anchor.BN(10_000),
+        total   : new anchor.BN(10_000),
+      });
+
+      // about half of it, give or take the validator clock drift
+      await claim(vesting);
+      expect(await claimed(vesting)).to.be.within(4_500, 5_500);
+    });
+
+    it("Releases nothing before the cliff", async () => {
+      const vesting = await createVesting({
+        start   : new anchor.BN((await now()) - 100),
+        cliff   : new anchor.BN(3_600),
+        duration: new anchor.BN(7_200),
+        total   : new anchor.BN(1_000),
+      });
+
+      await expectError(claim(vesting), "NothingToClaim");
+    });
+
+    it("Rejects a cliff after the end of the vesting", async () => {
+      await expectError(
+        createVesting({
+          start   : new anchor.BN(await now()),
+          cliff   : new anchor.BN(200),
+          duration: new anchor.BN(100),
+          total   : new anchor.BN(1_000),
+        }),
+        "InvalidVesting"
+      );
+    });
+  });
 });
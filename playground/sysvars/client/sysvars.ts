@@ -0,0 +1,82 @@
+import * as anchor from "@coral-xyz/anchor";
+import { IdlTypes, Program } from "@coral-xyz/anchor";
+import { Sysvars } from "../target/types/sysvars";
+
+// Snapshot types as described by the program's IDL
+type Types = IdlTypes<Sysvars>;
+export type ClockSnapshot         = Types["ClockSnapshot"];
+export type EpochScheduleSnapshot = Types["EpochScheduleSnapshot"];
+export type RentSnapshot          = Types["RentSnapshot"];
+export type InstructionSnapshot   = Types["InstructionSnapshot"];
+export type StakeHistorySnapshot  = Types["StakeHistorySnapshot"];
+export type LastRestartSlotSnapshot = Types["LastRestartSlotSnapshot"];
+export type EpochRewardsSnapshot  = Types["EpochRewardsSnapshot"];
+export type SlotHashesSnapshot    = Types["SlotHashesSnapshot"];
+
+export const SYSVAR_LAST_RESTART_SLOT_PUBKEY = new anchor.web3.PublicKey(
+  "SysvarLastRestartS1ot1111111111111111111111"
+);
+export const SYSVAR_EPOCH_REWARDS_PUBKEY = new anchor.web3.PublicKey(
+  "SysvarEpochRewards1111111111111111111111111"
+);
+
+// Reads sysvars through the `get_*` instructions of the sysvars program.
+// Every call is simulated and its return data decoded, nothing is sent.
+export class SysvarsClient {
+  constructor(readonly program: Program<Sysvars>) {}
+
+  getClock(): Promise<ClockSnapshot> {
+    return this.program.methods.getClock().view();
+  }
+
+  getEpochSchedule(): Promise<EpochScheduleSnapshot> {
+    return this.program.methods.getEpochSchedule().view();
+  }
+
+  getRent(): Promise<RentSnapshot> {
+    return this.program.methods.getRent().view();
+  }
+
+  getInstruction(index: number): Promise<InstructionSnapshot> {
+    return this.program.methods
+      .getInstruction(index)
+      .accounts({ instructionSysvar: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY })
+      .view();
+  }
+
+  getStakeHistory(maxEntries: number): Promise<StakeHistorySnapshot> {
+    return this.program.methods
+      .getStakeHistory(maxEntries)
+      .accounts({ stakeHistory: anchor.web3.SYSVAR_STAKE_HISTORY_PUBKEY })
+      .view();
+  }
+
+  // null when the cluster has no LastRestartSlot account
+  getLastRestartSlot(): Promise<LastRestartSlotSnapshot | null> {
+    return this.program.methods
+      .getLastRestartSlot()
+      .accounts({ lastRestartSlot: SYSVAR_LAST_RESTART_SLOT_PUBKEY })
+      .view();
+  }
+
+  // null outside of the epoch rewards distribution period
+  getEpochRewards(): Promise<EpochRewardsSnapshot | null> {
+    return this.program.methods
+      .getEpochRewards()
+      .accounts({ epochRewards: SYSVAR_EPOCH_REWARDS_PUBKEY })
+      .view();
+  }
+
+  getSlotHashes(maxEntries: number): Promise<SlotHashesSnapshot> {
+    return this.program.methods
+      .getSlotHashes(maxEntries)
+      .accounts({ slotHashes: anchor.web3.SYSVAR_SLOT_HASHES_PUBKEY })
+      .view();
+  }
+
+  // Decodes the return data of a `get_*` instruction that was sent as part of
+  // a larger transaction (the base64 payload of its "Program return:" log)
+  decodeReturnData<T>(typeName: keyof Types & string, base64: string): T {
+    return this.program.coder.types.decode(typeName, Buffer.from(base64, "base64"));
+  }
+}
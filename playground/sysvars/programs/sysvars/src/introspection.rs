@@ -0,0 +1,240 @@
+// Guards asserting properties of the transaction an instruction runs in,
+// read from the instructions sysvar.
+//
+// The instructions sysvar only lists the top-level instructions of the
+// transaction. What those instructions do through CPI is not visible, so
+// these guards are usually combined with `require_top_level` to make sure
+// the guarded instruction is one of the instructions being inspected.
+//
+// Every guard takes the instructions sysvar account as an `AccountInfo`, so
+// other programs can use this module by depending on this crate with the
+// `no-entrypoint` feature.
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{get_stack_height, Instruction, TRANSACTION_LEVEL_STACK_HEIGHT};
+use anchor_lang::solana_program::{ed25519_program, secp256k1_program, sysvar::instructions};
+
+use crate::Errors;
+
+// Programs that only configure the transaction and can be present next to
+// any instruction (compute unit limit and price)
+pub mod compute_budget_program {
+    use super::*;
+
+    declare_id!("ComputeBudget111111111111111111111111111111");
+}
+
+// Offsets inside the data of an Ed25519Program instruction: a u8 signature
+// count, a u8 of padding, then one offsets struct per signature
+const ED25519_OFFSETS_START: usize = 2;
+const ED25519_OFFSETS_SIZE: usize = 14;
+const ED25519_PUBKEY_SIZE: usize = 32;
+const ED25519_SIGNATURE_SIZE: usize = 64;
+
+// Same for a Secp256k1Program instruction: a u8 signature count, then one
+// offsets struct per signature (instruction indexes are u8 here)
+const SECP256K1_OFFSETS_START: usize = 1;
+const SECP256K1_OFFSETS_SIZE: usize = 11;
+const SECP256K1_ETH_ADDRESS_SIZE: usize = 20;
+const SECP256K1_SIGNATURE_SIZE: usize = 64 + 1; // signature + recovery id
+
+// Instruction index used by the Ed25519 precompile for "the data is in this
+// same instruction"
+const SAME_INSTRUCTION: u16 = u16::MAX;
+
+// Number of top-level instructions in the transaction (first 2 bytes of the
+// sysvar data)
+pub fn instruction_count(instruction_sysvar: &AccountInfo) -> Result<usize> {
+    require_keys_eq!(*instruction_sysvar.key, instructions::ID, Errors::InvalidInstructionSysvar);
+
+    let data = instruction_sysvar.try_borrow_data()?;
+    let count = data
+        .get(..2)
+        .map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]]))
+        .ok_or(Errors::InvalidInstructionSysvar)?;
+
+    Ok(count as usize)
+}
+
+// The instruction is a top-level instruction of the transaction, not a CPI
+pub fn require_top_level() -> Result<()> {
+    require_eq!(get_stack_height(), TRANSACTION_LEVEL_STACK_HEIGHT, Errors::NotTopLevel);
+    Ok(())
+}
+
+// The instruction is the only one in its transaction, compute budget
+// instructions apart
+pub fn require_only_instruction(instruction_sysvar: &AccountInfo) -> Result<()> {
+    let current_index = instructions::load_current_index_checked(instruction_sysvar)? as usize;
+
+    for index in 0..instruction_count(instruction_sysvar)? {
+        if index == current_index {
+            continue;
+        }
+
+        let instruction = instructions::load_instruction_at_checked(index, instruction_sysvar)?;
+        require_keys_eq!(instruction.program_id, compute_budget_program::ID, Errors::NotOnlyInstruction);
+    }
+
+    Ok(())
+}
+
+// Every top-level instruction of the transaction targets `program_id`,
+// the compute budget program or one of the `allowed` programs
+pub fn require_no_other_program(
+    instruction_sysvar: &AccountInfo,
+    program_id: &Pubkey,
+    allowed: &[Pubkey],
+) -> Result<()> {
+    for index in 0..instruction_count(instruction_sysvar)? {
+        let instruction = instructions::load_instruction_at_checked(index, instruction_sysvar)?;
+
+        require!(
+            instruction.program_id == *program_id
+                || instruction.program_id == compute_budget_program::ID
+                || allowed.contains(&instruction.program_id),
+            Errors::OtherProgramInvoked
+        );
+    }
+
+    Ok(())
+}
+
+// The instruction right before this one is an Ed25519Program instruction
+// verifying a single signature of `message` by `pubkey`. The precompile fails
+// the whole transaction when the signature is invalid, so if that instruction
+// is there, the signature is valid.
+pub fn require_preceded_by_ed25519(
+    instruction_sysvar: &AccountInfo,
+    pubkey: &Pubkey,
+    message: &[u8],
+) -> Result<()> {
+    let current_index = instructions::load_current_index_checked(instruction_sysvar)? as usize;
+    require!(current_index > 0, Errors::MissingEd25519Instruction);
+
+    let instruction = instructions::load_instruction_at_checked(current_index - 1, instruction_sysvar)?;
+    require_keys_eq!(instruction.program_id, ed25519_program::ID, Errors::MissingEd25519Instruction);
+
+    let (signer, signed_message) = ed25519_signed_message(&instruction).ok_or(Errors::Ed25519Mismatch)?;
+    require!(signer == pubkey.to_bytes() && signed_message == message, Errors::Ed25519Mismatch);
+
+    Ok(())
+}
+
+// Public key and message of an Ed25519Program instruction holding exactly one
+// signature whose public key, signature and message are all stored in the
+// instruction itself. Anything else (several signatures, data read from other
+// instructions) is rejected, since it could point the precompile at bytes we
+// do not check here.
+pub fn ed25519_signed_message(instruction: &Instruction) -> Option<([u8; ED25519_PUBKEY_SIZE], &[u8])> {
+    let data = &instruction.data;
+    if data.first() != Some(&1) {
+        return None;
+    }
+
+    let offsets = data.get(ED25519_OFFSETS_START..ED25519_OFFSETS_START + ED25519_OFFSETS_SIZE)?;
+    let read_u16 = |i: usize| u16::from_le_bytes([offsets[2 * i], offsets[2 * i + 1]]);
+
+    let signature_offset             = read_u16(0) as usize;
+    let signature_instruction_index  = read_u16(1);
+    let public_key_offset            = read_u16(2) as usize;
+    let public_key_instruction_index = read_u16(3);
+    let message_data_offset          = read_u16(4) as usize;
+    let message_data_size            = read_u16(5) as usize;
+    let message_instruction_index    = read_u16(6);
+
+    if signature_instruction_index != SAME_INSTRUCTION
+        || public_key_instruction_index != SAME_INSTRUCTION
+        || message_instruction_index != SAME_INSTRUCTION
+    {
+        return None;
+    }
+
+    // The precompile already checked these ranges, checked again to avoid panicking
+    data.get(signature_offset..signature_offset + ED25519_SIGNATURE_SIZE)?;
+    let public_key = data.get(public_key_offset..public_key_offset + ED25519_PUBKEY_SIZE)?;
+    let message = data.get(message_data_offset..message_data_offset + message_data_size)?;
+
+    Some((public_key.try_into().ok()?, message))
+}
+
+// The instruction right before this one is a Secp256k1Program instruction
+// verifying a single signature of `message` by the Ethereum address
+// `eth_address`. As for Ed25519, an invalid signature fails the transaction.
+pub fn require_preceded_by_secp256k1(
+    instruction_sysvar: &AccountInfo,
+    eth_address: &[u8; SECP256K1_ETH_ADDRESS_SIZE],
+    message: &[u8],
+) -> Result<()> {
+    let current_index = instructions::load_current_index_checked(instruction_sysvar)? as usize;
+    require!(current_index > 0, Errors::MissingSecp256k1Instruction);
+
+    let index = current_index - 1;
+    let instruction = instructions::load_instruction_at_checked(index, instruction_sysvar)?;
+    require_keys_eq!(instruction.program_id, secp256k1_program::ID, Errors::MissingSecp256k1Instruction);
+
+    let (signer, signed_message) = secp256k1_signed_message(&instruction, index).ok_or(Errors::Secp256k1Mismatch)?;
+    require!(signer == *eth_address && signed_message == message, Errors::Secp256k1Mismatch);
+
+    Ok(())
+}
+
+// Ethereum address and message of a Secp256k1Program instruction holding
+// exactly one signature, with all its data in the instruction itself. Unlike
+// Ed25519Program, this precompile has no "same instruction" index, so the
+// offsets must point at `own_index`, the position of the instruction in the
+// transaction.
+pub fn secp256k1_signed_message(
+    instruction: &Instruction,
+    own_index: usize,
+) -> Option<([u8; SECP256K1_ETH_ADDRESS_SIZE], &[u8])> {
+    let data = &instruction.data;
+    if data.first() != Some(&1) {
+        return None;
+    }
+
+    let offsets = data.get(SECP256K1_OFFSETS_START..SECP256K1_OFFSETS_START + SECP256K1_OFFSETS_SIZE)?;
+    let read_u16 = |at: usize| u16::from_le_bytes([offsets[at], offsets[at + 1]]) as usize;
+
+    let signature_offset              = read_u16(0);
+    let signature_instruction_index   = offsets[2] as usize;
+    let eth_address_offset            = read_u16(3);
+    let eth_address_instruction_index = offsets[5] as usize;
+    let message_data_offset           = read_u16(6);
+    let message_data_size             = read_u16(8);
+    let message_instruction_index     = offsets[10] as usize;
+
+    if signature_instruction_index != own_index
+        || eth_address_instruction_index != own_index
+        || message_instruction_index != own_index
+    {
+        return None;
+    }
+
+    // The precompile already checked these ranges, checked again to avoid panicking
+    data.get(signature_offset..signature_offset + SECP256K1_SIGNATURE_SIZE)?;
+    let eth_address = data.get(eth_address_offset..eth_address_offset + SECP256K1_ETH_ADDRESS_SIZE)?;
+    let message = data.get(message_data_offset..message_data_offset + message_data_size)?;
+
+    Some((eth_address.try_into().ok()?, message))
+}
+
+// A later instruction of the transaction targets `program_id` and its data
+// starts with `discriminator`, e.g. a "settle" instruction that has to close
+// what the current one opened
+pub fn require_later_instruction(
+    instruction_sysvar: &AccountInfo,
+    program_id: &Pubkey,
+    discriminator: &[u8],
+) -> Result<()> {
+    let current_index = instructions::load_current_index_checked(instruction_sysvar)? as usize;
+
+    for index in current_index + 1..instruction_count(instruction_sysvar)? {
+        let instruction = instructions::load_instruction_at_checked(index, instruction_sysvar)?;
+
+        if instruction.program_id == *program_id && instruction.data.starts_with(discriminator) {
+            return Ok(());
+        }
+    }
+
+    err!(Errors::MissingLaterInstruction)
+}
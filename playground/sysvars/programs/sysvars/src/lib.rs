@@ -1,11 +1,25 @@
 use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use std::mem::size_of;
+use anchor_lang::solana_program::sysvar::{
+    epoch_rewards::{self, EpochRewards},
+    instructions,
+    last_restart_slot::{self, LastRestartSlot},
+    slot_hashes,
+    stake_history,
+};
+
+pub mod introspection;
 
 declare_id!("6aHtT7SNv7myvhZCPBUGsXmUuatTs4jqygHFNmcMDmS9");
 
+// Return data is capped at 1024 bytes, so list-like sysvars (StakeHistory,
+// SlotHashes) only return their most recent entries
+pub const MAX_RETURNED_ENTRIES: u8 = 16;
+
 #[program]
 pub mod sysvars {
     use super::*;
-    use anchor_lang::solana_program::sysvar::{instructions, fees::Fees, recent_blockhashes::RecentBlockhashes};
 
     pub fn initialize(ctx: Context<Initialize>, number: u32) -> Result<()> {
         // Get the Clock sysvar
@@ -68,6 +82,231 @@ pub mod sysvars {
        Ok(())
     }
 
+    // One instruction per sysvar. Each one returns a Borsh-encoded snapshot
+    // through return data, decoded by the client in `client/sysvars.ts`
+
+    pub fn get_clock(_ctx: Context<GetSysvar>) -> Result<ClockSnapshot> {
+        Ok(Clock::get()?.into())
+    }
+
+    pub fn get_epoch_schedule(_ctx: Context<GetSysvar>) -> Result<EpochScheduleSnapshot> {
+        Ok(EpochSchedule::get()?.into())
+    }
+
+    pub fn get_rent(_ctx: Context<GetSysvar>) -> Result<RentSnapshot> {
+        Ok(Rent::get()?.into())
+    }
+
+    pub fn get_instruction(ctx: Context<GetInstruction>, index: u16) -> Result<InstructionSnapshot> {
+        let instruction = instructions::load_instruction_at_checked(
+            index as usize,
+            &ctx.accounts.instruction_sysvar,
+        )?;
+
+        Ok(InstructionSnapshot {
+            program_id: instruction.program_id,
+            accounts: instruction
+                .accounts
+                .iter()
+                .map(|meta| AccountMetaSnapshot {
+                    pubkey:      meta.pubkey,
+                    is_signer:   meta.is_signer,
+                    is_writable: meta.is_writable,
+                })
+                .collect(),
+            data: instruction.data,
+        })
+    }
+
+    pub fn get_stake_history(ctx: Context<GetStakeHistory>, max_entries: u8) -> Result<StakeHistorySnapshot> {
+        let stake_history = StakeHistory::from_account_info(&ctx.accounts.stake_history)?;
+
+        Ok(StakeHistorySnapshot {
+            entries: stake_history
+                .iter()
+                .take(max_entries.min(MAX_RETURNED_ENTRIES) as usize)
+                .map(|(epoch, entry)| StakeHistoryEntrySnapshot {
+                    epoch:        *epoch,
+                    effective:    entry.effective,
+                    activating:   entry.activating,
+                    deactivating: entry.deactivating,
+                })
+                .collect(),
+        })
+    }
+
+    // LastRestartSlot and EpochRewards are read from their accounts: their
+    // `get()` syscalls are behind feature gates that may not be active, and
+    // the EpochRewards account only exists while rewards are distributed
+    pub fn get_last_restart_slot(ctx: Context<GetLastRestartSlot>) -> Result<Option<LastRestartSlotSnapshot>> {
+        let account = &ctx.accounts.last_restart_slot;
+        if account.data_is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(LastRestartSlot::from_account_info(account)?.into()))
+    }
+
+    pub fn get_epoch_rewards(ctx: Context<GetEpochRewards>) -> Result<Option<EpochRewardsSnapshot>> {
+        let account = &ctx.accounts.epoch_rewards;
+        if account.data_is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(EpochRewards::from_account_info(account)?.into()))
+    }
+
+    // `SlotHashes::from_account_info` always fails (the account is too big to
+    // deserialize on-chain), so only the requested entries are read from the
+    // raw data: a u64 length followed by (slot: u64, hash: [u8; 32]) entries
+    pub fn get_slot_hashes(ctx: Context<GetSlotHashes>, max_entries: u8) -> Result<SlotHashesSnapshot> {
+        const ENTRY_SIZE: usize = 8 + 32;
+
+        let data = ctx.accounts.slot_hashes.try_borrow_data()?;
+        let len = data
+            .get(..8)
+            .map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap()) as usize)
+            .ok_or(ErrorCode::AccountDidNotDeserialize)?;
+        let count = len.min(max_entries.min(MAX_RETURNED_ENTRIES) as usize);
+
+        let mut entries = Vec::with_capacity(count);
+        for i in 0..count {
+            let offset = 8 + i * ENTRY_SIZE;
+            let entry = data
+                .get(offset..offset + ENTRY_SIZE)
+                .ok_or(ErrorCode::AccountDidNotDeserialize)?;
+
+            entries.push(SlotHashSnapshot {
+                slot: u64::from_le_bytes(entry[..8].try_into().unwrap()),
+                hash: entry[8..].try_into().unwrap(),
+            });
+        }
+
+        Ok(SlotHashesSnapshot { entries })
+    }
+
+    // Instructions protected by the guards of the `introspection` module
+
+    // Must be alone in its transaction
+    pub fn only_instruction(ctx: Context<Introspect>) -> Result<()> {
+        introspection::require_top_level()?;
+        introspection::require_only_instruction(&ctx.accounts.instruction_sysvar)?;
+
+        msg!("Alone in the transaction");
+        Ok(())
+    }
+
+    // Flash-loan style "open": must be followed by `settle` in the same
+    // transaction, and nothing but this program may run in between, so it
+    // cannot be sandwiched by instructions of another program
+    pub fn open(ctx: Context<Introspect>) -> Result<()> {
+        introspection::require_top_level()?;
+        introspection::require_no_other_program(&ctx.accounts.instruction_sysvar, &crate::ID, &[])?;
+        introspection::require_later_instruction(
+            &ctx.accounts.instruction_sysvar,
+            &crate::ID,
+            &instruction::Settle::DISCRIMINATOR,
+        )?;
+
+        msg!("Opened");
+        Ok(())
+    }
+
+    pub fn settle(_ctx: Context<Introspect>) -> Result<()> {
+        introspection::require_top_level()?;
+
+        msg!("Settled");
+        Ok(())
+    }
+
+    // Must come right after an Ed25519Program instruction verifying that
+    // `message` was signed by `signer`
+    pub fn check_ed25519(ctx: Context<Introspect>, signer: Pubkey, message: Vec<u8>) -> Result<()> {
+        introspection::require_preceded_by_ed25519(&ctx.accounts.instruction_sysvar, &signer, &message)?;
+
+        msg!("Message signed by {}", signer);
+        Ok(())
+    }
+
+    // Off-chain signed messages. A Verifier stores the keys allowed to sign
+    // for it and a nonce account whose value must be part of every message,
+    // so each signed message can only be used once.
+
+    pub fn init_verifier(
+        ctx: Context<InitVerifier>,
+        ed25519_signer: Pubkey,
+        secp256k1_signer: [u8; 20],
+    ) -> Result<()> {
+        let verifier = &mut ctx.accounts.verifier;
+        verifier.authority        = ctx.accounts.authority.key();
+        verifier.ed25519_signer   = ed25519_signer;
+        verifier.secp256k1_signer = secp256k1_signer;
+
+        ctx.accounts.nonce.value = 0;
+        Ok(())
+    }
+
+    // Only the verifier's authority can replace its signing keys, e.g. after
+    // one leaked. The nonce keeps going, so older messages stay used.
+    pub fn set_verifier_signers(
+        ctx: Context<SetVerifierSigners>,
+        ed25519_signer: Pubkey,
+        secp256k1_signer: [u8; 20],
+    ) -> Result<()> {
+        let verifier = &mut ctx.accounts.verifier;
+        verifier.ed25519_signer   = ed25519_signer;
+        verifier.secp256k1_signer = secp256k1_signer;
+        Ok(())
+    }
+
+    // Must come right after an Ed25519Program instruction verifying
+    // `signed_message(verifier, nonce, payload)` signed by the verifier's
+    // `ed25519_signer`
+    pub fn verify_ed25519_message(ctx: Context<VerifyMessage>, nonce: u64, payload: Vec<u8>) -> Result<()> {
+        let message = signed_message(&ctx.accounts.verifier.key(), nonce, &payload);
+        introspection::require_preceded_by_ed25519(
+            &ctx.accounts.instruction_sysvar,
+            &ctx.accounts.verifier.ed25519_signer,
+            &message,
+        )?;
+
+        use_nonce(&mut ctx.accounts.nonce, nonce)?;
+        msg!("Ed25519 signed payload accepted: {:?}", payload);
+        Ok(())
+    }
+
+    // Same with a Secp256k1Program instruction and the verifier's
+    // `secp256k1_signer` Ethereum address
+    pub fn verify_secp256k1_message(ctx: Context<VerifyMessage>, nonce: u64, payload: Vec<u8>) -> Result<()> {
+        let message = signed_message(&ctx.accounts.verifier.key(), nonce, &payload);
+        introspection::require_preceded_by_secp256k1(
+            &ctx.accounts.instruction_sysvar,
+            &ctx.accounts.verifier.secp256k1_signer,
+            &message,
+        )?;
+
+        use_nonce(&mut ctx.accounts.nonce, nonce)?;
+        msg!("Secp256k1 signed payload accepted: {:?}", payload);
+        Ok(())
+    }
+}
+
+// Bytes a signer has to sign: verifier address || nonce (u64 LE) || payload.
+// The verifier address keeps a message signed for one verifier from being
+// accepted by another one sharing the same key.
+pub fn signed_message(verifier: &Pubkey, nonce: u64, payload: &[u8]) -> Vec<u8> {
+    let mut message = Vec::with_capacity(32 + 8 + payload.len());
+    message.extend_from_slice(verifier.as_ref());
+    message.extend_from_slice(&nonce.to_le_bytes());
+    message.extend_from_slice(payload);
+    message
+}
+
+fn use_nonce(nonce_account: &mut Account<NonceAccount>, nonce: u64) -> Result<()> {
+    require_eq!(nonce, nonce_account.value, Errors::InvalidNonce);
+
+    nonce_account.value = nonce_account.value.checked_add(1).ok_or(Errors::InvalidNonce)?;
+    Ok(())
 }
 
 
@@ -78,9 +317,254 @@ pub struct Initialize<'info> {
     /// CHECK:
     pub stake_history: AccountInfo<'info>, // We create an account for the StakeHistory sysvar
     /// CHECK:
-    pub recent_blockhashes: AccountInfo<'info>,
-    /// CHECK:
     pub instruction_sysvar: AccountInfo<'info>,
-    /// CHECK:
+}
+
+#[derive(Accounts)]
+pub struct GetSysvar {}
+
+#[derive(Accounts)]
+pub struct Introspect<'info> {
+    /// CHECK: address is checked against the instructions sysvar id
+    #[account(address = instructions::ID)]
+    pub instruction_sysvar: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct GetInstruction<'info> {
+    /// CHECK: address is checked against the instructions sysvar id
+    #[account(address = instructions::ID)]
+    pub instruction_sysvar: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct GetStakeHistory<'info> {
+    /// CHECK: address is checked against the StakeHistory sysvar id
+    #[account(address = stake_history::ID)]
+    pub stake_history: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct GetLastRestartSlot<'info> {
+    /// CHECK: address is checked against the LastRestartSlot sysvar id
+    #[account(address = last_restart_slot::ID)]
     pub last_restart_slot: AccountInfo<'info>,
 }
+
+#[derive(Accounts)]
+pub struct GetEpochRewards<'info> {
+    /// CHECK: address is checked against the EpochRewards sysvar id
+    #[account(address = epoch_rewards::ID)]
+    pub epoch_rewards: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct GetSlotHashes<'info> {
+    /// CHECK: address is checked against the SlotHashes sysvar id
+    #[account(address = slot_hashes::ID)]
+    pub slot_hashes: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitVerifier<'info> {
+    #[account(init, payer = authority, space = size_of::<Verifier>() + 8)]
+    pub verifier: Account<'info, Verifier>,
+
+    #[account(init,
+              payer = authority,
+              space = size_of::<NonceAccount>() + 8,
+              seeds = [b"nonce", verifier.key().as_ref()],
+              bump)]
+    pub nonce: Account<'info, NonceAccount>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetVerifierSigners<'info> {
+    #[account(mut, has_one = authority)]
+    pub verifier: Account<'info, Verifier>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct VerifyMessage<'info> {
+    pub verifier: Account<'info, Verifier>,
+
+    #[account(mut, seeds = [b"nonce", verifier.key().as_ref()], bump)]
+    pub nonce: Account<'info, NonceAccount>,
+
+    /// CHECK: address is checked against the instructions sysvar id
+    #[account(address = instructions::ID)]
+    pub instruction_sysvar: AccountInfo<'info>,
+}
+
+#[account]
+pub struct Verifier {
+    pub authority:        Pubkey, // can replace the signers
+    pub ed25519_signer:   Pubkey,
+    pub secp256k1_signer: [u8; 20], // Ethereum address
+}
+
+#[account]
+pub struct NonceAccount {
+    pub value: u64, // nonce expected in the next signed message
+}
+
+#[error_code]
+pub enum Errors {
+    #[msg("Account is not the instructions sysvar")]
+    InvalidInstructionSysvar,
+    #[msg("Instruction must not be called through CPI")]
+    NotTopLevel,
+    #[msg("Instruction must be the only one in the transaction")]
+    NotOnlyInstruction,
+    #[msg("Transaction invokes another program")]
+    OtherProgramInvoked,
+    #[msg("Instruction must be preceded by an Ed25519Program instruction")]
+    MissingEd25519Instruction,
+    #[msg("Ed25519Program instruction does not verify the expected signer and message")]
+    Ed25519Mismatch,
+    #[msg("Required later instruction is missing from the transaction")]
+    MissingLaterInstruction,
+    #[msg("Instruction must be preceded by a Secp256k1Program instruction")]
+    MissingSecp256k1Instruction,
+    #[msg("Secp256k1Program instruction does not verify the expected signer and message")]
+    Secp256k1Mismatch,
+    #[msg("Nonce does not match the nonce account")]
+    InvalidNonce,
+}
+
+// Snapshots returned to clients. The sysvar types themselves are bincode
+// encoded and unknown to the IDL, so each one gets a Borsh mirror here.
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct ClockSnapshot {
+    pub slot:                  u64,
+    pub epoch_start_timestamp: i64,
+    pub epoch:                 u64,
+    pub leader_schedule_epoch: u64,
+    pub unix_timestamp:        i64,
+}
+
+impl From<Clock> for ClockSnapshot {
+    fn from(clock: Clock) -> Self {
+        Self {
+            slot:                  clock.slot,
+            epoch_start_timestamp: clock.epoch_start_timestamp,
+            epoch:                 clock.epoch,
+            leader_schedule_epoch: clock.leader_schedule_epoch,
+            unix_timestamp:        clock.unix_timestamp,
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct EpochScheduleSnapshot {
+    pub slots_per_epoch:             u64,
+    pub leader_schedule_slot_offset: u64,
+    pub warmup:                      bool,
+    pub first_normal_epoch:          u64,
+    pub first_normal_slot:           u64,
+}
+
+impl From<EpochSchedule> for EpochScheduleSnapshot {
+    fn from(epoch_schedule: EpochSchedule) -> Self {
+        Self {
+            slots_per_epoch:             epoch_schedule.slots_per_epoch,
+            leader_schedule_slot_offset: epoch_schedule.leader_schedule_slot_offset,
+            warmup:                      epoch_schedule.warmup,
+            first_normal_epoch:          epoch_schedule.first_normal_epoch,
+            first_normal_slot:           epoch_schedule.first_normal_slot,
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct RentSnapshot {
+    pub lamports_per_byte_year: u64,
+    pub exemption_threshold:    f64,
+    pub burn_percent:           u8,
+}
+
+impl From<Rent> for RentSnapshot {
+    fn from(rent: Rent) -> Self {
+        Self {
+            lamports_per_byte_year: rent.lamports_per_byte_year,
+            exemption_threshold:    rent.exemption_threshold,
+            burn_percent:           rent.burn_percent,
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct AccountMetaSnapshot {
+    pub pubkey:      Pubkey,
+    pub is_signer:   bool,
+    pub is_writable: bool,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct InstructionSnapshot {
+    pub program_id: Pubkey,
+    pub accounts:   Vec<AccountMetaSnapshot>,
+    pub data:       Vec<u8>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct StakeHistoryEntrySnapshot {
+    pub epoch:        u64,
+    pub effective:    u64,
+    pub activating:   u64,
+    pub deactivating: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct StakeHistorySnapshot {
+    pub entries: Vec<StakeHistoryEntrySnapshot>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct LastRestartSlotSnapshot {
+    pub last_restart_slot: u64,
+}
+
+impl From<LastRestartSlot> for LastRestartSlotSnapshot {
+    fn from(last_restart_slot: LastRestartSlot) -> Self {
+        Self {
+            last_restart_slot: last_restart_slot.last_restart_slot,
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct EpochRewardsSnapshot {
+    pub total_rewards:                      u64,
+    pub distributed_rewards:                u64,
+    pub distribution_complete_block_height: u64,
+}
+
+impl From<EpochRewards> for EpochRewardsSnapshot {
+    fn from(epoch_rewards: EpochRewards) -> Self {
+        Self {
+            total_rewards:                      epoch_rewards.total_rewards,
+            distributed_rewards:                epoch_rewards.distributed_rewards,
+            distribution_complete_block_height: epoch_rewards.distribution_complete_block_height,
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct SlotHashSnapshot {
+    pub slot: u64,
+    pub hash: [u8; 32],
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct SlotHashesSnapshot {
+    pub entries: Vec<SlotHashSnapshot>,
+}
@@ -1,23 +1,34 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { Sysvars } from "../target/types/sysvars";
+import { SysvarsClient } from "../client/sysvars";
+import { expect } from "chai";
 
 describe("sysvars", () => {
   // Configure the client to use the local cluster.
-  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
 
   const program = anchor.workspace.Sysvars as Program<Sysvars>;
+  const client  = new SysvarsClient(program);
+
+  // Same value as `MAX_RETURNED_ENTRIES` in the program
+  const MAX_RETURNED_ENTRIES = 16;
+
+  const expectError = async (promise: Promise<string>, code: string) => {
+    try {
+      await promise;
+      expect.fail(`should have failed with ${code}`);
+    } catch (e) {
+      expect(e.toString()).to.contain(code);
+    }
+  };
 
   // Create a StakeHistory PublicKey object
   const StakeHistory_PublicKey = new anchor.web3.PublicKey(
     "SysvarStakeHistory1111111111111111111111111"
   );
 
-  // Create a LastRestartSlot PublicKey object
-  const LastRestartSlot_PublicKey = new anchor.web3.PublicKey(
-    "SysvarLastRestartS1ot1111111111111111111111"
-  );
-
   it("Is initialized!", async () => {
     // Add your test here.
     //const tx = await program.methods.initialize().rpc();
@@ -26,9 +37,7 @@ describe("sysvars", () => {
       .initialize(3) // Call the initialze function with the number `3`
       .accounts({
         stakeHistory: StakeHistory_PublicKey, // pass the public key of StakeHistory sysvar to the list of accounts needed for the instruction
-        recentBlockhashes: anchor.web3.SYSVAR_RECENT_BLOCKHASHES_PUBKEY, // pass the public key of RecentBlockhashes sysvar to the list of accounts needed for the instruction
         instructionSysvar: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY, // Pass the public key of the Instruction sysvar to the list of accounts needed for the instruction
-        lastRestartSlot: LastRestartSlot_PublicKey, // pass the public key of LastRestartSlot sysvar to the list of accounts needed for the instruction
     })
     .rpc();
 
@@ -40,4 +49,314 @@ describe("sysvars", () => {
 
 
 
+  it("Returns the Clock", async () => {
+    const clock = await client.getClock();
+
+    expect(clock.slot.toNumber()).to.be.greaterThan(0);
+    expect(clock.slot.toNumber()).to.be.at.most(await provider.connection.getSlot());
+    expect(clock.epoch.toNumber()).to.equal((await provider.connection.getEpochInfo()).epoch);
+    // the local validator's clock follows the wall clock
+    expect(Math.abs(clock.unixTimestamp.toNumber() - Date.now() / 1000)).to.be.below(600);
+  });
+
+  it("Returns the EpochSchedule", async () => {
+    const epochSchedule = await client.getEpochSchedule();
+    const expected = await provider.connection.getEpochSchedule();
+
+    expect(epochSchedule.slotsPerEpoch.toNumber()).to.equal(expected.slotsPerEpoch);
+    expect(epochSchedule.leaderScheduleSlotOffset.toNumber()).to.equal(expected.leaderScheduleSlotOffset);
+    expect(epochSchedule.warmup).to.equal(expected.warmup);
+    expect(epochSchedule.firstNormalEpoch.toNumber()).to.equal(expected.firstNormalEpoch);
+    expect(epochSchedule.firstNormalSlot.toNumber()).to.equal(expected.firstNormalSlot);
+  });
+
+  it("Returns the Rent", async () => {
+    const rent = await client.getRent();
+
+    // an empty account still pays for its 128 bytes of metadata
+    const minimumBalance = await provider.connection.getMinimumBalanceForRentExemption(0);
+    expect(rent.lamportsPerByteYear.toNumber() * 128 * rent.exemptionThreshold).to.equal(minimumBalance);
+    expect(rent.burnPercent).to.be.at.most(100);
+  });
+
+  it("Returns an instruction of the transaction", async () => {
+    const instruction = await client.getInstruction(0);
+
+    expect(instruction.programId.equals(program.programId)).to.be.true;
+    expect(instruction.accounts).to.have.length(1);
+    expect(instruction.accounts[0].pubkey.equals(anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY)).to.be.true;
+    // 8 bytes discriminator + u16 index
+    expect(instruction.data.length).to.equal(10);
+  });
+
+  it("Returns the StakeHistory", async () => {
+    const stakeHistory = await client.getStakeHistory(255);
+
+    expect(stakeHistory.entries.length).to.be.at.most(MAX_RETURNED_ENTRIES);
+    for (let i = 1; i < stakeHistory.entries.length; i++) {
+      expect(stakeHistory.entries[i].epoch.toNumber()).to.be.below(stakeHistory.entries[i - 1].epoch.toNumber());
+    }
+  });
+
+  it("Returns the LastRestartSlot", async () => {
+    const lastRestartSlot = await client.getLastRestartSlot();
+
+    if (lastRestartSlot !== null) {
+      expect(lastRestartSlot.lastRestartSlot.toNumber()).to.be.at.most(await provider.connection.getSlot());
+    }
+  });
+
+  it("Returns the EpochRewards", async () => {
+    const epochRewards = await client.getEpochRewards();
+
+    if (epochRewards !== null) {
+      expect(epochRewards.distributedRewards.toNumber()).to.be.at.most(epochRewards.totalRewards.toNumber());
+    }
+  });
+
+  it("Returns the most recent SlotHashes", async () => {
+    const slotHashes = await client.getSlotHashes(4);
+    const clock = await client.getClock();
+
+    expect(slotHashes.entries).to.have.length(4);
+    for (let i = 0; i < slotHashes.entries.length; i++) {
+      expect(slotHashes.entries[i].slot.toNumber()).to.be.below(clock.slot.toNumber());
+      expect(slotHashes.entries[i].hash).to.have.length(32);
+      if (i > 0) {
+        expect(slotHashes.entries[i].slot.toNumber()).to.be.below(slotHashes.entries[i - 1].slot.toNumber());
+      }
+    }
+
+    // asking for more than the program can return is capped
+    expect((await client.getSlotHashes(255)).entries.length).to.be.at.most(MAX_RETURNED_ENTRIES);
+  });
+
+  describe("introspection guards", () => {
+    const accounts = { instructionSysvar: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY };
+
+    const settleIx = () => program.methods.settle().accounts(accounts).instruction();
+
+    const transferIx = () =>
+      anchor.web3.SystemProgram.transfer({
+        fromPubkey: provider.wallet.publicKey,
+        toPubkey  : provider.wallet.publicKey,
+        lamports  : 1,
+      });
+
+    it("only_instruction succeeds alone", async () => {
+      await program.methods.onlyInstruction().accounts(accounts).rpc();
+    });
+
+    it("only_instruction fails next to another instruction", async () => {
+      await expectError(
+        program.methods.onlyInstruction().accounts(accounts).postInstructions([await settleIx()]).rpc(),
+        "NotOnlyInstruction"
+      );
+    });
+
+    it("open succeeds when settled in the same transaction", async () => {
+      await program.methods.open().accounts(accounts).postInstructions([await settleIx()]).rpc();
+    });
+
+    it("open fails without settle", async () => {
+      await expectError(program.methods.open().accounts(accounts).rpc(), "MissingLaterInstruction");
+    });
+
+    it("open fails when another program is invoked", async () => {
+      await expectError(
+        program.methods
+          .open()
+          .accounts(accounts)
+          .postInstructions([transferIx(), await settleIx()])
+          .rpc(),
+        "OtherProgramInvoked"
+      );
+    });
+
+    describe("check_ed25519", () => {
+      const signer  = anchor.web3.Keypair.generate();
+      const message = Buffer.from("hello sysvars");
+
+      const ed25519Ix = (msg: Buffer) =>
+        anchor.web3.Ed25519Program.createInstructionWithPrivateKey({
+          privateKey: signer.secretKey,
+          message   : msg,
+        });
+
+      it("succeeds after a matching Ed25519Program instruction", async () => {
+        await program.methods
+          .checkEd25519(signer.publicKey, message)
+          .accounts(accounts)
+          .preInstructions([ed25519Ix(message)])
+          .rpc();
+      });
+
+      it("fails when another message was signed", async () => {
+        await expectError(
+          program.methods
+            .checkEd25519(signer.publicKey, message)
+            .accounts(accounts)
+            .preInstructions([ed25519Ix(Buffer.from("something else"))])
+            .rpc(),
+          "Ed25519Mismatch"
+        );
+      });
+
+      it("fails without an Ed25519Program instruction", async () => {
+        await expectError(
+          program.methods.checkEd25519(signer.publicKey, message).accounts(accounts).rpc(),
+          "MissingEd25519Instruction"
+        );
+      });
+    });
+  });
+
+  describe("off-chain signed messages", () => {
+    const verifier        = anchor.web3.Keypair.generate();
+    const ed25519Signer   = anchor.web3.Keypair.generate();
+    const secp256k1Secret = Buffer.from(anchor.web3.Keypair.generate().secretKey.slice(0, 32));
+
+    const [nonce] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("nonce"), verifier.publicKey.toBuffer()],
+      program.programId
+    );
+
+    const accounts = {
+      verifier         : verifier.publicKey,
+      nonce            : nonce,
+      instructionSysvar: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+    };
+
+    const payload = Buffer.from("voucher: 10 points");
+
+    // Same bytes as `signed_message` in the program
+    const signedMessage = (n: number, data: Buffer) =>
+      Buffer.concat([verifier.publicKey.toBuffer(), new anchor.BN(n).toArrayLike(Buffer, "le", 8), data]);
+
+    const ed25519Ix = (signer: anchor.web3.Keypair, message: Buffer) =>
+      anchor.web3.Ed25519Program.createInstructionWithPrivateKey({
+        privateKey: signer.secretKey,
+        message   : message,
+      });
+
+    const secp256k1Ix = (message: Buffer) =>
+      anchor.web3.Secp256k1Program.createInstructionWithPrivateKey({
+        privateKey: secp256k1Secret,
+        message   : message,
+      });
+
+    // The Ethereum address is stored in the instruction data, at the offset
+    // found in bytes 4..6
+    const ethAddress = (() => {
+      const data = secp256k1Ix(Buffer.alloc(0)).data;
+      const offset = data.readUInt16LE(4);
+      return Array.from(data.subarray(offset, offset + 20));
+    })();
+
+    const fetchNonce = async () => (await program.account.nonceAccount.fetch(nonce)).value.toNumber();
+
+    it("Initializes a verifier", async () => {
+      await program.methods
+        .initVerifier(ed25519Signer.publicKey, ethAddress)
+        .accounts({
+          verifier     : verifier.publicKey,
+          nonce        : nonce,
+          authority    : provider.wallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([verifier])
+        .rpc();
+
+      expect(await fetchNonce()).to.equal(0);
+    });
+
+    it("Accepts an Ed25519 signed message once", async () => {
+      const verify = () =>
+        program.methods
+          .verifyEd25519Message(new anchor.BN(0), payload)
+          .accounts(accounts)
+          .preInstructions([ed25519Ix(ed25519Signer, signedMessage(0, payload))])
+          .rpc();
+
+      await verify();
+      expect(await fetchNonce()).to.equal(1);
+
+      // replaying the same signed message
+      await expectError(verify(), "InvalidNonce");
+    });
+
+    it("Rejects an Ed25519 message signed by another key", async () => {
+      await expectError(
+        program.methods
+          .verifyEd25519Message(new anchor.BN(1), payload)
+          .accounts(accounts)
+          .preInstructions([ed25519Ix(anchor.web3.Keypair.generate(), signedMessage(1, payload))])
+          .rpc(),
+        "Ed25519Mismatch"
+      );
+    });
+
+    it("Accepts a Secp256k1 signed message", async () => {
+      await program.methods
+        .verifySecp256k1Message(new anchor.BN(1), payload)
+        .accounts(accounts)
+        .preInstructions([secp256k1Ix(signedMessage(1, payload))])
+        .rpc();
+
+      expect(await fetchNonce()).to.equal(2);
+    });
+
+    it("Rejects a Secp256k1 signed message with another payload", async () => {
+      await expectError(
+        program.methods
+          .verifySecp256k1Message(new anchor.BN(2), Buffer.from("voucher: 1000 points"))
+          .accounts(accounts)
+          .preInstructions([secp256k1Ix(signedMessage(2, payload))])
+          .rpc(),
+        "Secp256k1Mismatch"
+      );
+    });
+
+    it("Rejects a message without a precompile instruction", async () => {
+      await expectError(
+        program.methods.verifySecp256k1Message(new anchor.BN(2), payload).accounts(accounts).rpc(),
+        "MissingSecp256k1Instruction"
+      );
+    });
+
+    it("Rejects new signers set by another key than the authority", async () => {
+      const intruder = anchor.web3.Keypair.generate();
+
+      await expectError(
+        program.methods
+          .setVerifierSigners(intruder.publicKey, ethAddress)
+          .accounts({ verifier: verifier.publicKey, authority: intruder.publicKey })
+          .signers([intruder])
+          .rpc(),
+        "ConstraintHasOne"
+      );
+    });
+
+    it("Lets the authority replace the Ed25519 signer", async () => {
+      const newSigner = anchor.web3.Keypair.generate();
+
+      await program.methods
+        .setVerifierSigners(newSigner.publicKey, ethAddress)
+        .accounts({ verifier: verifier.publicKey, authority: provider.wallet.publicKey })
+        .rpc();
+
+      const verify = (signer: anchor.web3.Keypair) =>
+        program.methods
+          .verifyEd25519Message(new anchor.BN(2), payload)
+          .accounts(accounts)
+          .preInstructions([ed25519Ix(signer, signedMessage(2, payload))])
+          .rpc();
+
+      // the previous signer is no longer accepted
+      await expectError(verify(ed25519Signer), "Ed25519Mismatch");
+
+      await verify(newSigner);
+      expect(await fetchNonce()).to.equal(3);
+    });
+  });
 });